@@ -414,6 +414,24 @@ impl ProgramArch {
     }
 }
 
+/// Loader to deploy programs with.
+#[derive(ValueEnum, Parser, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProgramLoader {
+    /// Upgradeable BPF loader (loader-v3), used by `solana program`.
+    Upgradeable,
+    /// Loader-v4, used by `solana program-v4`.
+    V4,
+}
+
+impl ProgramLoader {
+    pub fn solana_subcommand(&self) -> &str {
+        match self {
+            Self::Upgradeable => "program",
+            Self::V4 => "program-v4",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BuildConfig {
     pub verifiable: bool,
@@ -1,12 +1,12 @@
 use crate::config::{
     get_default_ledger_path, BootstrapMode, BuildConfig, Config, ConfigOverride, Manifest,
-    PackageManager, ProgramArch, ProgramDeployment, ProgramWorkspace, ScriptsConfig, TestValidator,
-    WithPath, SHUTDOWN_WAIT, STARTUP_WAIT,
+    PackageManager, ProgramArch, ProgramDeployment, ProgramLoader, ProgramWorkspace, ScriptsConfig,
+    TestValidator, WithPath, SHUTDOWN_WAIT, STARTUP_WAIT,
 };
 use anchor_client::Cluster;
 use anchor_lang::idl::{IdlAccount, IdlInstruction, ERASED_AUTHORITY};
 use anchor_lang::prelude::UpgradeableLoaderState;
-use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::{bpf_loader_upgradeable, loader_v4};
 use anchor_lang::{AccountDeserialize, AnchorDeserialize, AnchorSerialize, Discriminator};
 use anchor_lang_idl::convert::convert_idl;
 use anchor_lang_idl::types::{Idl, IdlArrayLen, IdlDefinedFields, IdlType, IdlTypeDefTy};
@@ -257,7 +257,12 @@ pub enum Command {
         /// Don't upload IDL during deployment (IDL is uploaded by default)
         #[clap(long)]
         no_idl: bool,
-        /// Arguments to pass to the underlying `solana program deploy` command.
+        /// Loader to deploy the programs with. Defaults to the loader of the existing program
+        /// account, or the upgradeable loader for new programs.
+        #[clap(value_enum, long)]
+        loader: Option<ProgramLoader>,
+        /// Arguments to pass to the underlying `solana program deploy` (or
+        /// `solana program-v4 deploy`) command.
         #[clap(required = false, last = true)]
         solana_args: Vec<String>,
     },
@@ -275,7 +280,11 @@ pub enum Command {
         /// Max times to retry on failure.
         #[clap(long, default_value = "0")]
         max_retries: u32,
-        /// Arguments to pass to the underlying `solana program deploy` command.
+        /// Loader to upgrade the program with. Defaults to the loader of the program account.
+        #[clap(value_enum, long)]
+        loader: Option<ProgramLoader>,
+        /// Arguments to pass to the underlying `solana program deploy` (or
+        /// `solana program-v4 deploy`) command.
         #[clap(required = false, last = true)]
         solana_args: Vec<String>,
     },
@@ -820,6 +829,7 @@ fn process_command(opts: Opts) -> Result<()> {
             program_keypair,
             verifiable,
             no_idl,
+            loader,
             solana_args,
         } => deploy(
             &opts.cfg_override,
@@ -827,6 +837,7 @@ fn process_command(opts: Opts) -> Result<()> {
             program_keypair,
             verifiable,
             no_idl,
+            loader,
             solana_args,
         ),
         Command::Expand {
@@ -837,12 +848,14 @@ fn process_command(opts: Opts) -> Result<()> {
             program_id,
             program_filepath,
             max_retries,
+            loader,
             solana_args,
         } => upgrade(
             &opts.cfg_override,
             program_id,
             program_filepath,
             max_retries,
+            loader,
             solana_args,
         ),
         Command::Idl { subcmd } => idl(&opts.cfg_override, subcmd),
@@ -2989,7 +3002,7 @@ fn test(
         // In either case, skip the deploy if the user specifies.
         let is_localnet = cfg.provider.cluster == Cluster::Localnet;
         if (!is_localnet || skip_local_validator) && !skip_deploy {
-            deploy(cfg_override, None, None, false, true, vec![])?;
+            deploy(cfg_override, None, None, false, true, None, vec![])?;
         }
         let mut is_first_suite = true;
         if let Some(test_script) = cfg.scripts.get_mut("test") {
@@ -3557,16 +3570,14 @@ fn deploy(
     program_keypair: Option<String>,
     verifiable: bool,
     no_idl: bool,
+    loader: Option<ProgramLoader>,
     solana_args: Vec<String>,
 ) -> Result<()> {
     // Execute the code within the workspace
     with_workspace(cfg_override, |cfg| {
         let url = cluster_url(cfg, &cfg.test_validator);
         let keypair = cfg.provider.wallet.to_string();
-
-        // Augment the given solana args with recommended defaults.
         let client = create_client(&url);
-        let solana_args = add_recommended_deployment_solana_args(&client, solana_args)?;
 
         // Deploy the programs.
         println!("Deploying cluster: {url}");
@@ -3586,18 +3597,30 @@ fn deploy(
                 ),
             };
 
+            let (program_loader, exists) = get_program_loader(&client, &program_id, loader)?;
+            let program_arg = match (program_loader, exists) {
+                // `solana program-v4 deploy` only accepts the program keypair for new programs
+                (ProgramLoader::V4, false) => "--program-keypair",
+                _ => "--program-id",
+            };
+
             // Send deploy transactions using the Solana CLI
             let exit = std::process::Command::new("solana")
-                .arg("program")
+                .arg(program_loader.solana_subcommand())
                 .arg("deploy")
                 .arg("--url")
                 .arg(&url)
                 .arg("--keypair")
                 .arg(&keypair)
-                .arg("--program-id")
+                .arg(program_arg)
                 .arg(strip_workspace_prefix(program_keypair_filepath))
                 .arg(strip_workspace_prefix(binary_path))
-                .args(&solana_args)
+                // Augment the given solana args with recommended defaults.
+                .args(add_recommended_deployment_solana_args(
+                    &client,
+                    program_loader,
+                    solana_args.clone(),
+                )?)
                 .stdout(Stdio::inherit())
                 .stderr(Stdio::inherit())
                 .output()
@@ -3645,6 +3668,7 @@ fn upgrade(
     program_id: Pubkey,
     program_filepath: String,
     max_retries: u32,
+    loader: Option<ProgramLoader>,
     solana_args: Vec<String>,
 ) -> Result<()> {
     let path: PathBuf = program_filepath.parse().unwrap();
@@ -3653,11 +3677,12 @@ fn upgrade(
     with_workspace(cfg_override, |cfg| {
         let url = cluster_url(cfg, &cfg.test_validator);
         let client = create_client(&url);
-        let solana_args = add_recommended_deployment_solana_args(&client, solana_args)?;
+        let (loader, _) = get_program_loader(&client, &program_id, loader)?;
+        let solana_args = add_recommended_deployment_solana_args(&client, loader, solana_args)?;
 
         for retry in 0..(1 + max_retries) {
             let exit = std::process::Command::new("solana")
-                .arg("program")
+                .arg(loader.solana_subcommand())
                 .arg("deploy")
                 .arg("--url")
                 .arg(url.clone())
@@ -3686,6 +3711,29 @@ fn upgrade(
     })
 }
 
+/// Get the loader to deploy the program with and whether the program account exists.
+///
+/// If no loader is given, the loader that owns the existing program account is used so that
+/// deployments don't depend on the default loader of the Solana CLI or the cluster. New programs
+/// are deployed with the upgradeable loader by default.
+fn get_program_loader(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    loader: Option<ProgramLoader>,
+) -> Result<(ProgramLoader, bool)> {
+    let owner = client
+        .get_account_with_commitment(program_id, CommitmentConfig::confirmed())?
+        .value
+        .map(|account| account.owner);
+    let loader = match (loader, owner) {
+        (Some(loader), _) => loader,
+        (None, Some(owner)) if owner == loader_v4::id() => ProgramLoader::V4,
+        (None, _) => ProgramLoader::Upgradeable,
+    };
+
+    Ok((loader, owner.is_some()))
+}
+
 fn create_idl_account(
     cfg: &Config,
     keypair_path: &str,
@@ -4305,8 +4353,14 @@ fn get_node_version() -> Result<Version> {
 
 fn add_recommended_deployment_solana_args(
     client: &RpcClient,
+    loader: ProgramLoader,
     args: Vec<String>,
 ) -> Result<Vec<String>> {
+    // `solana program-v4` doesn't support the same arguments as `solana program`
+    if loader == ProgramLoader::V4 {
+        return Ok(args);
+    }
+
     let mut augmented_args = args.clone();
 
     // If no priority fee is provided, calculate a recommended fee based on recent txs.
@@ -200,6 +200,7 @@ name = "{1}"
 default = []
 cpi = ["no-entrypoint"]
 no-entrypoint = []
+lazy-entrypoint = []
 no-idl = []
 no-log-ix-name = []
 idl-build = ["anchor-lang/idl-build"]
@@ -439,6 +439,58 @@ pub fn handle_system_log(this_program_str: &str, log: &str) -> (Option<String>,
     }
 }
 
+/// Decodes the error thrown by the program with the given id from the logs of a failed
+/// transaction.
+///
+/// The error number is read from the `AnchorError` log line, and the fields of the error from the
+/// `Error Data` line logged right after it, see [`anchor_lang::error::DecodeError`].
+pub fn parse_error<E: anchor_lang::error::DecodeError>(
+    program_id: &Pubkey,
+    logs: &[String],
+) -> Option<E> {
+    use anchor_lang::__private::base64;
+    use anchor_lang::error::ERROR_DATA_LOG_PREFIX;
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    let invoke_re = Regex::new(r"^Program ([1-9A-HJ-NP-Za-km-z]+) invoke \[[\d]+\]$").unwrap();
+    let exit_re = Regex::new(r"^Program ([1-9A-HJ-NP-Za-km-z]+) (success|failed)").unwrap();
+    // Errors returned by a CPI are logged again by the caller, but belong to the callee
+    let error_re =
+        Regex::new(r"^AnchorError (?:occurred|thrown|caused)\b.*Error Number: (\d+)\.").unwrap();
+
+    let program_id_str = program_id.to_string();
+    let mut stack: Vec<&str> = vec![];
+    let mut error = None;
+    for (i, l) in logs.iter().enumerate() {
+        if let Some(c) = invoke_re.captures(l) {
+            stack.push(c.get(1).unwrap().as_str());
+        } else if exit_re.is_match(l) {
+            stack.pop();
+        } else if stack.last() == Some(&program_id_str.as_str()) {
+            let Some(c) = l
+                .strip_prefix(PROGRAM_LOG)
+                .and_then(|l| error_re.captures(l))
+            else {
+                continue;
+            };
+            let Ok(error_code_number) = c[1].parse::<u32>() else {
+                continue;
+            };
+            let error_data = logs
+                .get(i + 1)
+                .and_then(|l| l.strip_prefix(PROGRAM_LOG))
+                .and_then(|l| l.strip_prefix(ERROR_DATA_LOG_PREFIX))
+                .and_then(|data| STANDARD.decode(data).ok())
+                .unwrap_or_default();
+            error = Some((error_code_number, error_data));
+        }
+    }
+
+    let (error_code_number, error_data) = error?;
+    E::decode_error(error_code_number, &error_data)
+}
+
 pub struct Execution {
     stack: Vec<String>,
 }
@@ -501,6 +553,32 @@ pub enum ClientError {
     IOError(#[from] std::io::Error),
 }
 
+impl ClientError {
+    /// Decodes the error thrown by the program with the given id from the simulation logs of a
+    /// transaction that failed preflight checks, see [`parse_error`].
+    pub fn decode_error<E: anchor_lang::error::DecodeError>(
+        &self,
+        program_id: &Pubkey,
+    ) -> Option<E> {
+        use solana_rpc_client_api::client_error::ErrorKind;
+        use solana_rpc_client_api::request::{RpcError, RpcResponseErrorData};
+
+        match self {
+            ClientError::AnchorError(anchor_lang::error::Error::AnchorError(error)) => {
+                error.decode()
+            }
+            ClientError::SolanaClientError(error) => match error.kind() {
+                ErrorKind::RpcError(RpcError::RpcResponseError {
+                    data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+                    ..
+                }) => parse_error(program_id, result.logs.as_deref()?),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 pub trait AsSigner {
     fn as_signer(&self) -> &dyn Signer;
 }
@@ -548,6 +626,61 @@ impl<C: Deref<Target = impl Signer> + Clone, S: AsSigner> RequestBuilder<'_, C,
         self
     }
 
+    /// Add an Ed25519 program instruction that verifies the `signature` of `message` by `pubkey`.
+    ///
+    /// The verification can be checked on-chain with
+    /// [`TransactionInstructions::verify_ed25519`](anchor_lang::introspection::TransactionInstructions::verify_ed25519).
+    ///
+    /// Fails if the message is longer than the instruction can store.
+    pub fn ed25519_verify(
+        mut self,
+        pubkey: &Pubkey,
+        signature: &Signature,
+        message: &[u8],
+    ) -> Result<Self, ClientError> {
+        let signature: &[u8; 64] = signature.as_ref().try_into().unwrap();
+        self.instructions.push(Instruction {
+            program_id: solana_sdk::ed25519_program::ID,
+            accounts: vec![],
+            data: anchor_lang::introspection::ed25519_instruction_data(pubkey, signature, message)?,
+        });
+        Ok(self)
+    }
+
+    /// Add a Secp256k1 program instruction that verifies the `signature` (with its `recovery_id`)
+    /// of `message` by `eth_address`.
+    ///
+    /// The verification can be checked on-chain with
+    /// [`TransactionInstructions::verify_secp256k1`](anchor_lang::introspection::TransactionInstructions::verify_secp256k1).
+    ///
+    /// Fails if the message is longer than the instruction can store, or if the instruction
+    /// would be past the 255th instruction of the transaction.
+    pub fn secp256k1_verify(
+        mut self,
+        eth_address: &[u8; 20],
+        signature: &[u8; 64],
+        recovery_id: u8,
+        message: &[u8],
+    ) -> Result<Self, ClientError> {
+        let instruction_index = u8::try_from(self.instructions.len()).map_err(|_| {
+            anchor_lang::error::Error::from(
+                anchor_lang::error::ErrorCode::InstructionDidNotSerialize,
+            )
+        })?;
+        self.instructions.push(Instruction {
+            program_id: solana_sdk::secp256k1_program::ID,
+            accounts: vec![],
+            data: anchor_lang::introspection::secp256k1_instruction_data(
+                eth_address,
+                signature,
+                recovery_id,
+                message,
+                instruction_index,
+            )?,
+        });
+        Ok(self)
+    }
+
     #[must_use]
     pub fn program(mut self, program_id: Pubkey) -> Self {
         self.program_id = program_id;
@@ -920,4 +1053,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[error_code]
+    pub enum MockError {
+        Plain,
+        Insufficient { needed: u64 },
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use std::str::FromStr;
+
+        let caller = Pubkey::from_str("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS").unwrap();
+        let callee = Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").unwrap();
+        let logs = [
+            "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS invoke [1]",
+            "Program TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb invoke [2]",
+            "Program log: AnchorError occurred. Error Code: Plain. Error Number: 6000. Error Message: Plain.",
+            "Program TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb consumed 1411 of 200000 compute units",
+            "Program TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb failed: custom program error: 0x1770",
+            "Program log: AnchorError returned by CPI to program TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb in src/lib.rs:8. Error Code: Plain. Error Number: 6000. Error Message: Plain.",
+            "Program log: AnchorError thrown in src/lib.rs:10. Error Code: Insufficient. Error Number: 6001. Error Message: Insufficient.",
+            "Program log: Error Data: CgAAAAAAAAA=",
+            "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS consumed 2822 of 200000 compute units",
+            "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS failed: custom program error: 0x1771",
+        ];
+        let logs: Vec<String> = logs.iter().map(|&l| l.to_string()).collect();
+
+        assert!(matches!(
+            parse_error::<MockError>(&caller, &logs),
+            Some(MockError::Insufficient { needed: 10 })
+        ));
+        assert!(matches!(
+            parse_error::<MockError>(&callee, &logs),
+            Some(MockError::Plain)
+        ));
+        assert!(parse_error::<MockError>(&Pubkey::default(), &logs).is_none());
+    }
 }
@@ -66,8 +66,65 @@ pub struct IdlInstruction {
     pub args: Vec<IdlField>,
     #[serde(skip_serializing_if = "is_default")]
     pub returns: Option<IdlType>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub caller: Option<IdlInstructionCaller>,
+    #[serde(default, skip_serializing_if = "is_default", rename = "transferHook")]
+    pub transfer_hook: Option<IdlTransferHook>,
+}
+
+/// Restriction on how an instruction can be called.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlInstructionCaller {
+    /// The instruction must be a top-level instruction of the transaction, not called via CPI.
+    Transaction,
+    /// The instruction must be called via CPI from a top-level instruction of one of the programs.
+    Cpi { programs: Vec<String> },
+}
+
+/// Extra accounts of a transfer hook `Execute` instruction, which the token program resolves from
+/// the extra account meta list of the mint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdlTransferHook {
+    pub accounts: Vec<IdlExtraAccount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdlExtraAccount {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub writable: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub signer: bool,
+    #[serde(skip_serializing_if = "is_default")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "is_default")]
+    pub pda: Option<IdlExtraAccountPda>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdlExtraAccountPda {
+    pub seeds: Vec<IdlExtraAccountSeed>,
+    /// Index of the account of the program that owns the PDA. `None` for PDAs of the transfer
+    /// hook program.
+    #[serde(skip_serializing_if = "is_default")]
+    pub program: Option<u8>,
+}
+
+/// Seed of an extra account PDA, with indices referring to the accounts and the data of the
+/// `Execute` instruction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlExtraAccountSeed {
+    /// Constant bytes.
+    Const { value: Vec<u8> },
+    /// Slice of the instruction data.
+    Data { index: u8, length: u8 },
+    /// Key of an account.
+    Account { index: u8 },
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum IdlInstructionAccountItem {
@@ -92,6 +149,51 @@ pub struct IdlInstructionAccount {
     pub pda: Option<IdlPda>,
     #[serde(default, skip_serializing_if = "is_default")]
     pub relations: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub remaining: Option<IdlRemainingAccounts>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub signers: Option<IdlSigners>,
+}
+
+/// A list of accounts that is passed in place of a single account, with each of them having the
+/// properties of the enclosing [`IdlInstructionAccount`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdlRemainingAccounts {
+    /// Number of accounts in the list. `None` if the list consumes all of the accounts left, or
+    /// if the length can't be expressed in the IDL.
+    #[serde(skip_serializing_if = "is_default")]
+    pub len: Option<IdlRemainingAccountsLen>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlRemainingAccountsLen {
+    /// Fixed number of accounts.
+    Const { value: u64 },
+    /// Number of accounts given by an instruction argument.
+    Arg { path: String },
+}
+
+/// Signers expected by the account, of which at least `threshold` distinct ones must sign the
+/// transaction, as one of the accounts of the instruction or as a remaining account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdlSigners {
+    /// Path to the list of expected signers, e.g. `multisig.owners`.
+    pub path: String,
+    /// Minimum number of signers. `None` if it can't be expressed in the IDL.
+    #[serde(skip_serializing_if = "is_default")]
+    pub threshold: Option<IdlSignersThreshold>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlSignersThreshold {
+    /// Fixed number of signers.
+    Const { value: u64 },
+    /// Number of signers given by an instruction argument.
+    Arg { path: String },
+    /// Number of signers stored in an account, e.g. `multisig.threshold`.
+    Account { path: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -154,12 +256,14 @@ pub struct IdlConst {
     pub value: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct IdlErrorCode {
     pub code: u32,
     pub name: String,
     #[serde(skip_serializing_if = "is_default")]
     pub msg: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub fields: Option<IdlDefinedFields>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
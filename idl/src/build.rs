@@ -9,7 +9,10 @@ use anyhow::{anyhow, Result};
 use regex::Regex;
 use serde::Deserialize;
 
-use crate::types::{Idl, IdlEvent, IdlTypeDef};
+use crate::types::{
+    Idl, IdlAccount, IdlErrorCode, IdlEvent, IdlInstructionAccountItem, IdlTransferHook,
+    IdlTypeDef,
+};
 
 /// A trait that types must implement in order to include the type in the IDL definition.
 ///
@@ -43,6 +46,25 @@ pub trait IdlBuild {
     }
 }
 
+/// A trait that accounts structs must implement in order to include their accounts in the IDL
+/// definition of an instruction.
+///
+/// This trait is automatically implemented for all structs that use the `Accounts` derive macro.
+pub trait IdlBuildAccounts {
+    /// Create the IDL accounts of the struct, and insert the account types that are used by the
+    /// struct to the given maps.
+    fn create_accounts(
+        accounts: &mut BTreeMap<String, IdlAccount>,
+        types: &mut BTreeMap<String, IdlTypeDef>,
+    ) -> Vec<IdlInstructionAccountItem>;
+
+    /// Create the extra accounts of the struct if it is a transfer hook `Execute` accounts
+    /// struct.
+    fn create_transfer_hook() -> Option<IdlTransferHook> {
+        None
+    }
+}
+
 /// IDL builder using builder pattern.
 ///
 /// # Example
@@ -260,7 +282,27 @@ fn build(
             }
             State::Errors(lines) => {
                 if line == "--- IDL end errors ---" {
-                    error_codes = serde_json::from_str(&lines.join("\n"))?;
+                    #[derive(Deserialize)]
+                    #[serde(untagged)]
+                    enum IdlBuildErrorsPrint {
+                        WithTypes {
+                            errors: Vec<IdlErrorCode>,
+                            types: Vec<IdlTypeDef>,
+                        },
+                        // Printed without defined types, and by older versions of `anchor-lang`
+                        Errors(Vec<IdlErrorCode>),
+                    }
+
+                    match serde_json::from_str(&lines.join("\n"))? {
+                        IdlBuildErrorsPrint::WithTypes {
+                            errors,
+                            types: error_types,
+                        } => {
+                            error_codes = errors;
+                            types.extend(error_types.into_iter().map(|ty| (ty.name.clone(), ty)));
+                        }
+                        IdlBuildErrorsPrint::Errors(errors) => error_codes = errors,
+                    }
                     state = State::Pass;
                     continue;
                 }
@@ -334,6 +334,8 @@ mod legacy {
                 accounts: value.accounts.into_iter().map(Into::into).collect(),
                 args: value.args.into_iter().map(Into::into).collect(),
                 returns: value.returns.map(|r| r.into()),
+                caller: None,
+                transfer_hook: None,
             }
         }
     }
@@ -362,6 +364,7 @@ mod legacy {
                 name: value.name,
                 code: value.code,
                 msg: value.msg,
+                fields: None,
             }
         }
     }
@@ -528,6 +531,8 @@ mod legacy {
                         .transpose()
                         .unwrap_or_default(),
                     relations: acc.relations,
+                    remaining: Default::default(),
+                    signers: Default::default(),
                 }),
                 IdlAccountItem::IdlAccounts(accs) => Self::Composite(t::IdlInstructionAccounts {
                     name: accs.name.to_snake_case(),
@@ -1,33 +1,51 @@
 use proc_macro2::{Literal, TokenStream};
 use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, spanned::Spanned};
+
+pub fn gen_lazy(item: &syn::Item, is_zero_copy: bool) -> syn::Result<TokenStream> {
+    match item {
+        syn::Item::Struct(strct) if is_zero_copy => gen_lazy_zero_copy(strct),
+        syn::Item::Struct(strct) => gen_lazy_struct(strct),
+        syn::Item::Enum(enm) => gen_lazy_enum(enm),
+        _ => Err(syn::Error::new(item.span(), "Expected a struct or an enum")),
+    }
+}
 
-pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
-    let ident = &strct.ident;
-    let lazy_ident = format_ident!("Lazy{}", ident);
+fn gen_lazy_struct(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
+    let lazy_ident = format_ident!("Lazy{}", strct.ident);
     let load_common_ident = to_private_ident("load_common");
     let initialize_fields = to_private_ident("initialize_fields");
     let lazy_acc_ty = quote! { anchor_lang::accounts::lazy_account::LazyAccount };
+    let LazyGenerics {
+        ident,
+        trait_generics,
+        trait_args,
+        impl_generics,
+        where_clause,
+    } = LazyGenerics::new(
+        &strct.ident,
+        &strct.generics,
+        strct.fields.iter().map(|field| &field.ty),
+    );
     let disc_len = quote! { <#ident as anchor_lang::Discriminator>::DISCRIMINATOR.len() };
 
-    let load_common_docs = quote! {
-        /// The deserialized value is cached for future uses i.e. all subsequent calls to this
-        /// method do not deserialize the data again, instead, they return the cached value.
-        ///
-        /// To reload the data from the underlying account info (e.g. after a CPI call), run
-        /// [`LazyAccount::unload`] before running this method.
-        ///
-        /// See [`LazyAccount`]'s documentation for more information.
-    };
-    let load_panic_docs = quote! {
-        /// # Panics
-        ///
-        /// If there is an existing mutable reference crated by any of the `load_mut` methods.
-    };
-    let load_mut_panic_docs = quote! {
-        /// # Panics
-        ///
-        /// If there is an existing reference (mutable or not) created by any of the `load` methods.
-    };
+    let load_common_docs = load_common_docs();
+    let load_panic_docs = load_panic_docs();
+    let load_mut_panic_docs = load_mut_panic_docs();
+
+    // End of the serialized data, i.e. the last field's offset + size
+    let end = strct
+        .fields
+        .iter()
+        .enumerate()
+        .last()
+        .map(|(i, field)| {
+            let field_ident = to_field_ident(field, i);
+            let offset_of_ident = to_private_ident(format!("offset_of_{field_ident}"));
+            let size_of_ident = to_private_ident(format!("size_of_{field_ident}"));
+            quote! { self.#offset_of_ident() + self.#size_of_ident() }
+        })
+        .unwrap_or_else(|| quote!(#disc_len));
 
     let (loader_signatures, loader_impls) = strct
         .fields
@@ -40,8 +58,11 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
             let load_common_ident = to_private_ident(format!("load_common_{field_ident}"));
             let offset_of_ident = to_private_ident(format!("offset_of_{field_ident}"));
             let size_of_ident = to_private_ident(format!("size_of_{field_ident}"));
+            let write_ident = to_private_ident(format!("write_{field_ident}"));
 
-            let offset = i.eq(&0).then(|| quote!(#disc_len)).unwrap_or_else(|| {
+            let offset = if i == 0 {
+                quote!(#disc_len)
+            } else {
                 // Current offset is the previous field's offset + size
                 strct
                     .fields
@@ -54,7 +75,7 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
                         quote! { self.#offset_of_ident() + self.#size_of_ident() }
                     })
                     .expect("Previous field should always exist when i > 0")
-            });
+            };
 
             let ty = &field.ty;
             let ty_as_lazy = quote! { <#ty as anchor_lang::__private::Lazy> };
@@ -90,13 +111,16 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
 
                  #[doc(hidden)]
                 fn #size_of_ident(&self) -> usize;
+
+                #[doc(hidden)]
+                fn #write_ident(&self) -> anchor_lang::Result<()>;
             };
 
             let impls = quote! {
                 fn #load_ident(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ty>> {
                     self.#load_common_ident(|| {
                         // SAFETY: The common load method makes sure the field is initialized.
-                        ::core::cell::Ref::map(self.__account.borrow(), |acc| unsafe {
+                        ::core::cell::Ref::map(self.__account().borrow(), |acc| unsafe {
                             &*::core::ptr::addr_of!((*acc.as_ptr()).#field_ident)
                         })
                     })
@@ -104,8 +128,11 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
 
                 fn #load_mut_ident(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ty>> {
                     self.#load_common_ident(|| {
+                        // Only the fields loaded mutably are written back on exit
+                        self.__dirty.borrow_mut().as_mut().unwrap()[#i] = true;
+
                         // SAFETY: The common load method makes sure the field is initialized.
-                        ::core::cell::RefMut::map(self.__account.borrow_mut(), |acc| unsafe {
+                        ::core::cell::RefMut::map(self.__account().borrow_mut(), |acc| unsafe {
                             &mut *::core::ptr::addr_of_mut!((*acc.as_mut_ptr()).#field_ident)
                         })
                     })
@@ -129,7 +156,7 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
                     )?;
                     unsafe {
                         ::core::ptr::addr_of_mut!(
-                            (*self.__account.borrow_mut().as_mut_ptr()).#field_ident
+                            (*self.__account().borrow_mut().as_mut_ptr()).#field_ident
                         ).write(val)
                      };
 
@@ -150,6 +177,42 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
                 fn #size_of_ident(&self) -> usize {
                     #size
                 }
+
+                #[inline(never)]
+                fn #write_ident(&self) -> anchor_lang::Result<()> {
+                    let offset = self.#offset_of_ident();
+                    let size = self.#size_of_ident();
+                    let acc = self.__account().borrow();
+                    // SAFETY: Fields are initialized before being marked as dirty.
+                    let val = unsafe { &*::core::ptr::addr_of!((*acc.as_ptr()).#field_ident) };
+
+                    // Sized fields are written in place
+                    if #ty_as_lazy::SIZED {
+                        let mut data = self.__info.try_borrow_mut_data()?;
+                        let dst: &mut [u8] = &mut data[offset..offset + size];
+                        let mut writer = anchor_lang::__private::BpfWriter::new(dst);
+                        return anchor_lang::AnchorSerialize::serialize(val, &mut writer)
+                            .map_err(Into::into);
+                    }
+
+                    // Unsized fields shift the data after them if their size has changed
+                    let end = #end;
+                    let val = anchor_lang::AnchorSerialize::try_to_vec(val)?;
+                    let mut data = self.__info.try_borrow_mut_data()?;
+                    let new_end = end - size + val.len();
+                    if new_end > data.len() {
+                        return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
+                    }
+                    if val.len() != size {
+                        data.copy_within(offset + size..end, offset + val.len());
+                        if new_end < end {
+                            data[new_end..end].fill(0);
+                        }
+                    }
+                    data[offset..offset + val.len()].copy_from_slice(&val);
+
+                    Ok(())
+                }
             };
 
             Ok((signatures, impls))
@@ -165,9 +228,15 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
         .map(|(i, field)| to_field_ident(field, i))
         .map(|field| format_ident!("load_{field}"));
     let total_fields = strct.fields.len();
+    let field_indices = 0..total_fields;
+    let write_idents = strct
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| to_private_ident(format!("write_{}", to_field_ident(field, i))));
 
     Ok(quote! {
-        pub trait #lazy_ident {
+        pub trait #lazy_ident #trait_generics {
             /// Load a reference to the entire account.
             ///
             #load_common_docs
@@ -195,11 +264,11 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
             fn exit(&self, program_id: &anchor_lang::prelude::Pubkey) -> anchor_lang::Result<()>;
         }
 
-        impl<'info> #lazy_ident for #lazy_acc_ty<'info, #ident> {
+        impl #impl_generics #lazy_ident #trait_args for #lazy_acc_ty<'info, #ident> #where_clause {
             fn load(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ident>> {
                 self.#load_common_ident(|| {
                     // SAFETY: The common load method makes sure all fields are initialized.
-                    ::core::cell::Ref::map(self.__account.borrow(), |acc| unsafe {
+                    ::core::cell::Ref::map(self.__account().borrow(), |acc| unsafe {
                         acc.assume_init_ref()
                     })
                 })
@@ -207,8 +276,12 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
 
             fn load_mut(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ident>> {
                 self.#load_common_ident(|| {
+                    for dirty in self.__dirty.borrow_mut().as_mut().unwrap() {
+                        *dirty = true;
+                    }
+
                     // SAFETY: The common load method makes sure all fields are initialized.
-                    ::core::cell::RefMut::map(self.__account.borrow_mut(), |acc| unsafe {
+                    ::core::cell::RefMut::map(self.__account().borrow_mut(), |acc| unsafe {
                         acc.assume_init_mut()
                     })
                 })
@@ -235,7 +308,7 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
                     let offset = #disc_len;
                     let mut data = self.__info.data.borrow();
                     let val = anchor_lang::AnchorDeserialize::deserialize(&mut &data[offset..])?;
-                    unsafe { self.__account.borrow_mut().as_mut_ptr().write(val) };
+                    unsafe { self.__account().borrow_mut().as_mut_ptr().write(val) };
 
                     // Set fields to initialized
                     let mut fields = self.__fields.borrow_mut();
@@ -263,23 +336,278 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
             fn #initialize_fields(&self) {
                 if self.__fields.borrow().is_none() {
                     *self.__fields.borrow_mut() = Some(vec![false; #total_fields]);
+                    *self.__dirty.borrow_mut() = Some(vec![false; #total_fields]);
                 }
             }
 
-            // TODO: This method can be optimized to *only* serialize the fields that we have
-            // initialized rather than deserializing the whole account, and then serializing it
-            // back, which consumes a lot more CUs than it should for most accounts.
             fn exit(&self, program_id: &anchor_lang::prelude::Pubkey) -> anchor_lang::Result<()> {
                 // Only persist if the owner is the current program and the account is not closed
                 if &<#ident as anchor_lang::Owner>::owner() == program_id
                     && !anchor_lang::__private::is_closed(self.__info)
                 {
-                    // Make sure all fields are initialized
-                    let acc = self.load()?;
+                    let dirty = self.__dirty.borrow();
+                    let dirty = match dirty.as_ref() {
+                        Some(dirty) => dirty,
+                        None => &[] as &[bool],
+                    };
+                    let disc = <#ident as anchor_lang::Discriminator>::DISCRIMINATOR;
+                    let is_initialized = self.__info.data.borrow().starts_with(disc);
+
+                    if !is_initialized || (!dirty.is_empty() && !dirty.contains(&false)) {
+                        // Serialize the whole account, e.g. after `init` or `load_mut`
+                        let acc = self.load()?;
+                        let mut data = self.__info.try_borrow_mut_data()?;
+                        let dst: &mut [u8] = &mut data;
+                        let mut writer = anchor_lang::__private::BpfWriter::new(dst);
+                        acc.try_serialize(&mut writer)?;
+                    } else {
+                        // Only write the fields that have been loaded mutably
+                        #(if dirty[#field_indices] {
+                            self.#write_idents()?;
+                        })*
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Enums are deserialized as a whole, but the variant can be loaded without deserializing its
+/// fields.
+fn gen_lazy_enum(enm: &syn::ItemEnum) -> syn::Result<TokenStream> {
+    let lazy_ident = format_ident!("Lazy{}", enm.ident);
+    let load_common_ident = to_private_ident("load_common");
+    let lazy_acc_ty = quote! { anchor_lang::accounts::lazy_account::LazyAccount };
+    let LazyGenerics {
+        ident,
+        trait_generics,
+        trait_args,
+        impl_generics,
+        where_clause,
+    } = LazyGenerics::new(
+        &enm.ident,
+        &enm.generics,
+        enm.variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().map(|field| &field.ty)),
+    );
+    let disc_len = quote! { <#ident as anchor_lang::Discriminator>::DISCRIMINATOR.len() };
+    let load_common_docs = load_common_docs();
+    let load_panic_docs = load_panic_docs();
+    let load_mut_panic_docs = load_mut_panic_docs();
+
+    Ok(quote! {
+        pub trait #lazy_ident #trait_generics {
+            /// Load the index of the variant of the account, without deserializing the fields
+            /// of the variant.
+            fn load_variant(&self) -> anchor_lang::Result<u8>;
+
+            /// Load a reference to the entire account.
+            ///
+            #load_common_docs
+            ///
+            #load_panic_docs
+            fn load(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ident>>;
+
+            /// Load a mutable reference to the entire account.
+            ///
+            #load_common_docs
+            ///
+            #load_mut_panic_docs
+            fn load_mut(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ident>>;
+
+            #[doc(hidden)]
+            fn #load_common_ident<R>(&self, f: impl FnOnce() -> R) -> anchor_lang::Result<R>;
+
+            /// Run the exit routine of the account, similar to [`AccountsExit`] but implemented
+            /// as a regular method because we can't implement external traits for external structs.
+            fn exit(&self, program_id: &anchor_lang::prelude::Pubkey) -> anchor_lang::Result<()>;
+        }
+
+        impl #impl_generics #lazy_ident #trait_args for #lazy_acc_ty<'info, #ident> #where_clause {
+            fn load_variant(&self) -> anchor_lang::Result<u8> {
+                self.__info
+                    .try_borrow_data()?
+                    .get(#disc_len)
+                    .copied()
+                    .ok_or_else(|| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+            }
+
+            fn load(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ident>> {
+                self.#load_common_ident(|| {
+                    // SAFETY: The common load method makes sure the account is initialized.
+                    ::core::cell::Ref::map(self.__account().borrow(), |acc| unsafe {
+                        acc.assume_init_ref()
+                    })
+                })
+            }
+
+            fn load_mut(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ident>> {
+                self.#load_common_ident(|| {
+                    self.__dirty.borrow_mut().as_mut().unwrap()[0] = true;
+
+                    // SAFETY: The common load method makes sure the account is initialized.
+                    ::core::cell::RefMut::map(self.__account().borrow_mut(), |acc| unsafe {
+                        acc.assume_init_mut()
+                    })
+                })
+            }
+
+            #[inline(never)]
+            fn #load_common_ident<R>(&self, f: impl FnOnce() -> R) -> anchor_lang::Result<R> {
+                if self.__fields.borrow().is_none() {
+                    *self.__fields.borrow_mut() = Some(vec![false]);
+                    *self.__dirty.borrow_mut() = Some(vec![false]);
+                }
+
+                // Return early if initialized
+                if self.__fields.borrow().as_ref().unwrap()[0] {
+                    return Ok(f());
+                }
+
+                // Deserialize and write
+                let offset = #disc_len;
+                let data = self.__info.data.borrow();
+                let val = anchor_lang::AnchorDeserialize::deserialize(&mut &data[offset..])?;
+                unsafe { self.__account().borrow_mut().as_mut_ptr().write(val) };
+
+                // Set initialized
+                self.__fields.borrow_mut().as_mut().unwrap()[0] = true;
+
+                Ok(f())
+            }
+
+            fn exit(&self, program_id: &anchor_lang::prelude::Pubkey) -> anchor_lang::Result<()> {
+                // Only persist if the owner is the current program and the account is not closed
+                if &<#ident as anchor_lang::Owner>::owner() == program_id
+                    && !anchor_lang::__private::is_closed(self.__info)
+                {
+                    let is_dirty = self
+                        .__dirty
+                        .borrow()
+                        .as_ref()
+                        .map(|dirty| dirty[0])
+                        .unwrap_or_default();
+                    let disc = <#ident as anchor_lang::Discriminator>::DISCRIMINATOR;
+                    let is_initialized = self.__info.data.borrow().starts_with(disc);
+
+                    // Serialize the whole account if it's been loaded mutably or initialized
+                    if is_dirty || !is_initialized {
+                        let acc = self.load()?;
+                        let mut data = self.__info.try_borrow_mut_data()?;
+                        let dst: &mut [u8] = &mut data;
+                        let mut writer = anchor_lang::__private::BpfWriter::new(dst);
+                        acc.try_serialize(&mut writer)?;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Zero-copy accounts are not deserialized, the `load` methods return references to the account
+/// data instead, similar to `AccountLoader`.
+fn gen_lazy_zero_copy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
+    let lazy_ident = format_ident!("Lazy{}", strct.ident);
+    let lazy_acc_ty = quote! { anchor_lang::accounts::lazy_account::LazyAccount };
+    let LazyGenerics {
+        ident,
+        trait_generics,
+        trait_args,
+        impl_generics,
+        where_clause,
+    } = LazyGenerics::new(&strct.ident, &strct.generics, std::iter::empty());
+    let disc_len = quote! { <#ident as anchor_lang::Discriminator>::DISCRIMINATOR.len() };
+
+    let (loader_signatures, loader_impls) = strct
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let field_ident = to_field_ident(field, i);
+            let load_ident = format_ident!("load_{field_ident}");
+            let load_mut_ident = format_ident!("load_mut_{field_ident}");
+            let ty = &field.ty;
+
+            let signatures = quote! {
+                /// Load a reference to the field.
+                fn #load_ident(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ty>>;
+
+                /// Load a mutable reference to the field.
+                fn #load_mut_ident(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ty>>;
+            };
+            let impls = quote! {
+                fn #load_ident(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ty>> {
+                    self.load().map(|acc| ::core::cell::Ref::map(acc, |acc| &acc.#field_ident))
+                }
+
+                fn #load_mut_ident(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ty>> {
+                    self.load_mut()
+                        .map(|acc| ::core::cell::RefMut::map(acc, |acc| &mut acc.#field_ident))
+                }
+            };
+
+            (signatures, impls)
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    Ok(quote! {
+        pub trait #lazy_ident #trait_generics {
+            /// Load a reference to the account data.
+            fn load(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ident>>;
+
+            /// Load a mutable reference to the account data.
+            fn load_mut(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ident>>;
+
+            #(#loader_signatures)*
+
+            /// Run the exit routine of the account, similar to [`AccountsExit`] but implemented
+            /// as a regular method because we can't implement external traits for external structs.
+            fn exit(&self, program_id: &anchor_lang::prelude::Pubkey) -> anchor_lang::Result<()>;
+        }
+
+        impl #impl_generics #lazy_ident #trait_args for #lazy_acc_ty<'info, #ident> #where_clause {
+            fn load(&self) -> anchor_lang::Result<::core::cell::Ref<'_, #ident>> {
+                let data = self.__info.try_borrow_data()?;
+                let offset = #disc_len;
+                Ok(::core::cell::Ref::map(data, |data| {
+                    anchor_lang::__private::bytemuck::from_bytes(
+                        &data[offset..offset + ::core::mem::size_of::<#ident>()],
+                    )
+                }))
+            }
+
+            fn load_mut(&self) -> anchor_lang::Result<::core::cell::RefMut<'_, #ident>> {
+                // `AccountInfo` allows borrowing the data mutably even if the account is not
+                // writable, so check it for a better error
+                if !self.__info.is_writable {
+                    return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+                }
+
+                let data = self.__info.try_borrow_mut_data()?;
+                let offset = #disc_len;
+                Ok(::core::cell::RefMut::map(data, |data| {
+                    anchor_lang::__private::bytemuck::from_bytes_mut(
+                        &mut data[offset..offset + ::core::mem::size_of::<#ident>()],
+                    )
+                }))
+            }
+
+            #(#loader_impls)*
+
+            fn exit(&self, program_id: &anchor_lang::prelude::Pubkey) -> anchor_lang::Result<()> {
+                // The data is written in place, only the discriminator needs to be written e.g.
+                // after `init`
+                if &<#ident as anchor_lang::Owner>::owner() == program_id
+                    && !anchor_lang::__private::is_closed(self.__info)
+                {
+                    let disc = <#ident as anchor_lang::Discriminator>::DISCRIMINATOR;
                     let mut data = self.__info.try_borrow_mut_data()?;
-                    let dst: &mut [u8] = &mut data;
-                    let mut writer = anchor_lang::__private::BpfWriter::new(dst);
-                    acc.try_serialize(&mut writer)?;
+                    data[..disc.len()].copy_from_slice(disc);
                 }
 
                 Ok(())
@@ -288,6 +616,52 @@ pub fn gen_lazy(strct: &syn::ItemStruct) -> syn::Result<TokenStream> {
     })
 }
 
+/// Generics of the generated trait and its implementation for `LazyAccount`.
+struct LazyGenerics {
+    /// Account type, including the generic arguments
+    ident: TokenStream,
+    /// Generic parameters of the trait, e.g. `<T: Bound>`
+    trait_generics: TokenStream,
+    /// Generic arguments of the trait, e.g. `<T>`
+    trait_args: TokenStream,
+    /// Generic parameters of the implementation, i.e. the trait's with the `'info` lifetime
+    impl_generics: TokenStream,
+    /// Where clause of the implementation
+    where_clause: TokenStream,
+}
+
+impl LazyGenerics {
+    /// Create the generics from the account's generics. The given field types are required to
+    /// implement `Lazy` when the account has type parameters.
+    fn new<'a>(
+        ident: &syn::Ident,
+        generics: &syn::Generics,
+        field_tys: impl Iterator<Item = &'a syn::Type>,
+    ) -> Self {
+        let (trait_generics, trait_args, trait_where_clause) = generics.split_for_impl();
+
+        let mut impl_generics = generics.clone();
+        impl_generics.params.insert(0, parse_quote!('info));
+        if generics.type_params().next().is_some() {
+            let where_clause = impl_generics.make_where_clause();
+            for ty in field_tys {
+                where_clause.predicates.push(parse_quote! {
+                    #ty: anchor_lang::__private::Lazy + anchor_lang::AnchorSerialize
+                });
+            }
+        }
+        let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+
+        Self {
+            ident: quote! { #ident #trait_args },
+            trait_generics: quote! { #trait_generics #trait_where_clause },
+            trait_args: trait_args.to_token_stream(),
+            impl_generics: impl_generics.to_token_stream(),
+            where_clause: where_clause.to_token_stream(),
+        }
+    }
+}
+
 /// Get the field's ident and if the ident doesn't exist (e.g. for tuple structs), default to the
 /// given index.
 fn to_field_ident(field: &syn::Field, i: usize) -> TokenStream {
@@ -304,3 +678,31 @@ fn to_field_ident(field: &syn::Field, i: usize) -> TokenStream {
 fn to_private_ident<S: AsRef<str>>(ident: S) -> syn::Ident {
     format_ident!("__{}", ident.as_ref())
 }
+
+fn load_common_docs() -> TokenStream {
+    quote! {
+        /// The deserialized value is cached for future uses i.e. all subsequent calls to this
+        /// method do not deserialize the data again, instead, they return the cached value.
+        ///
+        /// To reload the data from the underlying account info (e.g. after a CPI call), run
+        /// [`LazyAccount::unload`] before running this method.
+        ///
+        /// See [`LazyAccount`]'s documentation for more information.
+    }
+}
+
+fn load_panic_docs() -> TokenStream {
+    quote! {
+        /// # Panics
+        ///
+        /// If there is an existing mutable reference crated by any of the `load_mut` methods.
+    }
+}
+
+fn load_mut_panic_docs() -> TokenStream {
+    quote! {
+        /// # Panics
+        ///
+        /// If there is an existing reference (mutable or not) created by any of the `load` methods.
+    }
+}
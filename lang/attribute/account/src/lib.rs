@@ -17,6 +17,9 @@ mod lazy;
 
 /// An attribute for a data structure representing a Solana account.
 ///
+/// Both structs and enums are supported, with the exception of zero copy
+/// accounts which must be structs.
+///
 /// `#[account]` generates trait implementations for the following traits:
 ///
 /// - [`AccountSerialize`](./trait.AccountSerialize.html)
@@ -53,6 +56,30 @@ mod lazy;
 ///     - `discriminator = MY_DISC`
 ///     - `discriminator = get_disc(...)`
 ///
+/// - `serializer`: Use a serialization format other than Borsh, see
+///   [`AccountSerializer`](./serializer/trait.AccountSerializer.html)
+///
+///     **Usage:** `serializer = <PATH>`
+///
+///     The account must not derive `AnchorSerialize` and `AnchorDeserialize`, and it can't be used with `zero_copy` or `LazyAccount`.
+///
+///     **Examples:**
+///
+///     - `serializer = anchor_lang::serializer::Bincode` (requires the `serde` feature of `anchor-lang`)
+///     - `serializer = MyCodec`
+///
+/// - `tombstone`: Allow closing the account with `close = <TARGET>, tombstone`, see
+///   [`Tombstone`](./trait.Tombstone.html)
+///
+///     **Usage:** `tombstone` or `tombstone = <CONST_EXPR>`
+///
+///     The tombstone defaults to `CLOSED_ACCOUNT_DISCRIMINATOR`, and deserializing tombstoned data fails with `AccountTombstoned`.
+///
+///     **Examples:**
+///
+///     - `tombstone`
+///     - `tombstone = [0xde, 0xad]`
+///
 /// # Zero Copy Deserialization
 ///
 /// **WARNING**: Zero copy deserialization is an experimental feature. It's
@@ -98,15 +125,35 @@ pub fn account(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let args = parse_macro_input!(args as AccountArgs);
+    let mut args = parse_macro_input!(args as AccountArgs);
     let namespace = args.namespace.unwrap_or_default();
     let is_zero_copy = args.zero_copy.is_some();
     let unsafe_bytemuck = args.zero_copy.unwrap_or_default();
 
-    let account_strct = parse_macro_input!(input as syn::ItemStruct);
-    let account_name = &account_strct.ident;
+    let account_item = parse_macro_input!(input as syn::Item);
+    let (account_name, account_generics) = match &account_item {
+        syn::Item::Struct(strct) => (&strct.ident, &strct.generics),
+        syn::Item::Enum(enm) if !is_zero_copy => (&enm.ident, &enm.generics),
+        _ => {
+            return syn::Error::new_spanned(
+                &account_item,
+                "Accounts must be structs, or enums without `zero_copy`",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
     let account_name_str = account_name.to_string();
-    let (impl_gen, type_gen, where_clause) = account_strct.generics.split_for_impl();
+    let (impl_gen, type_gen, where_clause) = account_generics.split_for_impl();
+
+    let serializer = args.overrides.as_mut().and_then(|ov| ov.serializer.take());
+    if let Some(serializer) = serializer.as_ref().filter(|_| is_zero_copy) {
+        return syn::Error::new_spanned(serializer, "`serializer` cannot be used with `zero_copy`")
+            .to_compile_error()
+            .into();
+    }
+
+    let tombstone = args.overrides.as_mut().and_then(|ov| ov.tombstone.take());
 
     let discriminator = args
         .overrides
@@ -121,12 +168,39 @@ pub fn account(
 
             gen_discriminator(namespace, account_name)
         });
-    let disc = if account_strct.generics.lt_token.is_some() {
+    let disc = if account_generics.lt_token.is_some() {
         quote! { #account_name::#type_gen::DISCRIMINATOR }
     } else {
         quote! { #account_name::DISCRIMINATOR }
     };
 
+    let (tombstone_impl, tombstone_check) = match &tombstone {
+        Some(tombstone) => {
+            let name = format!("account `{account_name}`");
+            (
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_gen anchor_lang::Tombstone for #account_name #type_gen #where_clause {
+                        const TOMBSTONE: &'static [u8] = {
+                            anchor_lang::__private::check_tombstone(
+                                #name,
+                                <Self as anchor_lang::Discriminator>::DISCRIMINATOR,
+                                #tombstone,
+                            );
+                            #tombstone
+                        };
+                    }
+                },
+                quote! {
+                    if buf.starts_with(<Self as anchor_lang::Tombstone>::TOMBSTONE) {
+                        return Err(anchor_lang::error!(anchor_lang::error::ErrorCode::AccountTombstoned).with_account_name(#account_name_str));
+                    }
+                },
+            )
+        }
+        None => Default::default(),
+    };
+
     let owner_impl = {
         if namespace.is_empty() {
             quote! {
@@ -167,11 +241,25 @@ pub fn account(
         }
     };
 
+    let lazy = {
+        #[cfg(feature = "lazy-account")]
+        match (namespace.is_empty() && serializer.is_none())
+            .then(|| lazy::gen_lazy(&account_item, is_zero_copy))
+        {
+            Some(Ok(lazy)) => lazy,
+            // If lazy codegen fails for whatever reason, return empty tokenstream which
+            // will make the account unusable with `LazyAccount<T>`
+            _ => Default::default(),
+        }
+        #[cfg(not(feature = "lazy-account"))]
+        proc_macro2::TokenStream::default()
+    };
+
     proc_macro::TokenStream::from({
         if is_zero_copy {
             quote! {
                 #bytemuck_derives
-                #account_strct
+                #account_item
 
                 #unsafe_bytemuck_impl
 
@@ -188,6 +276,7 @@ pub fn account(
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::AccountDeserialize for #account_name #type_gen #where_clause {
                     fn try_deserialize(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+                        #tombstone_check
                         if buf.len() < #disc.len() {
                             return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
                         }
@@ -207,23 +296,67 @@ pub fn account(
                     }
                 }
 
+                #tombstone_impl
+
                 #owner_impl
+
+                #lazy
             }
         } else {
-            let lazy = {
-                #[cfg(feature = "lazy-account")]
-                match namespace.is_empty().then(|| lazy::gen_lazy(&account_strct)) {
-                    Some(Ok(lazy)) => lazy,
-                    // If lazy codegen fails for whatever reason, return empty tokenstream which
-                    // will make the account unusable with `LazyAccount<T>`
-                    _ => Default::default(),
+            let (derives, serialize, deserialize) = match &serializer {
+                Some(serializer) => {
+                    let serializer = quote! {
+                        <#serializer as anchor_lang::serializer::AccountSerializer<Self>>
+                    };
+                    (
+                        quote! { #[derive(Clone)] },
+                        quote! { #serializer::serialize(self, writer) },
+                        quote! { #serializer::deserialize(&mut data) },
+                    )
                 }
-                #[cfg(not(feature = "lazy-account"))]
+                None => (
+                    quote! { #[derive(AnchorSerialize, AnchorDeserialize, Clone)] },
+                    quote! {
+                        if AnchorSerialize::serialize(self, writer).is_err() {
+                            return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
+                        }
+                        Ok(())
+                    },
+                    quote! {
+                        AnchorDeserialize::deserialize(&mut data)
+                            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+                    },
+                ),
+            };
+            let idl_build_impl = {
+                #[cfg(feature = "idl-build")]
+                match &serializer {
+                    // The `IdlBuild` impl is implemented by the `AnchorSerialize` derive otherwise
+                    Some(serializer) => {
+                        // Not a real attribute but exists in order to pass the serialization info
+                        let mut item = account_item.clone();
+                        let attr = syn::parse_quote!(#[serializer(#serializer)]);
+                        match &mut item {
+                            syn::Item::Struct(strct) => {
+                                strct.attrs.push(attr);
+                                anchor_syn::idl::impl_idl_build_struct(strct)
+                            }
+                            syn::Item::Enum(enm) => {
+                                enm.attrs.push(attr);
+                                anchor_syn::idl::impl_idl_build_enum(enm)
+                            }
+                            _ => unreachable!(),
+                        }
+                    }
+                    None => proc_macro2::TokenStream::default(),
+                }
+                #[cfg(not(feature = "idl-build"))]
                 proc_macro2::TokenStream::default()
             };
+
             quote! {
-                #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-                #account_strct
+                #derives
+                #account_item
 
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::AccountSerialize for #account_name #type_gen #where_clause {
@@ -232,16 +365,14 @@ pub fn account(
                             return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
                         }
 
-                        if AnchorSerialize::serialize(self, writer).is_err() {
-                            return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
-                        }
-                        Ok(())
+                        #serialize
                     }
                 }
 
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::AccountDeserialize for #account_name #type_gen #where_clause {
                     fn try_deserialize(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+                        #tombstone_check
                         if buf.len() < #disc.len() {
                             return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
                         }
@@ -254,16 +385,19 @@ pub fn account(
 
                     fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
                         let mut data: &[u8] = &buf[#disc.len()..];
-                        AnchorDeserialize::deserialize(&mut data)
-                            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+                        #deserialize
                     }
                 }
 
+                #idl_build_impl
+
                 #[automatically_derived]
                 impl #impl_gen anchor_lang::Discriminator for #account_name #type_gen #where_clause {
                     const DISCRIMINATOR: &'static [u8] = #discriminator;
                 }
 
+                #tombstone_impl
+
                 #owner_impl
 
                 #lazy
@@ -342,7 +476,27 @@ impl Parse for AccountArg {
         };
 
         // Overrides
-        input.parse::<Overrides>().map(Self::Overrides)
+        let overrides = input.parse::<Overrides>()?;
+        if let Some(transfer_hook) = &overrides.transfer_hook {
+            return Err(syn::Error::new(
+                transfer_hook.span(),
+                "`transfer_hook` is only allowed on instructions",
+            ));
+        }
+        if let Some(no_cpi) = &overrides.no_cpi {
+            return Err(syn::Error::new(
+                no_cpi.span(),
+                "`no_cpi` is only allowed on instructions",
+            ));
+        }
+        if let Some(allowed_callers) = &overrides.allowed_callers {
+            return Err(syn::Error::new_spanned(
+                quote! { #(#allowed_callers),* },
+                "`allowed_callers` is only allowed on instructions",
+            ));
+        }
+
+        Ok(Self::Overrides(overrides))
     }
 }
 
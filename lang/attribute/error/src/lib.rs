@@ -49,6 +49,22 @@ use syn::{parse_macro_input, Expr};
 ///
 /// The `#[msg(..)]` attribute is inert, and is used only as a marker so that
 /// parsers  and IDLs can map error codes to error messages.
+///
+/// # Fields
+///
+/// Variants can have named fields, which can be used in the message. The
+/// fields are Borsh serialized into
+/// [`AnchorError::error_data`](../../anchor_lang/error/struct.AnchorError.html#structfield.error_data),
+/// logged with the error, and included in the IDL so that clients can decode
+/// them with [`DecodeError`](../../anchor_lang/error/trait.DecodeError.html).
+///
+/// ```ignore
+/// #[error_code]
+/// pub enum MyError {
+///     #[msg("Insufficient funds, needed {needed} but only {available} is available")]
+///     InsufficientFunds { needed: u64, available: u64 },
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn error_code(
     args: proc_macro::TokenStream,
@@ -89,28 +105,20 @@ pub fn error(ts: proc_macro::TokenStream) -> TokenStream {
 }
 
 fn create_error(error_code: Expr, source: bool, account_name: Option<Expr>) -> TokenStream {
-    let error_origin = match (source, account_name) {
-        (false, None) => quote! { None },
+    let with_origin = match (source, account_name) {
+        (false, None) => quote! {},
         (false, Some(account_name)) => quote! {
-            Some(anchor_lang::error::ErrorOrigin::AccountName(#account_name.to_string()))
+            .with_account_name(#account_name)
         },
         (true, _) => quote! {
-            Some(anchor_lang::error::ErrorOrigin::Source(anchor_lang::error::Source {
+            .with_source(anchor_lang::error::Source {
                 filename: file!(),
                 line: line!()
-            }))
+            })
         },
     };
 
     TokenStream::from(quote! {
-        anchor_lang::error::Error::from(
-            anchor_lang::error::AnchorError {
-                error_name: #error_code.name(),
-                error_code_number: #error_code.into(),
-                error_msg: #error_code.to_string(),
-                error_origin: #error_origin,
-                compared_values: None
-            }
-        )
+        anchor_lang::error::Error::from(#error_code)#with_origin
     })
 }
@@ -34,6 +34,37 @@ pub fn event(
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let args = parse_macro_input!(args as Overrides);
+    if let Some(transfer_hook) = &args.transfer_hook {
+        return syn::Error::new(
+            transfer_hook.span(),
+            "`transfer_hook` is only allowed on instructions",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if let Some(no_cpi) = &args.no_cpi {
+        return syn::Error::new(no_cpi.span(), "`no_cpi` is only allowed on instructions")
+            .to_compile_error()
+            .into();
+    }
+    if let Some(allowed_callers) = &args.allowed_callers {
+        return syn::Error::new_spanned(
+            quote! { #(#allowed_callers),* },
+            "`allowed_callers` is only allowed on instructions",
+        )
+        .to_compile_error()
+        .into();
+    }
+    if let Some(serializer) = &args.serializer {
+        return syn::Error::new_spanned(serializer, "`serializer` is only allowed on accounts")
+            .to_compile_error()
+            .into();
+    }
+    if let Some(tombstone) = &args.tombstone {
+        return syn::Error::new_spanned(tombstone, "`tombstone` is only allowed on accounts")
+            .to_compile_error()
+            .into();
+    }
     let event_strct = parse_macro_input!(input as syn::ItemStruct);
     let event_name = &event_strct.ident;
 
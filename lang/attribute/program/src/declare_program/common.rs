@@ -129,18 +129,41 @@ pub fn convert_idl_type_def_to_ts(
         let default_attr =
             can_derive_default(ty_def, ty_defs).then_some(quote!(#[derive(Default)]));
 
+        let serde_attr = is_used_by_bincode(ty_def, ty_defs).then(|| {
+            quote! {
+                #[derive(
+                    anchor_lang::__private::serde::Serialize,
+                    anchor_lang::__private::serde::Deserialize
+                )]
+                #[serde(crate = "anchor_lang::__private::serde")]
+            }
+        });
+
         let ser_attr = match &ty_def.serialization {
-            IdlSerialization::Borsh => quote!(#[derive(AnchorSerialize, AnchorDeserialize)]),
+            IdlSerialization::Borsh => quote! {
+                #[derive(AnchorSerialize, AnchorDeserialize)]
+                #serde_attr
+            },
             IdlSerialization::Bytemuck => quote!(#[zero_copy]),
             IdlSerialization::BytemuckUnsafe => quote!(#[zero_copy(unsafe)]),
+            // Only the `bincode` format is known, the (de)serialization of other formats is
+            // implemented by the user
+            IdlSerialization::Custom(_) => quote!(#serde_attr),
             _ => unimplemented!("{:?}", ty_def.serialization),
         };
 
-        let clone_attr = matches!(ty_def.serialization, IdlSerialization::Borsh)
-            .then(|| quote!(#[derive(Clone)]))
-            .unwrap_or_default();
+        let is_owned = matches!(
+            ty_def.serialization,
+            IdlSerialization::Borsh | IdlSerialization::Custom(_)
+        );
+
+        let clone_attr = if is_owned {
+            quote!(#[derive(Clone)])
+        } else {
+            quote!()
+        };
 
-        let copy_attr = matches!(ty_def.serialization, IdlSerialization::Borsh)
+        let copy_attr = is_owned
             .then(|| can_derive_copy(ty_def, ty_defs).then(|| quote!(#[derive(Copy)])))
             .flatten()
             .unwrap_or_default();
@@ -267,6 +290,70 @@ pub fn convert_idl_type_def_to_ts(
     }
 }
 
+/// Whether the type is serialized with `bincode`, either directly or as a part of another type.
+fn is_used_by_bincode(ty_def: &IdlTypeDef, ty_defs: &[IdlTypeDef]) -> bool {
+    fn uses(ty: &IdlType, name: &str, ty_defs: &[IdlTypeDef], visited: &mut Vec<String>) -> bool {
+        match ty {
+            IdlType::Option(inner) | IdlType::Vec(inner) | IdlType::Array(inner, _) => {
+                uses(inner, name, ty_defs, visited)
+            }
+            IdlType::Defined {
+                name: defined,
+                generics,
+            } => {
+                let uses_generic = generics.iter().any(|generic| match generic {
+                    IdlGenericArg::Type { ty } => uses(ty, name, ty_defs, visited),
+                    IdlGenericArg::Const { .. } => false,
+                });
+                if uses_generic || defined == name {
+                    return true;
+                }
+                if visited.contains(defined) {
+                    return false;
+                }
+                visited.push(defined.to_owned());
+
+                ty_defs
+                    .iter()
+                    .find(|ty_def| &ty_def.name == defined)
+                    .map(|ty_def| uses_def(ty_def, name, ty_defs, visited))
+                    .unwrap_or_default()
+            }
+            _ => false,
+        }
+    }
+
+    fn uses_def(
+        ty_def: &IdlTypeDef,
+        name: &str,
+        ty_defs: &[IdlTypeDef],
+        visited: &mut Vec<String>,
+    ) -> bool {
+        let mut uses_fields = |fields: Option<&IdlDefinedFields>| match fields {
+            Some(IdlDefinedFields::Named(fields)) => fields
+                .iter()
+                .any(|field| uses(&field.ty, name, ty_defs, visited)),
+            Some(IdlDefinedFields::Tuple(tys)) => {
+                tys.iter().any(|ty| uses(ty, name, ty_defs, visited))
+            }
+            None => false,
+        };
+
+        match &ty_def.ty {
+            IdlTypeDefTy::Struct { fields } => uses_fields(fields.as_ref()),
+            IdlTypeDefTy::Enum { variants } => variants
+                .iter()
+                .any(|variant| uses_fields(variant.fields.as_ref())),
+            IdlTypeDefTy::Type { alias } => uses(alias, name, ty_defs, visited),
+        }
+    }
+
+    ty_defs
+        .iter()
+        .filter(|def| matches!(&def.serialization, IdlSerialization::Custom(s) if s == "bincode"))
+        .any(|def| def.name == ty_def.name || uses_def(def, &ty_def.name, ty_defs, &mut vec![]))
+}
+
 fn can_derive_copy(ty_def: &IdlTypeDef, ty_defs: &[IdlTypeDef]) -> bool {
     match &ty_def.ty {
         IdlTypeDefTy::Struct { fields } => {
@@ -33,7 +33,7 @@ pub fn gen_accounts_mod(idl: &Idl) -> proc_macro2::TokenStream {
                     Self::try_deserialize_unchecked(buf)
                 }
             };
-            match ty_def.serialization {
+            match &ty_def.serialization {
                 IdlSerialization::Borsh => quote! {
                     impl anchor_lang::AccountSerialize for #name {
                         fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> anchor_lang::Result<()> {
@@ -58,6 +58,29 @@ pub fn gen_accounts_mod(idl: &Idl) -> proc_macro2::TokenStream {
                         }
                     }
                 },
+                IdlSerialization::Custom(format) if format == "bincode" => quote! {
+                    impl anchor_lang::AccountSerialize for #name {
+                        fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> anchor_lang::Result<()> {
+                            if writer.write_all(#disc).is_err() {
+                                return Err(anchor_lang::error::ErrorCode::AccountDidNotSerialize.into());
+                            }
+
+                            <anchor_lang::serializer::Bincode as anchor_lang::serializer::AccountSerializer<Self>>::serialize(self, writer)
+                        }
+                    }
+
+                    impl anchor_lang::AccountDeserialize for #name {
+                        #try_deserialize
+
+                        fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+                            let mut data: &[u8] = &buf[#disc.len()..];
+                            <anchor_lang::serializer::Bincode as anchor_lang::serializer::AccountSerializer<Self>>::deserialize(&mut data)
+                        }
+                    }
+                },
+                // The format is not known, `AccountSerialize` and `AccountDeserialize` need to be
+                // implemented by the user
+                IdlSerialization::Custom(_) => quote! {},
                 _ => {
                     let unsafe_bytemuck_impl =
                         matches!(ty_def.serialization, IdlSerialization::BytemuckUnsafe)
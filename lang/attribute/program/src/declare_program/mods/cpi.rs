@@ -22,6 +22,13 @@ pub fn gen_cpi_mod(idl: &Idl) -> proc_macro2::TokenStream {
 }
 
 fn gen_cpi_instructions(idl: &Idl) -> proc_macro2::TokenStream {
+    // Map the errors of the program back to its error type
+    let error_ty = if idl.errors.is_empty() {
+        quote! { anchor_lang::error::ErrorCode }
+    } else {
+        quote! { errors::ProgramError }
+    };
+
     let ixs = idl.instructions.iter().map(|ix| {
         let method_name = format_ident!("{}", ix.name);
         let accounts_ident = format_ident!("{}", ix.name.to_camel_case());
@@ -89,7 +96,7 @@ fn gen_cpi_instructions(idl: &Idl) -> proc_macro2::TokenStream {
                     &acc_infos,
                     ctx.signer_seeds,
                 ).map_or_else(
-                    |e| Err(Into::into(e)),
+                    |e| Err(anchor_lang::error::Error::from_cpi::<#error_ty>(ix.program_id, e)),
                     |_| { #ret_value }
                 )
             }
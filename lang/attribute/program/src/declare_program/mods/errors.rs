@@ -1,11 +1,25 @@
-use anchor_lang_idl::types::Idl;
+use anchor_lang_idl::types::{Idl, IdlDefinedFields};
 use quote::{format_ident, quote};
 
+use super::common::convert_idl_type_to_syn_type;
+
 pub fn gen_errors_mod(idl: &Idl) -> proc_macro2::TokenStream {
     let errors = idl.errors.iter().map(|e| {
         let name = format_ident!("{}", e.name);
+        let code = e.code;
+        let fields = match &e.fields {
+            Some(IdlDefinedFields::Named(fields)) => {
+                let fields = fields.iter().map(|field| {
+                    let name = format_ident!("{}", field.name);
+                    let ty = convert_idl_type_to_syn_type(&field.ty);
+                    quote! { #name: #ty }
+                });
+                quote! { { #(#fields),* } }
+            }
+            _ => quote! {},
+        };
         quote! {
-            #name,
+            #name #fields = #code,
         }
     });
 
@@ -20,8 +34,9 @@ pub fn gen_errors_mod(idl: &Idl) -> proc_macro2::TokenStream {
     quote! {
         /// Program error type definitions.
         pub mod errors {
+            use super::*;
 
-            #[anchor_lang::error_code]
+            #[anchor_lang::error_code(offset = 0)]
             pub enum ProgramError {
                 #(#errors)*
             }
@@ -1,5 +1,5 @@
 use anchor_lang_idl::types::{
-    Idl, IdlInstruction, IdlInstructionAccountItem, IdlInstructionAccounts,
+    Idl, IdlInstruction, IdlInstructionAccountItem, IdlInstructionAccounts, IdlRemainingAccountsLen,
 };
 use anchor_syn::{
     codegen::accounts::{__client_accounts, __cpi_client_accounts},
@@ -149,6 +149,8 @@ fn gen_internal_accounts_common(
             discriminator: Default::default(),
             docs: Default::default(),
             returns: Default::default(),
+            caller: Default::default(),
+            transfer_hook: Default::default(),
         })
         .chain(idl.instructions.iter().cloned())
         .collect::<Vec<_>>();
@@ -169,17 +171,30 @@ fn gen_internal_accounts_common(
                     let attrs = {
                         let signer = acc.signer.then_some(quote!(signer));
                         let mt = acc.writable.then_some(quote!(mut));
-
-                        match (signer, mt) {
-                            (None, None) => None,
-                            (Some(s), None) => Some(quote!(#s)),
-                            (None, Some(m)) => Some(quote!(#m)),
-                            (Some(s), Some(m)) => Some(quote!(#s, #m)),
-                        }
+                        // The length is only used when deserializing the accounts on-chain, which
+                        // isn't generated here, so it's only given to keep the list anywhere in
+                        // the struct.
+                        let remaining =
+                            acc.remaining
+                                .as_ref()
+                                .map(|remaining| match &remaining.len {
+                                    Some(IdlRemainingAccountsLen::Const { value }) => {
+                                        quote!(remaining, len = #value)
+                                    }
+                                    _ => quote!(remaining, len = 0),
+                                });
+
+                        let attrs = [signer, mt, remaining]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>();
+                        (!attrs.is_empty()).then(|| quote!(#(#attrs),*))
                     };
 
                     let acc_expr = if acc.optional {
                         quote! { Option<AccountInfo #generics> }
+                    } else if acc.remaining.is_some() {
+                        quote! { Vec<AccountInfo #generics> }
                     } else {
                         quote! { AccountInfo #generics }
                     };
@@ -8,14 +8,29 @@ use syn::parse_macro_input;
 
 /// The `#[program]` attribute defines the module containing all instruction
 /// handlers defining all entries into a Solana program.
+///
+/// The error type of the program can be given with `#[program(error = MyError)]`, so that the
+/// errors of the CPI helpers generated with the `cpi` feature are decoded into it, see
+/// `anchor_lang::error::Error::from_cpi`.
+///
+/// The compilation fails if a discriminator is a prefix of another one of the same kind:
+///
+/// - instructions, including the IDL and event CPI instruction tags
+/// - accounts of the `Accounts` structs of the instructions, including nested structs
+/// - events emitted with `emit!` or `emit_cpi!` in the program module, given as struct
+///   expressions, e.g. `emit!(MyEvent { .. })`
 #[proc_macro_attribute]
 pub fn program(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    parse_macro_input!(input as anchor_syn::Program)
-        .to_token_stream()
-        .into()
+    let args = match args.is_empty() {
+        true => None,
+        false => Some(parse_macro_input!(args as anchor_syn::ProgramArgs)),
+    };
+    let mut program = parse_macro_input!(input as anchor_syn::Program);
+    program.args = args;
+    program.to_token_stream().into()
 }
 
 /// Declare an external program based on its IDL.
@@ -46,6 +61,11 @@ pub fn program(
 /// definition, you should consider creating a separate crate for the external program definition
 /// and reusing it in your programs.
 ///
+/// Accounts with a custom serialization format other than `bincode` (see
+/// `anchor_lang::serializer`) don't implement `AccountSerialize` and `AccountDeserialize`, and they
+/// need to be implemented for the generated account types. The `bincode` accounts require the
+/// `serde` feature of `anchor-lang`.
+///
 /// # Example
 ///
 /// A full on-chain CPI usage example can be found [here].
@@ -127,6 +147,26 @@ pub fn interface(
 ///     - `discriminator = MY_DISC`
 ///     - `discriminator = get_disc(...)`
 ///
+/// - `transfer_hook`: Mark the instruction as the SPL transfer hook `Execute` handler
+///
+///     **Usage:** `transfer_hook`
+///
+///     Uses the `Execute` discriminator of the transfer hook interface and adds the `initialize_extra_account_meta_list` and `update_extra_account_meta_list` instructions, which write the extra accounts of the `#[transfer_hook]` accounts struct to the validation account of a mint.
+///
+///     Requires the `transfer-hook` feature of `anchor-lang`, and `anchor-spl` with the `token_2022_extensions` feature.
+///
+/// - `no_cpi`: Reject calls via CPI, e.g. to prevent reentrancy
+///
+///     **Usage:** `no_cpi`
+///
+///     The stack height is checked before the accounts are deserialized, failing with `ErrorCode::InstructionCalledViaCpi`.
+///
+/// - `allowed_callers`: Only allow calls via CPI from the given programs
+///
+///     **Usage:** `allowed_callers = [<PROGRAM_ID>, ...]`
+///
+///     The instruction must be called via CPI directly from a top-level instruction of one of the programs, failing with `ErrorCode::InstructionCallerNotAllowed`. The calling program is read from the Instructions sysvar, which must be passed as one of the accounts.
+///
 /// # Example
 ///
 /// ```ignore
@@ -9,6 +9,7 @@ use syn::parse_macro_input;
 ///
 /// # Table of Contents
 /// - [Instruction Attribute](#instruction-attribute)
+/// - [Transfer Hook Attribute](#transfer-hook-attribute)
 /// - [Constraints](#constraints)
 ///
 /// # Instruction Attribute
@@ -34,6 +35,60 @@ use syn::parse_macro_input;
 /// }
 /// ```
 ///
+/// Composite fields get the instruction's arguments the same way, unless
+/// `#[instruction(..)]` is used on the field to forward the given expressions
+/// as the instruction arguments of the composite struct, in the order they are
+/// declared by it. The expressions can use the arguments of the parent struct.
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// #[instruction(amount: u64)]
+/// pub struct Transfer<'info, M: MintKind> {
+///     ...
+/// }
+///
+/// #[derive(Accounts)]
+/// #[instruction(bump: u8, amount: u64)]
+/// pub struct Deposit<'info, M: MintKind> {
+///     #[instruction(amount)]
+///     pub transfer: Transfer<'info, M>,
+///     ...
+/// }
+/// ```
+///
+/// Structs with generic types are used with their generic arguments in the
+/// instruction context, e.g. `Context<Deposit<Usdc>>`.
+///
+/// # Transfer Hook Attribute
+///
+/// The `#[transfer_hook]` attribute marks the struct as the accounts of a transfer hook
+/// `Execute` instruction (requires the `transfer-hook` feature of `anchor-lang`, and `anchor-spl`
+/// with the `token_2022_extensions` feature).
+/// The first 5 fields must be the accounts of the transfer hook interface, in order: source
+/// token account, mint, destination token account, authority and the extra account meta list.
+///
+/// All remaining fields are the extra accounts, which are resolved by the token program from
+/// their `seeds` or `address` constraints, or from the `Program` type. Seeds may reference
+/// earlier accounts with `.key()`, instruction arguments and constant bytes.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// #[transfer_hook]
+/// pub struct Execute<'info> {
+///     pub source: InterfaceAccount<'info, TokenAccount>,
+///     pub mint: InterfaceAccount<'info, Mint>,
+///     pub destination: InterfaceAccount<'info, TokenAccount>,
+///     /// CHECK: Can be any account
+///     pub authority: UncheckedAccount<'info>,
+///     /// CHECK: Validated against the mint
+///     pub extra_account_meta_list: UncheckedAccount<'info>,
+///     #[account(mut, seeds = [b"counter", mint.key().as_ref()], bump)]
+///     pub counter: Account<'info, Counter>,
+/// }
+/// ```
+///
 /// # Constraints
 ///
 /// There are different types of constraints that can be applied with the `#[account(..)]` attribute.
@@ -247,7 +302,15 @@ use syn::parse_macro_input;
 ///                 Add <code>seeds::program = &lt;expr&gt;</code> to derive the PDA from a different
 ///                 program than the currently executing one.<br>
 ///                 This constraint behaves slightly differently when used with <code>init</code>.
-///                 See its description.
+///                 See its description.<br><br>
+///                 The struct gets <code>find_&lt;field&gt;_address</code> and
+///                 <code>create_&lt;field&gt;_address</code> (with a given bump) associated functions
+///                 deriving the address from the same seeds. They take the id of the program
+///                 deriving the PDA first, unless <code>seeds::program</code> is given, then the
+///                 accounts and instruction arguments used in the seeds: <code>account.key()</code>
+///                 as a <code>&Pubkey</code>, <code>account.field</code> as a reference to the
+///                 account data and instruction arguments by value, e.g.
+///                 <code>Example::find_vault_address(&crate::ID, &user, id)</code>.
 ///                 <br><br>
 ///                 Example:
 ///                 <pre><code>
@@ -384,7 +447,7 @@ use syn::parse_macro_input;
 ///         </tr>
 ///         <tr>
 ///             <td>
-///                 <code>#[account(close = &lt;target_account&gt;)]</code>
+///                 <code>#[account(close = &lt;target_account&gt;)]</code><br><br><code>#[account(close = &lt;target_account&gt;, tombstone)]</code>
 ///             </td>
 ///             <td>
 ///                 Closes the account by:<br>
@@ -393,10 +456,17 @@ use syn::parse_macro_input;
 ///                 &nbsp;&nbsp;&nbsp;&nbsp;- Resetting the data of the account<br><br>
 ///                 Requires <code>mut</code> to exist on the account.
 ///                 <br><br>
+///                 With <code>tombstone</code>, the account is kept owned by the program and its
+///                 data is replaced with the tombstone of the account type instead, so the account
+///                 can't be initialized or loaded again if it's refunded in the same transaction.
+///                 The account type must allow it with <code>#[account(tombstone)]</code>.
+///                 <br><br>
 ///                 Example:
 ///                 <pre><code>
 /// #[account(mut, close = receiver)]
 /// pub data_account: Account<'info, MyData>,
+/// #[account(mut, close = receiver, tombstone)]
+/// pub other_data_account: Account<'info, MyTombstonedData>,
 /// #[account(mut)]
 /// pub receiver: SystemAccount<'info>
 ///                 </code></pre>
@@ -420,7 +490,8 @@ use syn::parse_macro_input;
 ///         </tr>
 ///         <tr>
 ///             <td>
-///                 <code>#[account(realloc = &lt;space&gt;, realloc::payer = &lt;target&gt;, realloc::zero = &lt;bool&gt;)]</code>
+///                 <code>#[account(realloc = &lt;space&gt;, realloc::payer = &lt;target&gt;, realloc::zero = &lt;bool&gt;)]</code><br><br>
+///                 <code>#[account(realloc = auto, realloc::payer = &lt;target&gt;)]</code>
 ///             </td>
 ///             <td>
 ///                 Used to <a href="https://docs.rs/solana-program/latest/solana_program/account_info/struct.AccountInfo.html#method.realloc" target = "_blank" rel = "noopener noreferrer">realloc</a>
@@ -440,6 +511,10 @@ use syn::parse_macro_input;
 ///                 to prevent reallocation over the `MAX_PERMITTED_DATA_INCREASE` limit (which can unintentionally cause account data overwrite other accounts).
 ///                 The constraint group also ensure account reallocation idempotency but checking and restricting duplicate account reallocation within a single ix.
 ///                 <br><br>
+///                 With <code>realloc = auto</code>, the account is instead resized at the end of the instruction to the exact
+///                 length of its serialized data, see <code>AccountSerialize::serialized_len</code>, e.g. after pushing to a
+///                 <code>Vec</code> field. It can only be used on <code>Account</code> types, and <code>realloc::zero</code> is optional.
+///                 <br><br>
 ///                 Example:
 ///                 <pre>
 /// #[derive(Accounts)]
@@ -460,6 +535,129 @@ use syn::parse_macro_input;
 ///                 </pre>
 ///             </td>
 ///         </tr>
+///         <tr>
+///             <td>
+///                 <code>#[account(instructions::before = &lt;expr&gt;)]</code><br><br>
+///                 <code>#[account(instructions::after = &lt;expr&gt;)]</code><br><br>
+///                 <code>#[account(instructions::not_before = &lt;expr&gt;)]</code><br><br>
+///                 <code>#[account(instructions::not_after = &lt;expr&gt;)]</code>
+///             </td>
+///             <td>
+///                 Checks that a matching instruction exists (or doesn't exist) before or after the
+///                 current instruction in the transaction. Can only be used on the Instructions sysvar,
+///                 whose address is checked by the constraint.
+///                 <br><br>
+///                 <code>expr</code> is either a program id, which matches any instruction of the
+///                 program, or an <code>anchor_lang::introspection::InstructionMatcher</code>.
+///                 Custom errors are supported via <code>@</code>.<br><br>
+///                 Example:
+///                 <pre><code>
+/// #[account(
+///     instructions::before = ed25519_program::ID,
+///     instructions::after = InstructionMatcher::of::&lt;instruction::Repay&gt;(),
+///     instructions::not_after = InstructionMatcher::of::&lt;instruction::Borrow&gt;() @ MyError::NestedBorrow,
+/// )]
+/// pub instructions: UncheckedAccount<'info>
+///                 </code></pre>
+///             </td>
+///         </tr>
+///         <tr>
+///             <td>
+///                 <code>#[account(upgrade_authority = &lt;program_data&gt;)]</code><br><br>
+///                 <code>#[account(upgrade_authority = &lt;program_data&gt; @ &lt;custom_error&gt;)]</code>
+///             </td>
+///             <td>
+///                 Checks that the account is a signer and the upgrade authority of the executing
+///                 program. <code>program_data</code> must be an <code>Account&lt;'info, ProgramData&gt;</code>
+///                 field, whose address is checked to be the program data address of the executing program.
+///                 <br><br>
+///                 The program data account is included in the IDL as a PDA so that clients can
+///                 resolve it automatically.<br><br>
+///                 Custom errors are supported via <code>@</code>.<br><br>
+///                 Example:
+///                 <pre><code>
+/// #[account(upgrade_authority = program_data)]
+/// pub authority: Signer<'info>,
+/// pub program_data: Account<'info, ProgramData>,
+///                 </code></pre>
+///             </td>
+///         </tr>
+///         <tr>
+///             <td>
+///                 <code>#[account(signers = &lt;expr&gt;, threshold = &lt;expr&gt;)]</code><br><br>
+///                 <code>#[account(signers = &lt;expr&gt;, threshold = &lt;expr&gt; @ &lt;custom_error&gt;)]</code>
+///             </td>
+///             <td>
+///                 Checks that at least <code>threshold</code> distinct keys of <code>signers</code>
+///                 (e.g. a <code>Vec&lt;Pubkey&gt;</code>) signed the transaction, as one of the
+///                 accounts of the struct or as a remaining account. The error reports the number
+///                 of signers found and the number required. A threshold of 0 is rejected.<br><br>
+///                 In a struct used as a composite field, only the accounts of the struct and the
+///                 ones after it are looked up, not the accounts of the fields before it.<br><br>
+///                 The list of signers and the threshold are included in the IDL.<br><br>
+///                 Custom errors are supported via <code>@</code>.<br><br>
+///                 Example:
+///                 <pre><code>
+/// #[account(signers = multisig.owners, threshold = multisig.threshold)]
+/// pub multisig: Account<'info, Multisig>,
+///                 </code></pre>
+///             </td>
+///         </tr>
+///         <tr>
+///             <td>
+///                 <code>#[account(remaining)]</code><br><br>
+///                 <code>#[account(remaining, len = &lt;expr&gt;)]</code>
+///             </td>
+///             <td>
+///                 Deserializes a list of accounts into a <code>Vec</code> of an account type, e.g.
+///                 <code>Vec&lt;Account&lt;'info, Pool&gt;&gt;</code>. The other constraints of the
+///                 field are checked for each element, with the position of the element available as
+///                 <code>&lt;field&gt;_index</code>, and the bumps of <code>seeds</code> are stored
+///                 in a <code>Vec&lt;u8&gt;</code>.
+///                 <br><br>
+///                 <code>len</code> is the number of accounts, e.g. a constant or an instruction
+///                 argument. Without it, all of the accounts left are consumed, so the field must
+///                 be the last one. Can't be used with <code>init</code>, <code>zero</code>,
+///                 <code>close</code>, <code>realloc</code> or the SPL constraints.<br><br>
+///                 Example:
+///                 <pre><code>
+/// #[account(
+///     remaining,
+///     len = n_pools,
+///     mut,
+///     has_one = authority,
+///     seeds = [b"pool", &(pools_index as u16).to_le_bytes()],
+///     bump,
+/// )]
+/// pub pools: Vec<Account<'info, Pool>>,
+///                 </code></pre>
+///             </td>
+///         </tr>
+///         <tr>
+///             <td>
+///                 <code>#[account(mut, dup)]</code>
+///             </td>
+///             <td>
+///                 By default, the same account can't be given for more than one mutable
+///                 <code>Account</code>, <code>LazyAccount</code>, <code>AccountLoader</code> or
+///                 <code>InterfaceAccount</code> field, including the fields of nested structs,
+///                 because the changes written back on exit would overwrite each other.
+///                 Other mutable fields, such as <code>Signer</code>, <code>SystemAccount</code>,
+///                 <code>UncheckedAccount</code> and <code>AccountInfo</code>, aren't checked since
+///                 nothing is written back for them, so they may still alias any account.
+///                 Nested structs with a manual <code>Accounts</code> implementation aren't
+///                 checked either.<br>
+///                 <code>dup</code> allows the field to alias another one. Requires
+///                 <code>mut</code> or <code>init</code>.<br><br>
+///                 Example:
+///                 <pre><code>
+/// #[account(mut)]
+/// pub from: Account<'info, Vault>,
+/// #[account(mut, dup)]
+/// pub to: Account<'info, Vault>,
+///                 </code></pre>
+///             </td>
+///         </tr>
 ///     </tbody>
 /// </table>
 ///
@@ -628,7 +826,7 @@ use syn::parse_macro_input;
 ///         </tr>
 ///     <tbody>
 /// </table>
-#[proc_macro_derive(Accounts, attributes(account, instruction))]
+#[proc_macro_derive(Accounts, attributes(account, instruction, transfer_hook))]
 pub fn derive_accounts(item: TokenStream) -> TokenStream {
     parse_macro_input!(item as anchor_syn::AccountsStruct)
         .to_token_stream()
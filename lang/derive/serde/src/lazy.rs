@@ -41,6 +41,13 @@ pub fn gen_lazy(input: proc_macro::TokenStream) -> syn::Result<proc_macro2::Toke
         _ => unreachable!(),
     };
 
+    // Type parameters are required to implement `Lazy`, similar to the `borsh` derives
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(syn::parse_quote!(anchor_lang::__private::Lazy));
+    }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     Ok(quote! {
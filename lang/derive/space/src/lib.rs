@@ -1,11 +1,12 @@
 use std::collections::VecDeque;
 
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, TokenStream as TokenStream2, TokenTree};
-use quote::{quote, quote_spanned, ToTokens};
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{
-    parse::ParseStream, parse2, parse_macro_input, punctuated::Punctuated, token::Comma, Attribute,
-    DeriveInput, Field, Fields, GenericArgument, LitInt, PathArguments, Type, TypeArray,
+    parse::ParseStream, parse2, parse_macro_input, parse_quote, punctuated::Punctuated,
+    token::Comma, Attribute, DeriveInput, Field, Fields, GenericArgument, Generics, LitInt,
+    PathArguments, Type, TypeArray,
 };
 
 /// Implements a [`Space`](./trait.Space.html) trait on the given
@@ -14,6 +15,13 @@ use syn::{
 /// For types that have a variable size like String and Vec, it is necessary to indicate the size by the `max_len` attribute.
 /// For nested types, it is necessary to specify a size for each variable type (see example).
 ///
+/// Maps (`BTreeMap`, `HashMap`) and sets (`BTreeSet`, `HashSet`) take the maximum number of
+/// entries, followed by the sizes of the variable types of the key and then the value.
+///
+/// The space of an enum is the space of its largest variant, plus one byte for the variant index.
+///
+/// Type parameters are required to implement `Space`.
+///
 /// # Example
 /// ```ignore
 /// #[account]
@@ -24,6 +32,9 @@ use syn::{
 ///     pub string_one: String,
 ///     #[max_len(10, 5)]
 ///     pub nested: Vec<Vec<u8>>,
+///     // At most 10 entries, with keys of at most 32 bytes and values of at most 5 elements.
+///     #[max_len(10, 32, 5)]
+///     pub map: BTreeMap<String, Vec<u64>>,
 /// }
 ///
 /// #[derive(Accounts)]
@@ -38,59 +49,82 @@ use syn::{
 #[proc_macro_derive(InitSpace, attributes(max_len))]
 pub fn derive_init_space(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = add_space_bounds(input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let name = input.ident;
 
-    let process_struct_fields = |fields: Punctuated<Field, Comma>| {
-        let recurse = fields.into_iter().map(|f| {
-            let mut max_len_args = get_max_len_args(&f.attrs);
-            len_from_type(f.ty, &mut max_len_args)
-        });
-
-        quote! {
-            #[automatically_derived]
-            impl #impl_generics anchor_lang::Space for #name #ty_generics #where_clause {
-                const INIT_SPACE: usize = 0 #(+ #recurse)*;
-            }
-        }
+    let process_fields = |fields: Punctuated<Field, Comma>| {
+        let recurse = fields
+            .iter()
+            .enumerate()
+            .map(|(index, f)| len_from_field(f, index));
+        quote!(0 #(+ #recurse)*)
     };
 
-    let expanded: TokenStream2 = match input.data {
+    let init_space: TokenStream2 = match input.data {
         syn::Data::Struct(strct) => match strct.fields {
-            Fields::Named(named) => process_struct_fields(named.named),
-            Fields::Unnamed(unnamed) => process_struct_fields(unnamed.unnamed),
-            Fields::Unit => quote! {
-                #[automatically_derived]
-                impl #impl_generics anchor_lang::Space for #name #ty_generics #where_clause {
-                    const INIT_SPACE: usize = 0;
-                }
-            },
+            Fields::Named(named) => process_fields(named.named),
+            Fields::Unnamed(unnamed) => process_fields(unnamed.unnamed),
+            Fields::Unit => quote!(0),
         },
         syn::Data::Enum(enm) => {
-            let variants = enm.variants.into_iter().map(|v| {
-                let len = v.fields.into_iter().map(|f| {
-                    let mut max_len_args = get_max_len_args(&f.attrs);
-                    len_from_type(f.ty, &mut max_len_args)
-                });
-
-                quote! {
-                    0 #(+ #len)*
-                }
+            let variants = enm.variants.into_iter().map(|v| match v.fields {
+                Fields::Named(named) => process_fields(named.named),
+                Fields::Unnamed(unnamed) => process_fields(unnamed.unnamed),
+                Fields::Unit => quote!(0),
             });
-
             let max = gen_max(variants);
 
-            quote! {
-                #[automatically_derived]
-                impl anchor_lang::Space for #name {
-                    const INIT_SPACE: usize = 1 + #max;
-                }
-            }
+            quote!(1 + #max)
+        }
+        syn::Data::Union(_) => {
+            return syn::Error::new_spanned(&name, "InitSpace can't be derived for unions")
+                .to_compile_error()
+                .into()
         }
-        _ => unimplemented!(),
     };
 
-    TokenStream::from(expanded)
+    TokenStream::from(quote! {
+        #[automatically_derived]
+        impl #impl_generics anchor_lang::Space for #name #ty_generics #where_clause {
+            const INIT_SPACE: usize = #init_space;
+        }
+    })
+}
+
+/// Adds a `Space` bound to the type parameters.
+fn add_space_bounds(mut generics: Generics) -> Generics {
+    let params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: anchor_lang::Space));
+    }
+    generics
+}
+
+fn len_from_field(field: &Field, index: usize) -> TokenStream2 {
+    let name = match &field.ident {
+        Some(ident) => format!("field `{ident}`"),
+        None => format!("field {index}"),
+    };
+    let mut max_len_args = match get_max_len_args(&field.attrs) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error(),
+    };
+    let len = len_from_type(field.ty.clone(), &mut max_len_args, field, &name);
+    match max_len_args {
+        Some(args) if !args.is_empty() => syn::Error::new_spanned(
+            field,
+            format!("Too many lengths in the max_len attribute of {name}"),
+        )
+        .to_compile_error(),
+        _ => len,
+    }
 }
 
 fn gen_max<T: Iterator<Item = TokenStream2>>(mut iter: T) -> TokenStream2 {
@@ -102,18 +136,23 @@ fn gen_max<T: Iterator<Item = TokenStream2>>(mut iter: T) -> TokenStream2 {
     }
 }
 
-fn len_from_type(ty: Type, attrs: &mut Option<VecDeque<TokenStream2>>) -> TokenStream2 {
+fn len_from_type(
+    ty: Type,
+    attrs: &mut Option<VecDeque<TokenStream2>>,
+    field: &Field,
+    name: &str,
+) -> TokenStream2 {
+    let error = |message: String| syn::Error::new_spanned(field, message).to_compile_error();
     match ty {
         Type::Array(TypeArray { elem, len, .. }) => {
             let array_len = len.to_token_stream();
-            let type_len = len_from_type(*elem, attrs);
+            let type_len = len_from_type(*elem, attrs, field, name);
             quote!((#array_len * #type_len))
         }
         Type::Path(ty_path) => {
             let path_segment = ty_path.path.segments.last().unwrap();
-            let ident = &path_segment.ident;
-            let type_name = ident.to_string();
-            let first_ty = get_first_ty_arg(&path_segment.arguments);
+            let type_name = path_segment.ident.to_string();
+            let ty_args = get_ty_args(&path_segment.arguments);
 
             match type_name.as_str() {
                 "i8" | "u8" | "bool" => quote!(1),
@@ -122,29 +161,40 @@ fn len_from_type(ty: Type, attrs: &mut Option<VecDeque<TokenStream2>>) -> TokenS
                 "i64" | "u64" | "f64" => quote!(8),
                 "i128" | "u128" => quote!(16),
                 "String" => {
-                    let max_len = get_next_arg(ident, attrs);
+                    let max_len = get_next_arg(attrs, field, name);
                     quote!((4 + #max_len))
                 }
                 "Pubkey" => quote!(32),
-                "Option" => {
-                    if let Some(ty) = first_ty {
-                        let type_len = len_from_type(ty, attrs);
-
-                        quote!((1 + #type_len))
-                    } else {
-                        quote_spanned!(ident.span() => compile_error!("Invalid argument in Option"))
+                "PhantomData" => quote!(0),
+                "Option" | "Box" => match ty_args.as_slice() {
+                    [ty] => {
+                        let type_len = len_from_type(ty.clone(), attrs, field, name);
+                        match type_name.as_str() {
+                            "Option" => quote!((1 + #type_len)),
+                            _ => type_len,
+                        }
                     }
-                }
-                "Vec" => {
-                    if let Some(ty) = first_ty {
-                        let max_len = get_next_arg(ident, attrs);
-                        let type_len = len_from_type(ty, attrs);
+                    _ => error(format!("Invalid argument in {type_name} of {name}")),
+                },
+                "Vec" | "VecDeque" | "BTreeSet" | "HashSet" => match ty_args.as_slice() {
+                    [ty] => {
+                        let max_len = get_next_arg(attrs, field, name);
+                        let type_len = len_from_type(ty.clone(), attrs, field, name);
 
                         quote!((4 + #type_len * #max_len))
-                    } else {
-                        quote_spanned!(ident.span() => compile_error!("Invalid argument in Vec"))
                     }
-                }
+                    _ => error(format!("Invalid argument in {type_name} of {name}")),
+                },
+                "BTreeMap" | "HashMap" => match ty_args.as_slice() {
+                    [key, value] => {
+                        let max_len = get_next_arg(attrs, field, name);
+                        let key_len = len_from_type(key.clone(), attrs, field, name);
+                        let value_len = len_from_type(value.clone(), attrs, field, name);
+
+                        quote!((4 + (#key_len + #value_len) * #max_len))
+                    }
+                    _ => error(format!("Invalid arguments in {type_name} of {name}")),
+                },
                 _ => {
                     let ty = &ty_path.path;
                     quote!(<#ty as anchor_lang::Space>::INIT_SPACE)
@@ -155,22 +205,29 @@ fn len_from_type(ty: Type, attrs: &mut Option<VecDeque<TokenStream2>>) -> TokenS
             let recurse = ty_tuple
                 .elems
                 .iter()
-                .map(|t| len_from_type(t.clone(), attrs));
+                .map(|t| len_from_type(t.clone(), attrs, field, name));
             quote! {
                 (0 #(+ #recurse)*)
             }
         }
-        _ => panic!("Type {ty:?} is not supported"),
+        _ => error(format!(
+            "Type `{}` of {name} is not supported",
+            ty.to_token_stream()
+        )),
     }
 }
 
-fn get_first_ty_arg(args: &PathArguments) -> Option<Type> {
+fn get_ty_args(args: &PathArguments) -> Vec<Type> {
     match args {
-        PathArguments::AngleBracketed(bracket) => bracket.args.iter().find_map(|el| match el {
-            GenericArgument::Type(ty) => Some(ty.to_owned()),
-            _ => None,
-        }),
-        _ => None,
+        PathArguments::AngleBracketed(bracket) => bracket
+            .args
+            .iter()
+            .filter_map(|el| match el {
+                GenericArgument::Type(ty) => Some(ty.to_owned()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
@@ -191,21 +248,27 @@ fn parse_len_arg(item: ParseStream) -> Result<VecDeque<TokenStream2>, syn::Error
     Ok(result)
 }
 
-fn get_max_len_args(attributes: &[Attribute]) -> Option<VecDeque<TokenStream2>> {
+fn get_max_len_args(
+    attributes: &[Attribute],
+) -> Result<Option<VecDeque<TokenStream2>>, syn::Error> {
     attributes
         .iter()
         .find(|a| a.path.is_ident("max_len"))
-        .and_then(|a| a.parse_args_with(parse_len_arg).ok())
+        .map(|a| a.parse_args_with(parse_len_arg))
+        .transpose()
 }
 
-fn get_next_arg(ident: &Ident, args: &mut Option<VecDeque<TokenStream2>>) -> TokenStream2 {
-    if let Some(arg_list) = args {
-        if let Some(arg) = arg_list.pop_back() {
-            quote!(#arg)
-        } else {
-            quote_spanned!(ident.span() => compile_error!("The number of lengths are invalid."))
-        }
-    } else {
-        quote_spanned!(ident.span() => compile_error!("Expected max_len attribute."))
-    }
+fn get_next_arg(
+    args: &mut Option<VecDeque<TokenStream2>>,
+    field: &Field,
+    name: &str,
+) -> TokenStream2 {
+    let message = match args {
+        Some(arg_list) => match arg_list.pop_back() {
+            Some(arg) => return quote!(#arg),
+            None => format!("Not enough lengths in the max_len attribute of {name}"),
+        },
+        None => format!("Expected max_len attribute for {name}"),
+    };
+    syn::Error::new_spanned(field, message).to_compile_error()
 }
@@ -7,7 +7,7 @@ use crate::solana_program::instruction::AccountMeta;
 use crate::solana_program::pubkey::Pubkey;
 use crate::{
     Accounts, AccountsClose, AccountsExit, Key, Owner, Result, ToAccountInfo, ToAccountInfos,
-    ToAccountMetas, ZeroCopy,
+    ToAccountMetas, ZeroCopy, CLOSED_ACCOUNT_DISCRIMINATOR,
 };
 use std::cell::{Ref, RefMut};
 use std::collections::BTreeSet;
@@ -115,15 +115,9 @@ impl<'info, T: ZeroCopy + Owner> AccountLoader<'info, T> {
         }
     }
 
-    /// Constructs a new `Loader` from a previously initialized account.
-    #[inline(never)]
-    pub fn try_from(acc_info: &'info AccountInfo<'info>) -> Result<AccountLoader<'info, T>> {
-        if acc_info.owner != &T::owner() {
-            return Err(Error::from(ErrorCode::AccountOwnedByWrongProgram)
-                .with_pubkeys((*acc_info.owner, T::owner())));
-        }
-
-        let data = &acc_info.try_borrow_data()?;
+    /// Checks the discriminator of the account data, which is [`CLOSED_ACCOUNT_DISCRIMINATOR`]
+    /// for accounts closed with a tombstone, same as `Account`.
+    fn check_discriminator(data: &[u8]) -> Result<()> {
         let disc = T::DISCRIMINATOR;
         if data.len() < disc.len() {
             return Err(ErrorCode::AccountDiscriminatorNotFound.into());
@@ -131,9 +125,26 @@ impl<'info, T: ZeroCopy + Owner> AccountLoader<'info, T> {
 
         let given_disc = &data[..disc.len()];
         if given_disc != disc {
+            if data.starts_with(CLOSED_ACCOUNT_DISCRIMINATOR) {
+                return Err(ErrorCode::AccountTombstoned.into());
+            }
             return Err(ErrorCode::AccountDiscriminatorMismatch.into());
         }
 
+        Ok(())
+    }
+
+    /// Constructs a new `Loader` from a previously initialized account.
+    #[inline(never)]
+    pub fn try_from(acc_info: &'info AccountInfo<'info>) -> Result<AccountLoader<'info, T>> {
+        if acc_info.owner != &T::owner() {
+            return Err(Error::from(ErrorCode::AccountOwnedByWrongProgram)
+                .with_pubkeys((*acc_info.owner, T::owner())));
+        }
+
+        let data = &acc_info.try_borrow_data()?;
+        Self::check_discriminator(data)?;
+
         Ok(AccountLoader::new(acc_info))
     }
 
@@ -153,15 +164,8 @@ impl<'info, T: ZeroCopy + Owner> AccountLoader<'info, T> {
     /// Returns a Ref to the account data structure for reading.
     pub fn load(&self) -> Result<Ref<'_, T>> {
         let data = self.acc_info.try_borrow_data()?;
+        Self::check_discriminator(&data)?;
         let disc = T::DISCRIMINATOR;
-        if data.len() < disc.len() {
-            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
-        }
-
-        let given_disc = &data[..disc.len()];
-        if given_disc != disc {
-            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
-        }
 
         Ok(Ref::map(data, |data| {
             bytemuck::from_bytes(&data[disc.len()..mem::size_of::<T>() + disc.len()])
@@ -177,15 +181,8 @@ impl<'info, T: ZeroCopy + Owner> AccountLoader<'info, T> {
         }
 
         let data = self.acc_info.try_borrow_mut_data()?;
+        Self::check_discriminator(&data)?;
         let disc = T::DISCRIMINATOR;
-        if data.len() < disc.len() {
-            return Err(ErrorCode::AccountDiscriminatorNotFound.into());
-        }
-
-        let given_disc = &data[..disc.len()];
-        if given_disc != disc {
-            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
-        }
 
         Ok(RefMut::map(data, |data| {
             bytemuck::from_bytes_mut(
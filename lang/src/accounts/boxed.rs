@@ -21,6 +21,10 @@ use std::collections::BTreeSet;
 use std::ops::Deref;
 
 impl<'info, B, T: Accounts<'info, B>> Accounts<'info, B> for Box<T> {
+    const ACCOUNTS_LEN: Option<usize> = T::ACCOUNTS_LEN;
+    const __ANCHOR_PRIVATE_DISCRIMINATORS: crate::__private::DiscriminatorTree =
+        T::__ANCHOR_PRIVATE_DISCRIMINATORS;
+
     fn try_accounts(
         program_id: &Pubkey,
         accounts: &mut &'info [AccountInfo<'info>],
@@ -30,6 +34,14 @@ impl<'info, B, T: Accounts<'info, B>> Accounts<'info, B> for Box<T> {
     ) -> Result<Self> {
         T::try_accounts(program_id, accounts, ix_data, bumps, reallocs).map(Box::new)
     }
+
+    fn __anchor_private_visit_mut_accounts(
+        &self,
+        path: &mut Vec<&'static str>,
+        visit: &mut dyn FnMut(&[&'static str], &Pubkey),
+    ) {
+        T::__anchor_private_visit_mut_accounts(self, path, visit)
+    }
 }
 
 impl<'info, T: AccountsExit<'info>> AccountsExit<'info> for Box<T> {
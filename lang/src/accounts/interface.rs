@@ -6,8 +6,8 @@ use crate::solana_program::account_info::AccountInfo;
 use crate::solana_program::instruction::AccountMeta;
 use crate::solana_program::pubkey::Pubkey;
 use crate::{
-    AccountDeserialize, Accounts, AccountsExit, CheckId, Key, Result, ToAccountInfos,
-    ToAccountMetas,
+    is_program_executable, AccountDeserialize, Accounts, AccountsExit, CheckId, Key,
+    LoaderV4Program, Result, ToAccountInfos, ToAccountMetas,
 };
 use std::collections::BTreeSet;
 use std::ops::Deref;
@@ -28,7 +28,7 @@ use std::ops::Deref;
 /// Checks:
 ///
 /// - `expected_programs.contains(account_info.key)`
-/// - `account_info.executable == true`
+/// - `account_info.executable == true`, or the program is owned by loader-v4 and is deployed
 ///
 /// # Example
 /// ```ignore
@@ -81,13 +81,16 @@ impl<'a, T> Interface<'a, T> {
     pub fn programdata_address(&self) -> Result<Option<Pubkey>> {
         self.0.programdata_address()
     }
+    pub fn loader_v4_state(&self) -> Result<Option<LoaderV4Program>> {
+        self.0.loader_v4_state()
+    }
 }
 impl<'a, T: CheckId> TryFrom<&'a AccountInfo<'a>> for Interface<'a, T> {
     type Error = Error;
     /// Deserializes the given `info` into a `Program`.
     fn try_from(info: &'a AccountInfo<'a>) -> Result<Self> {
         T::check_id(info.key)?;
-        if !info.executable {
+        if !is_program_executable(info) {
             return Err(ErrorCode::InvalidProgramExecutable.into());
         }
         Ok(Self::new(info))
@@ -1,11 +1,17 @@
 //! Like [`Account`](crate::Account), but deserializes on-demand.
 
-use std::{cell::RefCell, collections::BTreeSet, fmt, mem::MaybeUninit, rc::Rc};
+use std::{
+    cell::{OnceCell, RefCell},
+    collections::BTreeSet,
+    fmt,
+    mem::MaybeUninit,
+    rc::Rc,
+};
 
 use crate::{
     error::{Error, ErrorCode},
-    AccountInfo, AccountMeta, AccountSerialize, Accounts, AccountsClose, Discriminator, Key, Owner,
-    Pubkey, Result, ToAccountInfo, ToAccountInfos, ToAccountMetas,
+    AccountInfo, AccountMeta, Accounts, AccountsClose, Discriminator, Key, Owner, Pubkey, Result,
+    ToAccountInfo, ToAccountInfos, ToAccountMetas,
 };
 
 /// Deserialize account data lazily (on-demand).
@@ -40,9 +46,10 @@ use crate::{
 /// deserialize the account fully, using [`LazyAccount`] will have additional overhead and
 /// therefore use slightly more compute units.
 ///
-/// Currently, using the `mut` constraint eventually results in the whole account getting
-/// deserialized, meaning it won't use fewer compute units compared to [`Account`]. This might get
-/// optimized in the future.
+/// When the account is `mut`, only the fields loaded with `load_mut_<field>` are written back to
+/// the account data on exit. Fixed-size fields are written in place, while the data after a
+/// dynamically-sized field is shifted if the size of the field has changed. Using `load_mut` (or
+/// initializing the account) results in the whole account getting serialized.
 ///
 /// # Features
 ///
@@ -53,6 +60,13 @@ use crate::{
 ///   non-inlined, meaning that they're less likely to cause stack violation errors.
 /// - Each individual field can be deserialized with the generated `load_<field>` and
 ///   `load_mut_<field>` methods.
+/// - Enums can be used, their variant can be loaded without deserializing the fields of the
+///   variant with the generated `load_variant` method.
+/// - Generic accounts can be used.
+/// - Zero-copy accounts (`#[account(zero_copy)]`) can be used, in which case nothing is
+///   deserialized and the `load` methods return references to the account data, similar to
+///   [`AccountLoader`](crate::prelude::AccountLoader).
+/// - Can be created with the `init` and `zero` constraints. The discriminator is written on exit.
 ///
 /// # Example
 ///
@@ -142,8 +156,9 @@ use crate::{
 ///
 /// ## Memory
 ///
-/// All fields (including the inner account type) are heap allocated. It only uses 24 bytes (3x
-/// pointer size) of stack memory in total.
+/// All fields (including the inner account type) are heap allocated, and the inner account type is
+/// only allocated once it's loaded. It only uses 32 bytes (4x pointer size) of stack memory in
+/// total.
 ///
 /// It's worth noting that where the account is being deserialized matters. For example, the main
 /// place where Anchor programs are likely to hit stack violation errors is a generated function
@@ -172,44 +187,58 @@ use crate::{
 /// [SIMD-0166]: https://github.com/solana-foundation/solana-improvement-documents/pull/166
 pub struct LazyAccount<'info, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     /// **INTERNAL FIELD DO NOT USE!**
     #[doc(hidden)]
     pub __info: &'info AccountInfo<'info>,
     /// **INTERNAL FIELD DO NOT USE!**
     #[doc(hidden)]
-    pub __account: Rc<RefCell<MaybeUninit<T>>>,
+    pub __account: OnceCell<Rc<RefCell<MaybeUninit<T>>>>,
     /// **INTERNAL FIELD DO NOT USE!**
     #[doc(hidden)]
     pub __fields: Rc<RefCell<Option<Vec<bool>>>>,
+    /// **INTERNAL FIELD DO NOT USE!**
+    #[doc(hidden)]
+    pub __dirty: Rc<RefCell<Option<Vec<bool>>>>,
 }
 
 impl<T> fmt::Debug for LazyAccount<'_, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone + fmt::Debug,
+    T: Discriminator + Owner + Clone + fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("LazyAccount")
             .field("info", &self.__info)
             .field("account", &self.__account)
             .field("fields", &self.__fields)
+            .field("dirty", &self.__dirty)
             .finish()
     }
 }
 
 impl<'info, T> LazyAccount<'info, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     fn new(info: &'info AccountInfo<'info>) -> LazyAccount<'info, T> {
         Self {
             __info: info,
-            __account: Rc::new(RefCell::new(MaybeUninit::uninit())),
+            __account: OnceCell::new(),
             __fields: Rc::new(RefCell::new(None)),
+            __dirty: Rc::new(RefCell::new(None)),
         }
     }
 
+    /// **INTERNAL METHOD DO NOT USE!**
+    ///
+    /// Get the cached account value, which is only allocated on first use.
+    #[doc(hidden)]
+    pub fn __account(&self) -> &RefCell<MaybeUninit<T>> {
+        self.__account
+            .get_or_init(|| Rc::new(RefCell::new(MaybeUninit::uninit())))
+    }
+
     /// Check both the owner and the discriminator.
     pub fn try_from(info: &'info AccountInfo<'info>) -> Result<LazyAccount<'info, T>> {
         let data = &info.try_borrow_data()?;
@@ -260,15 +289,18 @@ where
     /// If there is an existing reference (mutable or not) created by any of the `load` methods.
     pub fn unload(&self) -> Result<&Self> {
         // TODO: Should we drop the initialized fields manually?
-        *self.__account.borrow_mut() = MaybeUninit::uninit();
+        if let Some(acc) = self.__account.get() {
+            *acc.borrow_mut() = MaybeUninit::uninit();
+        }
         *self.__fields.borrow_mut() = None;
+        *self.__dirty.borrow_mut() = None;
         Ok(self)
     }
 }
 
 impl<'info, B, T> Accounts<'info, B> for LazyAccount<'info, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     #[inline(never)]
     fn try_accounts(
@@ -289,7 +321,7 @@ where
 
 impl<'info, T> AccountsClose<'info> for LazyAccount<'info, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     fn close(&self, sol_destination: AccountInfo<'info>) -> Result<()> {
         crate::common::close(self.to_account_info(), sol_destination)
@@ -298,7 +330,7 @@ where
 
 impl<T> ToAccountMetas for LazyAccount<'_, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
         let is_signer = is_signer.unwrap_or(self.__info.is_signer);
@@ -312,7 +344,7 @@ where
 
 impl<'info, T> ToAccountInfos<'info> for LazyAccount<'info, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
         vec![self.to_account_info()]
@@ -321,7 +353,7 @@ where
 
 impl<'info, T> AsRef<AccountInfo<'info>> for LazyAccount<'info, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     fn as_ref(&self) -> &AccountInfo<'info> {
         self.__info
@@ -330,7 +362,7 @@ where
 
 impl<T> Key for LazyAccount<'_, T>
 where
-    T: AccountSerialize + Discriminator + Owner + Clone,
+    T: Discriminator + Owner + Clone,
 {
     fn key(&self) -> Pubkey {
         *self.__info.key
@@ -4,9 +4,11 @@ use crate::error::{Error, ErrorCode};
 use crate::solana_program::account_info::AccountInfo;
 use crate::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
 use crate::solana_program::instruction::AccountMeta;
+use crate::solana_program::loader_v4;
 use crate::solana_program::pubkey::Pubkey;
 use crate::{
-    AccountDeserialize, Accounts, AccountsExit, Id, Key, Result, ToAccountInfos, ToAccountMetas,
+    is_program_executable, AccountDeserialize, Accounts, AccountsExit, Id, Key, LoaderV4Program,
+    Result, ToAccountInfos, ToAccountMetas,
 };
 use std::collections::BTreeSet;
 use std::fmt;
@@ -19,6 +21,9 @@ use std::ops::Deref;
 /// if the program is owned by the [`BPFUpgradeableLoader`](https://docs.rs/solana-program/latest/solana_program/bpf_loader_upgradeable/index.html)
 /// which will contain the `programdata_address` property of the `Program` variant of the [`UpgradeableLoaderState`](https://docs.rs/solana-program/latest/solana_program/bpf_loader_upgradeable/enum.UpgradeableLoaderState.html) enum.
 ///
+/// Programs owned by loader-v4 store their state in the program account itself, which is
+/// returned by the `loader_v4_state` function.
+///
 /// # Table of Contents
 /// - [Basic Functionality](#basic-functionality)
 /// - [Out of the Box Types](#out-of-the-box-types)
@@ -28,7 +33,7 @@ use std::ops::Deref;
 /// Checks:
 ///
 /// - `account_info.key == expected_program`
-/// - `account_info.executable == true`
+/// - `account_info.executable == true`, or the program is owned by loader-v4 and is deployed
 ///
 /// # Example
 /// ```ignore
@@ -122,6 +127,16 @@ impl<'a, T> Program<'a, T> {
             Ok(None)
         }
     }
+
+    /// Returns the state of the program if it is owned by loader-v4.
+    pub fn loader_v4_state(&self) -> Result<Option<LoaderV4Program>> {
+        if *self.info.owner == loader_v4::ID {
+            let mut data: &[u8] = &self.info.try_borrow_data()?;
+            LoaderV4Program::try_deserialize(&mut data).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 impl<'a, T: Id> TryFrom<&'a AccountInfo<'a>> for Program<'a, T> {
@@ -131,7 +146,7 @@ impl<'a, T: Id> TryFrom<&'a AccountInfo<'a>> for Program<'a, T> {
         if info.key != &T::id() {
             return Err(Error::from(ErrorCode::InvalidProgramId).with_pubkeys((*info.key, T::id())));
         }
-        if !info.executable {
+        if !is_program_executable(info) {
             return Err(ErrorCode::InvalidProgramExecutable.into());
         }
 
@@ -1,6 +1,11 @@
-use crate::prelude::{Id, System};
+use crate::context::CpiContext;
+use crate::error::{Error, ErrorCode};
+use crate::prelude::{Id, Rent, System};
 use crate::solana_program::account_info::AccountInfo;
+use crate::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use crate::solana_program::pubkey::Pubkey;
 use crate::solana_program::system_program;
+use crate::solana_program::sysvar::Sysvar;
 use crate::Result;
 
 pub fn close<'info>(info: AccountInfo<'info>, sol_destination: AccountInfo<'info>) -> Result<()> {
@@ -14,6 +19,402 @@ pub fn close<'info>(info: AccountInfo<'info>, sol_destination: AccountInfo<'info
     info.resize(0).map_err(Into::into)
 }
 
+/// Closes the account like [`close`], but replaces its data with `tombstone` and keeps it owned
+/// by the program.
+pub fn close_tombstone<'info>(
+    info: AccountInfo<'info>,
+    sol_destination: AccountInfo<'info>,
+    tombstone: &[u8],
+) -> Result<()> {
+    let dest_starting_lamports = sol_destination.lamports();
+    **sol_destination.lamports.borrow_mut() =
+        dest_starting_lamports.checked_add(info.lamports()).unwrap();
+    **info.lamports.borrow_mut() = 0;
+
+    info.resize(tombstone.len())?;
+    info.try_borrow_mut_data()?.copy_from_slice(tombstone);
+    Ok(())
+}
+
 pub fn is_closed(info: &AccountInfo) -> bool {
     info.owner == &System::id() && info.data_is_empty()
 }
+
+/// Resizes the account to `new_space` bytes, transferring the missing rent exemption from `payer`
+/// or refunding the excess lamports to it.
+pub fn realloc<'info>(
+    info: &AccountInfo<'info>,
+    new_space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let delta_space = new_space as isize - info.data_len() as isize;
+    if delta_space == 0 {
+        return Ok(());
+    }
+    if delta_space > MAX_PERMITTED_DATA_INCREASE as isize {
+        return Err(ErrorCode::AccountReallocExceedsLimit.into());
+    }
+
+    let new_rent_minimum = Rent::get()?.minimum_balance(new_space);
+    if new_rent_minimum > info.lamports() {
+        crate::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                crate::system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            new_rent_minimum - info.lamports(),
+        )?;
+    } else if delta_space < 0 {
+        let refund = info.lamports() - new_rent_minimum;
+        **payer.lamports.borrow_mut() = payer.lamports().checked_add(refund).unwrap();
+        **info.lamports.borrow_mut() = new_rent_minimum;
+    }
+
+    info.resize(new_space).map_err(Into::into)
+}
+
+/// Returns an error naming both fields if the same account is given for more than one mutable
+/// field.
+///
+/// `visit` must call the given function with the path and key of each mutable account. It's
+/// called a second time to find the field names only if a duplicate is found.
+pub fn check_duplicate_mut_accounts<F>(visit: F) -> Result<()>
+where
+    F: Fn(&mut dyn FnMut(&[&'static str], &Pubkey)),
+{
+    let mut keys = Vec::new();
+    visit(&mut |_, key| keys.push(*key));
+
+    let duplicate = keys
+        .iter()
+        .enumerate()
+        .find(|(i, key)| keys[..*i].contains(key))
+        .map(|(_, key)| *key);
+    match duplicate {
+        None => Ok(()),
+        Some(duplicate) => {
+            let mut names = Vec::new();
+            visit(&mut |path, key| {
+                if *key == duplicate && names.len() < 2 {
+                    names.push(path.join("."));
+                }
+            });
+            Err(Error::from(ErrorCode::ConstraintDuplicateMutableAccount)
+                .with_account_name(names.join(", ")))
+        }
+    }
+}
+
+/// Returns the number of distinct `signers` with a signing account in `accounts`.
+pub fn count_signers(signers: &[Pubkey], accounts: &[AccountInfo]) -> usize {
+    signers
+        .iter()
+        .enumerate()
+        .filter(|(i, signer)| !signers[..*i].contains(signer))
+        .filter(|(_, signer)| {
+            accounts
+                .iter()
+                .any(|info| info.is_signer && info.key == *signer)
+        })
+        .count()
+}
+
+/// Discriminators of a program, checked for collisions during constant evaluation.
+///
+/// Each discriminator is checked against the previously added ones, and the compilation fails if
+/// it's empty, or if it's a prefix of another discriminator (or the other way around).
+///
+/// ```ignore
+/// const _: () = {
+///     let discriminators = Discriminators::new("the IDL instruction tag", IDL_IX_TAG_LE);
+///     let discriminators = discriminators.with("instruction `initialize`", &[1]);
+/// };
+/// ```
+pub struct Discriminators<'a> {
+    name: &'static str,
+    discriminator: &'static [u8],
+    prev: Option<&'a Discriminators<'a>>,
+}
+
+impl<'a> Discriminators<'a> {
+    pub const fn new(name: &'static str, discriminator: &'static [u8]) -> Self {
+        if discriminator.is_empty() {
+            const_panic(&["Empty discriminators are not allowed for ", name]);
+        }
+
+        Self {
+            name,
+            discriminator,
+            prev: None,
+        }
+    }
+
+    pub const fn with(&'a self, name: &'static str, discriminator: &'static [u8]) -> Self {
+        let new = Self::new(name, discriminator);
+
+        let mut prev = Some(self);
+        while let Some(other) = prev {
+            if starts_with(discriminator, other.discriminator)
+                || starts_with(other.discriminator, discriminator)
+            {
+                const_panic(&[
+                    "Ambiguous discriminators for ",
+                    other.name,
+                    " and ",
+                    name,
+                    ", one of them is a prefix of the other",
+                ]);
+            }
+            prev = other.prev;
+        }
+
+        Self {
+            prev: Some(self),
+            ..new
+        }
+    }
+}
+
+/// Discriminators of the accounts of an accounts struct, including the ones of its nested
+/// structs, see [`check_discriminator_tree`].
+///
+/// The discriminator of an account is `None` if its type doesn't implement
+/// [`Discriminator`](crate::Discriminator), see [`DiscriminatorProbe`].
+pub struct DiscriminatorTree {
+    pub items: &'static [(&'static str, Option<&'static [u8]>)],
+    pub nested: &'static [&'static DiscriminatorTree],
+}
+
+impl DiscriminatorTree {
+    pub const EMPTY: Self = Self {
+        items: &[],
+        nested: &[],
+    };
+
+    /// Number of discriminators in the tree, skipping the missing and empty ones.
+    pub const fn count(&self) -> usize {
+        let mut len = 0;
+        let mut i = 0;
+        while i < self.items.len() {
+            if let (_, Some(discriminator)) = self.items[i] {
+                if !discriminator.is_empty() {
+                    len += 1;
+                }
+            }
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < self.nested.len() {
+            len += self.nested[i].count();
+            i += 1;
+        }
+
+        len
+    }
+
+    const fn flatten<const N: usize>(
+        &self,
+        mut entries: [(&'static str, &'static [u8]); N],
+        mut len: usize,
+    ) -> ([(&'static str, &'static [u8]); N], usize) {
+        let mut i = 0;
+        while i < self.items.len() {
+            if let (name, Some(discriminator)) = self.items[i] {
+                if !discriminator.is_empty() {
+                    entries[len] = (name, discriminator);
+                    len += 1;
+                }
+            }
+            i += 1;
+        }
+
+        let mut i = 0;
+        while i < self.nested.len() {
+            (entries, len) = self.nested[i].flatten(entries, len);
+            i += 1;
+        }
+
+        (entries, len)
+    }
+}
+
+/// Fails the constant evaluation if a discriminator of the tree is a prefix of another one (or
+/// the other way around), where `N` is the [`count`](DiscriminatorTree::count) of the tree.
+///
+/// Unlike [`Discriminators`], the same name can be given more than once, e.g. for an account
+/// used by multiple instructions, and its discriminators are not checked against each other.
+/// Empty discriminators are skipped, since they are used by the wrappers of external accounts.
+pub const fn check_discriminator_tree<const N: usize>(
+    kind: &'static str,
+    tree: &DiscriminatorTree,
+) {
+    let (entries, _) = tree.flatten([("", &[]); N], 0);
+
+    let mut i = 0;
+    while i < N {
+        let (name, discriminator) = entries[i];
+        let mut j = 0;
+        while j < i {
+            let (other_name, other) = entries[j];
+            if !str_eq(name, other_name)
+                && (starts_with(discriminator, other) || starts_with(other, discriminator))
+            {
+                const_panic(&[
+                    "Ambiguous discriminators for the ",
+                    kind,
+                    " `",
+                    other_name,
+                    "` and the ",
+                    kind,
+                    " `",
+                    name,
+                    "`, one of them is a prefix of the other",
+                ]);
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Gets the discriminator of `T` during constant evaluation if it implements
+/// [`Discriminator`](crate::Discriminator), since the account types of an accounts struct aren't
+/// required to.
+///
+/// The inherent constant takes precedence over the one of [`DiscriminatorProbeFallback`], which
+/// must be in scope, when `T` is known to implement the trait.
+pub struct DiscriminatorProbe<T>(core::marker::PhantomData<T>);
+
+impl<T: crate::Discriminator> DiscriminatorProbe<T> {
+    pub const DISCRIMINATOR: Option<&'static [u8]> = Some(T::DISCRIMINATOR);
+}
+
+pub trait DiscriminatorProbeFallback {
+    const DISCRIMINATOR: Option<&'static [u8]> = None;
+}
+
+impl<T> DiscriminatorProbeFallback for DiscriminatorProbe<T> {}
+
+/// Fails the constant evaluation if the tombstone of the account is zeroed, which would allow
+/// initializing it with `zero`, or if it overlaps with the discriminator of the account.
+pub const fn check_tombstone(
+    name: &'static str,
+    discriminator: &'static [u8],
+    tombstone: &'static [u8],
+) {
+    let mut i = 0;
+    while i < tombstone.len() && tombstone[i] == 0 {
+        i += 1;
+    }
+    if i == tombstone.len() {
+        const_panic(&["Zeroed tombstones are not allowed for ", name]);
+    }
+
+    Discriminators::new(name, discriminator).with("its tombstone", tombstone);
+}
+
+const fn starts_with(data: &[u8], prefix: &[u8]) -> bool {
+    if data.len() < prefix.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < prefix.len() {
+        if data[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    a.len() == b.len() && starts_with(a, b)
+}
+
+/// Panics with the concatenation of `parts`, since formatting is not available in `const fn`.
+const fn const_panic(parts: &[&str]) -> ! {
+    let mut buf = [0; 256];
+    let mut len = 0;
+
+    let mut i = 0;
+    while i < parts.len() {
+        let part = parts[i].as_bytes();
+        let mut j = 0;
+        while j < part.len() && len < buf.len() {
+            buf[len] = part[j];
+            len += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    match core::str::from_utf8(buf.split_at(len).0) {
+        Ok(msg) => panic!("{}", msg),
+        Err(_) => panic!("Invalid discriminators"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discriminators() {
+        let discriminators = Discriminators::new("`a`", &[1, 2]);
+        let discriminators = discriminators.with("`b`", &[1, 3]);
+        discriminators.with("`c`", &[2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ambiguous discriminators for `a` and `b`")]
+    fn discriminators_prefix() {
+        Discriminators::new("`a`", &[1, 2]).with("`b`", &[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ambiguous discriminators for `a` and `c`")]
+    fn discriminators_longer() {
+        let discriminators = Discriminators::new("`a`", &[1, 2]);
+        let discriminators = discriminators.with("`b`", &[2]);
+        discriminators.with("`c`", &[1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Empty discriminators are not allowed for `b`")]
+    fn discriminators_empty() {
+        Discriminators::new("`a`", &[1]).with("`b`", &[]);
+    }
+
+    const INNER: DiscriminatorTree = DiscriminatorTree {
+        items: &[("A", Some(&[1, 2])), ("B", None), ("C", Some(&[]))],
+        nested: &[],
+    };
+
+    #[test]
+    fn discriminator_tree() {
+        const TREE: DiscriminatorTree = DiscriminatorTree {
+            items: &[("A", Some(&[1, 2])), ("D", Some(&[1, 3]))],
+            nested: &[&INNER],
+        };
+        assert_eq!(TREE.count(), 3);
+        check_discriminator_tree::<{ TREE.count() }>("account", &TREE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ambiguous discriminators for the account `D` and the account `A`")]
+    fn discriminator_tree_prefix() {
+        const TREE: DiscriminatorTree = DiscriminatorTree {
+            items: &[("D", Some(&[1]))],
+            nested: &[&INNER],
+        };
+        check_discriminator_tree::<{ TREE.count() }>("account", &TREE);
+    }
+}
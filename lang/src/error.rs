@@ -1,5 +1,6 @@
 use crate::solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use anchor_lang::error_code;
+use base64::Engine;
 use borsh::maybestd::io::Error as BorshIoError;
 use std::fmt::{Debug, Display};
 use std::num::TryFromIntError;
@@ -7,6 +8,30 @@ use std::num::TryFromIntError;
 /// The starting point for user defined error codes.
 pub const ERROR_CODE_OFFSET: u32 = 6000;
 
+/// Prefix of the log line with the base64 encoded [`AnchorError::error_data`], logged right after
+/// the error.
+pub const ERROR_DATA_LOG_PREFIX: &str = "Error Data: ";
+
+/// Decodes a user defined error from its error number and the Borsh serialized fields of the
+/// variant.
+///
+/// This is implemented by the [`error_code`](crate::error_code) attribute. Fields of the
+/// variants are serialized into [`AnchorError::error_data`] and logged as a base64 encoded line
+/// that starts with [`ERROR_DATA_LOG_PREFIX`], e.g.
+///
+/// ```ignore
+/// #[error_code]
+/// pub enum MyError {
+///     #[msg("Insufficient funds, needed {needed} but only {available} is available")]
+///     InsufficientFunds { needed: u64, available: u64 },
+/// }
+/// ```
+pub trait DecodeError: Sized {
+    /// Returns the error with the given error number, or `None` if the number doesn't belong to
+    /// this error type or the data can't be deserialized.
+    fn decode_error(error_code_number: u32, error_data: &[u8]) -> Option<Self>;
+}
+
 /// Error codes that can be returned by internal framework code.
 ///
 /// - &gt;= 100 Instruction error codes
@@ -33,6 +58,12 @@ pub enum ErrorCode {
     /// 103 - The program could not serialize the given instruction
     #[msg("The program could not serialize the given instruction")]
     InstructionDidNotSerialize,
+    /// 104 - The instruction can't be called via CPI
+    #[msg("The instruction can't be called via CPI")]
+    InstructionCalledViaCpi,
+    /// 105 - The instruction must be called via CPI from an allowed program
+    #[msg("The instruction must be called via CPI from an allowed program")]
+    InstructionCallerNotAllowed,
 
     // IDL instructions
     /// 1000 - The program was compiled without idl instructions
@@ -177,6 +208,21 @@ pub enum ErrorCode {
     /// 2039 - A transfer hook extension transfer hook program id constraint was violated
     #[msg("A transfer hook extension transfer hook program id constraint was violated")]
     ConstraintMintTransferHookExtensionProgramId,
+    /// 2040 - An instructions constraint was violated, a required instruction is missing
+    #[msg("An instructions constraint was violated, a required instruction is missing")]
+    ConstraintInstructionsMissing,
+    /// 2041 - An instructions constraint was violated, a forbidden instruction is present
+    #[msg("An instructions constraint was violated, a forbidden instruction is present")]
+    ConstraintInstructionsForbidden,
+    /// 2042 - An upgrade authority constraint was violated
+    #[msg("An upgrade authority constraint was violated")]
+    ConstraintUpgradeAuthority,
+    /// 2043 - The same account was given for more than one mutable field
+    #[msg("The same account was given for more than one mutable field")]
+    ConstraintDuplicateMutableAccount,
+    /// 2044 - Not enough of the expected signers signed the transaction
+    #[msg("Not enough of the expected signers signed the transaction")]
+    ConstraintSignerThreshold,
 
     // Require
     /// 2500 - A require expression was violated
@@ -256,6 +302,9 @@ pub enum ErrorCode {
     /// 3017 - The account was duplicated for more than one reallocation
     #[msg("The account was duplicated for more than one reallocation")]
     AccountDuplicateReallocs,
+    /// 3018 - The account was closed with a tombstone
+    #[msg("The account was closed with a tombstone")]
+    AccountTombstoned,
 
     // Miscellaneous
     /// 4100 - The declared program id does not match actual program id
@@ -267,6 +316,9 @@ pub enum ErrorCode {
     /// 4102 - Invalid numeric conversion error
     #[msg("Error during numeric conversion")]
     InvalidNumericConversion = 4102,
+    /// 4103 - No preceding signature verification instruction was found for the signer and message
+    #[msg("No preceding signature verification instruction was found for the signer and message")]
+    SignatureNotVerified = 4103,
 
     // Deprecated
     /// 5000 - The API being used is deprecated and should no longer be used
@@ -320,6 +372,7 @@ impl From<TryFromIntError> for Error {
             error_name: ErrorCode::InvalidNumericConversion.name(),
             error_code_number: ErrorCode::InvalidNumericConversion.into(),
             error_msg: format!("{e}"),
+            error_data: None,
             error_origin: None,
             compared_values: None,
         }))
@@ -337,10 +390,16 @@ impl Error {
     pub fn with_account_name(mut self, account_name: impl ToString) -> Self {
         match &mut self {
             Error::AnchorError(ae) => {
-                ae.error_origin = Some(ErrorOrigin::AccountName(account_name.to_string()));
+                set_error_origin(
+                    &mut ae.error_origin,
+                    ErrorOrigin::AccountName(account_name.to_string()),
+                );
             }
             Error::ProgramError(pe) => {
-                pe.error_origin = Some(ErrorOrigin::AccountName(account_name.to_string()));
+                set_error_origin(
+                    &mut pe.error_origin,
+                    ErrorOrigin::AccountName(account_name.to_string()),
+                );
             }
         };
         self
@@ -349,15 +408,66 @@ impl Error {
     pub fn with_source(mut self, source: Source) -> Self {
         match &mut self {
             Error::AnchorError(ae) => {
-                ae.error_origin = Some(ErrorOrigin::Source(source));
+                set_error_origin(&mut ae.error_origin, ErrorOrigin::Source(source));
             }
             Error::ProgramError(pe) => {
-                pe.error_origin = Some(ErrorOrigin::Source(source));
+                set_error_origin(&mut pe.error_origin, ErrorOrigin::Source(source));
             }
         };
         self
     }
 
+    /// Converts the error of a failed CPI into the program `program_id`.
+    ///
+    /// Custom error codes are mapped back to the callee's error type `E`, or to the framework
+    /// [`ErrorCode`], so that the error keeps the name and message of the callee. The origin of
+    /// the error is set to [`ErrorOrigin::Cpi`], and setting an origin afterwards, e.g. with
+    /// [`Error::with_source`], records where the CPI was made in the calling program.
+    ///
+    /// Only the error code is returned by a failed CPI, not the [`AnchorError::error_data`] of
+    /// the callee, so variants of `E` with fields can't be decoded. Their errors are kept as
+    /// [`ProgramError::Custom`] with the error code, which can be compared with the code of the
+    /// variant.
+    pub fn from_cpi<E: DecodeError + Into<Error>>(
+        program_id: Pubkey,
+        program_error: ProgramError,
+    ) -> Self {
+        let error = match program_error {
+            ProgramError::Custom(code) => E::decode_error(code, &[])
+                .map(Into::into)
+                .or_else(|| ErrorCode::decode_error(code, &[]).map(Into::into))
+                .unwrap_or_else(|| program_error.into()),
+            _ => program_error.into(),
+        };
+
+        let origin = ErrorOrigin::Cpi {
+            program_id,
+            caller: None,
+        };
+        match error {
+            Error::AnchorError(mut ae) => {
+                ae.error_origin = Some(origin);
+                Error::AnchorError(ae)
+            }
+            Error::ProgramError(mut pe) => {
+                pe.error_origin = Some(origin);
+                Error::ProgramError(pe)
+            }
+        }
+    }
+
+    /// Decodes the error into the user defined error type `E`, e.g. the error type of the callee
+    /// of a failed CPI, see [`Error::from_cpi`].
+    pub fn decode<E: DecodeError>(&self) -> Option<E> {
+        match self {
+            Error::AnchorError(ae) => ae.decode(),
+            Error::ProgramError(pe) => match pe.program_error {
+                ProgramError::Custom(code) => E::decode_error(code, &[]),
+                _ => None,
+            },
+        }
+    }
+
     pub fn with_pubkeys(mut self, pubkeys: (Pubkey, Pubkey)) -> Self {
         let pubkeys = Some(ComparedValues::Pubkeys((pubkeys.0, pubkeys.1)));
         match &mut self {
@@ -438,6 +548,16 @@ impl ProgramErrorWithOrigin {
                     self.program_error
                 ));
             }
+            Some(ErrorOrigin::Cpi { program_id, caller }) => {
+                anchor_lang::solana_program::log::sol_log(&format!(
+                    "ProgramError returned by CPI to program {}{}. Error Code: {:?}. Error Number: {}. Error Message: {}.",
+                    program_id,
+                    cpi_caller_origin(caller),
+                    self.program_error,
+                    u64::from(self.program_error.clone()),
+                    self.program_error
+                ));
+            }
         }
         match &self.compared_values {
             Some(ComparedValues::Pubkeys((left, right))) => {
@@ -455,12 +575,15 @@ impl ProgramErrorWithOrigin {
     }
 
     pub fn with_source(mut self, source: Source) -> Self {
-        self.error_origin = Some(ErrorOrigin::Source(source));
+        set_error_origin(&mut self.error_origin, ErrorOrigin::Source(source));
         self
     }
 
     pub fn with_account_name(mut self, account_name: impl ToString) -> Self {
-        self.error_origin = Some(ErrorOrigin::AccountName(account_name.to_string()));
+        set_error_origin(
+            &mut self.error_origin,
+            ErrorOrigin::AccountName(account_name.to_string()),
+        );
         self
     }
 }
@@ -485,6 +608,31 @@ pub enum ComparedValues {
 pub enum ErrorOrigin {
     Source(Source),
     AccountName(String),
+    /// The error was returned by a CPI into `program_id`, see [`Error::from_cpi`].
+    Cpi {
+        program_id: Pubkey,
+        /// Origin of the error in the calling program.
+        caller: Option<Box<ErrorOrigin>>,
+    },
+}
+
+/// Sets the origin of an error, keeping the callee of CPI errors.
+fn set_error_origin(error_origin: &mut Option<ErrorOrigin>, origin: ErrorOrigin) {
+    match error_origin {
+        Some(ErrorOrigin::Cpi { caller, .. }) => *caller = Some(Box::new(origin)),
+        _ => *error_origin = Some(origin),
+    }
+}
+
+/// Formats the origin in the calling program of a CPI error for logging.
+fn cpi_caller_origin(caller: &Option<Box<ErrorOrigin>>) -> String {
+    match caller.as_deref() {
+        Some(ErrorOrigin::Source(source)) => format!(" in {}:{}", source.filename, source.line),
+        Some(ErrorOrigin::AccountName(account_name)) => {
+            format!(" caused by account: {account_name}")
+        }
+        Some(ErrorOrigin::Cpi { .. }) | None => String::new(),
+    }
 }
 
 #[derive(Debug)]
@@ -492,6 +640,8 @@ pub struct AnchorError {
     pub error_name: String,
     pub error_code_number: u32,
     pub error_msg: String,
+    /// Borsh serialized fields of the error, see [`DecodeError`].
+    pub error_data: Option<Vec<u8>>,
     pub error_origin: Option<ErrorOrigin>,
     pub compared_values: Option<ComparedValues>,
 }
@@ -524,6 +674,23 @@ impl AnchorError {
                     self.error_msg
                 ));
             }
+            Some(ErrorOrigin::Cpi { program_id, caller }) => {
+                anchor_lang::solana_program::log::sol_log(&format!(
+                    "AnchorError returned by CPI to program {}{}. Error Code: {}. Error Number: {}. Error Message: {}.",
+                    program_id,
+                    cpi_caller_origin(caller),
+                    self.error_name,
+                    self.error_code_number,
+                    self.error_msg
+                ));
+            }
+        }
+        if let Some(error_data) = &self.error_data {
+            anchor_lang::solana_program::log::sol_log(&format!(
+                "{}{}",
+                ERROR_DATA_LOG_PREFIX,
+                base64::engine::general_purpose::STANDARD.encode(error_data)
+            ));
         }
         match &self.compared_values {
             Some(ComparedValues::Pubkeys((left, right))) => {
@@ -541,14 +708,25 @@ impl AnchorError {
     }
 
     pub fn with_source(mut self, source: Source) -> Self {
-        self.error_origin = Some(ErrorOrigin::Source(source));
+        set_error_origin(&mut self.error_origin, ErrorOrigin::Source(source));
         self
     }
 
     pub fn with_account_name(mut self, account_name: impl ToString) -> Self {
-        self.error_origin = Some(ErrorOrigin::AccountName(account_name.to_string()));
+        set_error_origin(
+            &mut self.error_origin,
+            ErrorOrigin::AccountName(account_name.to_string()),
+        );
         self
     }
+
+    /// Decodes the error into the user defined error type `E`.
+    pub fn decode<E: DecodeError>(&self) -> Option<E> {
+        E::decode_error(
+            self.error_code_number,
+            self.error_data.as_deref().unwrap_or_default(),
+        )
+    }
 }
 
 impl Display for AnchorError {
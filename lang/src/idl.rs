@@ -80,4 +80,7 @@ impl IdlAccount {
 }
 
 #[cfg(feature = "idl-build")]
-pub use anchor_lang_idl::{build::IdlBuild, *};
+pub use anchor_lang_idl::{
+    build::{IdlBuild, IdlBuildAccounts},
+    *,
+};
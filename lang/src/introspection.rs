@@ -0,0 +1,766 @@
+//! Typed access to the instructions of the current transaction through the
+//! [Instructions sysvar](crate::solana_program::sysvar::instructions).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use anchor_lang::introspection::{InstructionMatcher, TransactionInstructions};
+//!
+//! pub fn borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+//!     let ixs = TransactionInstructions::new(&ctx.accounts.instructions)?;
+//!
+//!     // The loan must be repaid later in the same transaction
+//!     let repay = ixs.find_after::<crate::instruction::Repay>()?;
+//!     require!(
+//!         repay.is_some_and(|(_, repay)| repay.amount >= amount),
+//!         MyError::MissingRepay
+//!     );
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Signatures verified by the Ed25519 and Secp256k1 native programs earlier in the transaction can
+//! be checked with [`TransactionInstructions::verify_ed25519`] and
+//! [`TransactionInstructions::verify_secp256k1`]:
+//!
+//! ```ignore
+//! pub fn update_price(ctx: Context<UpdatePrice>, price: u64) -> Result<()> {
+//!     let ixs = TransactionInstructions::new(&ctx.accounts.instructions)?;
+//!     ixs.verify_ed25519(&ctx.accounts.oracle.signer, &price.to_le_bytes())?;
+//!     ctx.accounts.oracle.price = price;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! How an instruction can be called is restricted with the `#[instruction]` attribute, which
+//! checks the stack height and the Instructions sysvar with [`require_top_level`] and
+//! [`require_cpi_caller`]:
+//!
+//! ```ignore
+//! // Can't be called via CPI, e.g. to prevent reentrancy
+//! #[instruction(no_cpi)]
+//! pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+//!     // ...
+//! }
+//!
+//! // Can only be called via CPI from the router program, the Instructions sysvar must be one of
+//! // the accounts
+//! #[instruction(allowed_callers = [router::ID])]
+//! pub fn swap(ctx: Context<Swap>, amount: u64) -> Result<()> {
+//!     // ...
+//! }
+//! ```
+
+use crate::error::ErrorCode;
+use crate::solana_program::account_info::AccountInfo;
+use crate::solana_program::instruction::{
+    get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use crate::solana_program::program_error::ProgramError;
+use crate::solana_program::pubkey::Pubkey;
+use crate::{AnchorDeserialize, Discriminator, Owner, Result};
+use solana_instructions_sysvar as instructions;
+use std::ops::Range;
+
+/// The instructions of the current transaction, loaded from the Instructions sysvar account.
+///
+/// Instructions are deserialized lazily, only when they are accessed.
+pub struct TransactionInstructions<'a, 'info> {
+    sysvar: &'a AccountInfo<'info>,
+    len: usize,
+    current_index: usize,
+}
+
+impl<'a, 'info> TransactionInstructions<'a, 'info> {
+    /// Creates the introspection API from the Instructions sysvar account.
+    ///
+    /// Returns [`ErrorCode::AccountSysvarMismatch`] if the account is not the Instructions sysvar.
+    pub fn new(sysvar: &'a AccountInfo<'info>) -> Result<Self> {
+        if !solana_sdk_ids::sysvar::instructions::check_id(sysvar.key) {
+            return Err(ErrorCode::AccountSysvarMismatch.into());
+        }
+
+        // The first 2 bytes of the sysvar data is the number of instructions
+        let len = {
+            let data = sysvar.try_borrow_data()?;
+            let len = data.get(..2).ok_or(ProgramError::InvalidAccountData)?;
+            u16::from_le_bytes([len[0], len[1]]) as usize
+        };
+        let current_index = instructions::load_current_index_checked(sysvar)? as usize;
+
+        Ok(Self {
+            sysvar,
+            len,
+            current_index,
+        })
+    }
+
+    /// Number of instructions in the transaction.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the transaction has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Index of the currently executing top-level instruction.
+    pub fn current_index(&self) -> usize {
+        self.current_index
+    }
+
+    /// Loads the instruction at the given index.
+    pub fn get(&self, index: usize) -> Result<Instruction> {
+        instructions::load_instruction_at_checked(index, self.sysvar).map_err(Into::into)
+    }
+
+    /// Loads the currently executing top-level instruction.
+    pub fn current(&self) -> Result<Instruction> {
+        self.get(self.current_index)
+    }
+
+    /// Loads the instruction at the given offset from the current instruction, e.g. `-1` for the
+    /// previous instruction, or `None` if the index is out of bounds.
+    pub fn get_relative(&self, offset: i64) -> Result<Option<Instruction>> {
+        match (self.current_index as i64).checked_add(offset) {
+            Some(index) if index >= 0 && (index as usize) < self.len => {
+                self.get(index as usize).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Iterates over all instructions of the transaction, with their indices.
+    pub fn iter(&self) -> InstructionsIter<'a, 'info> {
+        self.iter_range(0..self.len)
+    }
+
+    /// Iterates over the instructions before the current instruction, with their indices.
+    pub fn before(&self) -> InstructionsIter<'a, 'info> {
+        self.iter_range(0..self.current_index)
+    }
+
+    /// Iterates over the instructions after the current instruction, with their indices.
+    pub fn after(&self) -> InstructionsIter<'a, 'info> {
+        self.iter_range(self.current_index + 1..self.len)
+    }
+
+    fn iter_range(&self, indices: Range<usize>) -> InstructionsIter<'a, 'info> {
+        InstructionsIter {
+            sysvar: self.sysvar,
+            indices,
+        }
+    }
+
+    /// Returns the index of the first instruction before the current instruction that matches.
+    pub fn position_before<'m>(
+        &self,
+        matcher: impl Into<InstructionMatcher<'m>>,
+    ) -> Result<Option<usize>> {
+        position(self.before(), &matcher.into())
+    }
+
+    /// Returns the index of the first instruction after the current instruction that matches.
+    pub fn position_after<'m>(
+        &self,
+        matcher: impl Into<InstructionMatcher<'m>>,
+    ) -> Result<Option<usize>> {
+        position(self.after(), &matcher.into())
+    }
+
+    /// Finds and decodes the closest instruction of type `T` before the current instruction.
+    pub fn find_before<T>(&self) -> Result<Option<(usize, T)>>
+    where
+        T: Discriminator + Owner + AnchorDeserialize,
+    {
+        for index in (0..self.current_index).rev() {
+            if let Some(ix) = decode_instruction::<T>(&self.get(index)?)? {
+                return Ok(Some((index, ix)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds and decodes the closest instruction of type `T` after the current instruction.
+    pub fn find_after<T>(&self) -> Result<Option<(usize, T)>>
+    where
+        T: Discriminator + Owner + AnchorDeserialize,
+    {
+        for res in self.after() {
+            let (index, ix) = res?;
+            if let Some(ix) = decode_instruction::<T>(&ix)? {
+                return Ok(Some((index, ix)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks that an Ed25519 program instruction before the current instruction verified the
+    /// signature of `message` by `pubkey`.
+    ///
+    /// Only signatures whose public key and message are stored in the Ed25519 instruction itself
+    /// are considered, as created by [`ed25519_instruction_data`].
+    ///
+    /// Returns [`ErrorCode::SignatureNotVerified`] if no such signature exists.
+    pub fn verify_ed25519(&self, pubkey: &Pubkey, message: &[u8]) -> Result<()> {
+        self.verify_signature(&solana_sdk_ids::ed25519_program::ID, |index, data| {
+            ed25519_signs(data, index, pubkey.as_ref(), message)
+        })
+    }
+
+    /// Checks that a Secp256k1 program instruction before the current instruction verified the
+    /// signature of `message` by the Ethereum address `eth_address`.
+    ///
+    /// Only signatures whose address and message are stored in the Secp256k1 instruction itself
+    /// are considered, as created by [`secp256k1_instruction_data`].
+    ///
+    /// Returns [`ErrorCode::SignatureNotVerified`] if no such signature exists.
+    pub fn verify_secp256k1(&self, eth_address: &[u8; 20], message: &[u8]) -> Result<()> {
+        self.verify_signature(&solana_sdk_ids::secp256k1_program::ID, |index, data| {
+            secp256k1_signs(data, index, eth_address, message)
+        })
+    }
+
+    fn verify_signature(
+        &self,
+        program_id: &Pubkey,
+        signs: impl Fn(usize, &[u8]) -> bool,
+    ) -> Result<()> {
+        for res in self.before() {
+            let (index, ix) = res?;
+            if ix.program_id == *program_id && signs(index, &ix.data) {
+                return Ok(());
+            }
+        }
+        Err(ErrorCode::SignatureNotVerified.into())
+    }
+}
+
+/// Returns [`ErrorCode::InstructionCalledViaCpi`] if the current instruction is called via CPI.
+///
+/// This is checked before the accounts are deserialized for instructions with the
+/// `#[instruction(no_cpi)]` attribute.
+pub fn require_top_level() -> Result<()> {
+    if get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Err(ErrorCode::InstructionCalledViaCpi.into());
+    }
+    Ok(())
+}
+
+/// Returns [`ErrorCode::InstructionCallerNotAllowed`] unless the current instruction is called via
+/// CPI directly from a top-level instruction of one of the `callers` programs.
+///
+/// The calling program is loaded from the Instructions sysvar account, which must be one of the
+/// `accounts`. Nested CPIs are rejected since their caller can't be determined.
+///
+/// This is checked before the accounts are deserialized for instructions with the
+/// `#[instruction(allowed_callers = [..])]` attribute.
+pub fn require_cpi_caller(accounts: &[AccountInfo], callers: &[Pubkey]) -> Result<()> {
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+        return Err(ErrorCode::InstructionCallerNotAllowed.into());
+    }
+
+    let sysvar = accounts
+        .iter()
+        .find(|account| solana_sdk_ids::sysvar::instructions::check_id(account.key))
+        .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+    let caller = TransactionInstructions::new(sysvar)?.current()?.program_id;
+    if !callers.contains(&caller) {
+        return Err(ErrorCode::InstructionCallerNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Iterator over the instructions of the transaction, created by
+/// [`TransactionInstructions::iter`], [`TransactionInstructions::before`] and
+/// [`TransactionInstructions::after`].
+pub struct InstructionsIter<'a, 'info> {
+    sysvar: &'a AccountInfo<'info>,
+    indices: Range<usize>,
+}
+
+impl Iterator for InstructionsIter<'_, '_> {
+    type Item = Result<(usize, Instruction)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.indices.next()?;
+        Some(
+            instructions::load_instruction_at_checked(index, self.sysvar)
+                .map(|ix| (index, ix))
+                .map_err(Into::into),
+        )
+    }
+}
+
+fn position(mut ixs: InstructionsIter, matcher: &InstructionMatcher) -> Result<Option<usize>> {
+    ixs.find_map(|res| match res {
+        Ok((index, ix)) => matcher.matches(&ix).then_some(Ok(index)),
+        Err(e) => Some(Err(e)),
+    })
+    .transpose()
+}
+
+/// Matches instructions by program id and, optionally, by discriminator.
+///
+/// Program ids convert to a matcher of any instruction of the program, and
+/// [`InstructionMatcher::of`] creates a matcher from an Anchor `instruction::*` struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InstructionMatcher<'a> {
+    /// Program id of the instruction.
+    pub program_id: Pubkey,
+    /// Prefix of the instruction data, `None` to match any instruction of the program.
+    pub discriminator: Option<&'a [u8]>,
+}
+
+impl InstructionMatcher<'static> {
+    /// Matches the Anchor instruction `T`, e.g. `my_program::instruction::Deposit`.
+    pub fn of<T: Discriminator + Owner>() -> Self {
+        Self {
+            program_id: T::owner(),
+            discriminator: Some(T::DISCRIMINATOR),
+        }
+    }
+
+    /// Matches any instruction of the given program.
+    pub fn program(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            discriminator: None,
+        }
+    }
+}
+
+impl<'a> InstructionMatcher<'a> {
+    /// Matches instructions of the given program whose data starts with `discriminator`.
+    pub fn with_discriminator(program_id: Pubkey, discriminator: &'a [u8]) -> Self {
+        Self {
+            program_id,
+            discriminator: Some(discriminator),
+        }
+    }
+
+    /// Whether the instruction matches.
+    pub fn matches(&self, ix: &Instruction) -> bool {
+        let discriminator_matches = match self.discriminator {
+            Some(discriminator) => ix.data.starts_with(discriminator),
+            None => true,
+        };
+        ix.program_id == self.program_id && discriminator_matches
+    }
+}
+
+impl From<Pubkey> for InstructionMatcher<'static> {
+    fn from(program_id: Pubkey) -> Self {
+        Self::program(program_id)
+    }
+}
+
+/// Decodes the Anchor instruction `T` from `ix`.
+///
+/// Returns `None` if the instruction is not `T`, i.e. the program id or the discriminator doesn't
+/// match, and [`ErrorCode::InstructionDidNotDeserialize`] if the instruction data is invalid.
+pub fn decode_instruction<T>(ix: &Instruction) -> Result<Option<T>>
+where
+    T: Discriminator + Owner + AnchorDeserialize,
+{
+    if !InstructionMatcher::of::<T>().matches(ix) {
+        return Ok(None);
+    }
+
+    T::deserialize(&mut &ix.data[T::DISCRIMINATOR.len()..])
+        .map(Some)
+        .map_err(|_| ErrorCode::InstructionDidNotDeserialize.into())
+}
+
+/// Size of the Ed25519 signature offsets, see [`ed25519_instruction_data`].
+const ED25519_OFFSETS_SIZE: usize = 14;
+/// Start of the Ed25519 signature offsets, after the number of signatures and a padding byte.
+const ED25519_OFFSETS_START: usize = 2;
+/// Size of the Secp256k1 signature offsets, see [`secp256k1_instruction_data`].
+const SECP256K1_OFFSETS_SIZE: usize = 11;
+/// Start of the Secp256k1 signature offsets, after the number of signatures.
+const SECP256K1_OFFSETS_START: usize = 1;
+
+/// Creates the data of an Ed25519 program instruction that verifies a single `signature` of
+/// `message` by `pubkey`, with all values stored in the instruction itself.
+///
+/// Returns [`ErrorCode::InstructionDidNotSerialize`] if the message is longer than the `u16`
+/// offsets of the instruction allow.
+pub fn ed25519_instruction_data(
+    pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    let pubkey_offset = ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for value in [
+        to_u16(signature_offset)?,
+        u16::MAX,
+        to_u16(pubkey_offset)?,
+        u16::MAX,
+        to_u16(message_offset)?,
+        to_u16(message.len())?,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Ok(data)
+}
+
+/// Creates the data of a Secp256k1 program instruction that verifies a single `signature` (with
+/// its `recovery_id`) of `message` by `eth_address`, with all values stored in the instruction
+/// itself.
+///
+/// `instruction_index` is the index of the Secp256k1 instruction in the transaction.
+///
+/// Returns [`ErrorCode::InstructionDidNotSerialize`] if the message is longer than the `u16`
+/// offsets of the instruction allow.
+pub fn secp256k1_instruction_data(
+    eth_address: &[u8; 20],
+    signature: &[u8; 64],
+    recovery_id: u8,
+    message: &[u8],
+    instruction_index: u8,
+) -> Result<Vec<u8>> {
+    let eth_address_offset = SECP256K1_OFFSETS_START + SECP256K1_OFFSETS_SIZE;
+    let signature_offset = eth_address_offset + 20;
+    let message_offset = signature_offset + 64 + 1;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.push(1);
+    data.extend_from_slice(&to_u16(signature_offset)?.to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(&to_u16(eth_address_offset)?.to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(&to_u16(message_offset)?.to_le_bytes());
+    data.extend_from_slice(&to_u16(message.len())?.to_le_bytes());
+    data.push(instruction_index);
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(signature);
+    data.push(recovery_id);
+    data.extend_from_slice(message);
+    Ok(data)
+}
+
+/// Converts an offset or a length of the signature verification instructions, stored as `u16`.
+fn to_u16(value: usize) -> Result<u16> {
+    u16::try_from(value).map_err(|_| ErrorCode::InstructionDidNotSerialize.into())
+}
+
+/// Whether the Ed25519 instruction data at `ix_index` contains a signature of `message` by
+/// `pubkey`, with both stored in the instruction itself.
+fn ed25519_signs(data: &[u8], ix_index: usize, pubkey: &[u8], message: &[u8]) -> bool {
+    let read_u16 = |offset: usize| {
+        data.get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    // The instruction index is `u16::MAX` for the current instruction
+    let is_current = |index: usize| index == u16::MAX as usize || index == ix_index;
+
+    let num_signatures = data.first().copied().unwrap_or_default() as usize;
+    (0..num_signatures).any(|i| {
+        let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_SIZE;
+        let offsets = (0..7)
+            .map(|j| read_u16(start + 2 * j))
+            .collect::<Option<Vec<_>>>();
+        let [_, signature_ix, pubkey_offset, pubkey_ix, message_offset, message_size, message_ix] =
+            match offsets.as_deref() {
+                Some(&[a, b, c, d, e, f, g]) => [a, b, c, d, e, f, g],
+                _ => return false,
+            };
+
+        is_current(signature_ix)
+            && is_current(pubkey_ix)
+            && is_current(message_ix)
+            && data.get(pubkey_offset..pubkey_offset + 32) == Some(pubkey)
+            && data.get(message_offset..message_offset + message_size) == Some(message)
+    })
+}
+
+/// Whether the Secp256k1 instruction data at `ix_index` contains a signature of `message` by
+/// `eth_address`, with both stored in the instruction itself.
+fn secp256k1_signs(data: &[u8], ix_index: usize, eth_address: &[u8], message: &[u8]) -> bool {
+    let read_u16 = |offset: usize| {
+        data.get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let read_u8 = |offset: usize| data.get(offset).map(|index| *index as usize);
+
+    let num_signatures = data.first().copied().unwrap_or_default() as usize;
+    (0..num_signatures).any(|i| {
+        let start = SECP256K1_OFFSETS_START + i * SECP256K1_OFFSETS_SIZE;
+        let offsets = (
+            read_u8(start + 2),
+            read_u16(start + 3),
+            read_u8(start + 5),
+            read_u16(start + 6),
+            read_u16(start + 8),
+            read_u8(start + 10),
+        );
+        let (
+            signature_ix,
+            eth_address_offset,
+            eth_address_ix,
+            message_offset,
+            message_size,
+            message_ix,
+        ) = match offsets {
+            (Some(a), Some(b), Some(c), Some(d), Some(e), Some(f)) => (a, b, c, d, e, f),
+            _ => return false,
+        };
+
+        signature_ix == ix_index
+            && eth_address_ix == ix_index
+            && message_ix == ix_index
+            && data.get(eth_address_offset..eth_address_offset + 20) == Some(eth_address)
+            && data.get(message_offset..message_offset + message_size) == Some(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use solana_instruction::BorrowedInstruction;
+    use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+    use super::*;
+    use crate::error::Error;
+    use crate::solana_program::clock::Epoch;
+
+    const PROGRAM_A: Pubkey = Pubkey::new_from_array([1; 32]);
+    const PROGRAM_B: Pubkey = Pubkey::new_from_array([2; 32]);
+
+    #[derive(AnchorDeserialize)]
+    struct Repay {
+        amount: u64,
+    }
+
+    impl Discriminator for Repay {
+        const DISCRIMINATOR: &'static [u8] = &[7, 7];
+    }
+
+    impl Owner for Repay {
+        fn owner() -> Pubkey {
+            PROGRAM_B
+        }
+    }
+
+    fn sysvar_data(current_index: u16) -> Vec<u8> {
+        let repay = [7, 7, 42, 0, 0, 0, 0, 0, 0, 0];
+        let ixs = [
+            BorrowedInstruction {
+                program_id: &PROGRAM_A,
+                accounts: vec![],
+                data: &[1],
+            },
+            BorrowedInstruction {
+                program_id: &PROGRAM_A,
+                accounts: vec![],
+                data: &[2],
+            },
+            BorrowedInstruction {
+                program_id: &PROGRAM_B,
+                accounts: vec![],
+                data: &repay,
+            },
+        ];
+        let mut data = instructions::construct_instructions_data(&ixs);
+        instructions::store_current_index_checked(&mut data, current_index).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_transaction_instructions() {
+        let key = solana_sdk_ids::sysvar::instructions::ID;
+        let mut lamports = 0;
+        let mut data = sysvar_data(1);
+        let sysvar = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &key,
+            false,
+            Epoch::default(),
+        );
+        let ixs = TransactionInstructions::new(&sysvar).unwrap();
+
+        assert_eq!(ixs.len(), 3);
+        assert_eq!(ixs.current_index(), 1);
+        assert_eq!(ixs.current().unwrap().data, vec![2]);
+        assert_eq!(ixs.get_relative(-1).unwrap().unwrap().data, vec![1]);
+        assert!(ixs.get_relative(2).unwrap().is_none());
+
+        assert_eq!(ixs.position_before(PROGRAM_A).unwrap(), Some(0));
+        assert_eq!(ixs.position_before(PROGRAM_B).unwrap(), None);
+        assert_eq!(ixs.position_after(PROGRAM_B).unwrap(), Some(2));
+        assert_eq!(
+            ixs.position_after(InstructionMatcher::with_discriminator(PROGRAM_B, &[8]))
+                .unwrap(),
+            None
+        );
+
+        let (index, repay) = ixs.find_after::<Repay>().unwrap().unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(repay.amount, 42);
+        assert!(ixs.find_before::<Repay>().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_transaction_instructions_wrong_sysvar() {
+        let key = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = sysvar_data(0);
+        let sysvar = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &key,
+            false,
+            Epoch::default(),
+        );
+        assert!(TransactionInstructions::new(&sysvar).is_err());
+    }
+
+    static STACK_HEIGHT: AtomicU64 = AtomicU64::new(0);
+
+    struct StackHeightStubs;
+
+    impl SyscallStubs for StackHeightStubs {
+        fn sol_get_stack_height(&self) -> u64 {
+            STACK_HEIGHT.load(Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn test_require_caller() {
+        set_syscall_stubs(Box::new(StackHeightStubs));
+        let set_stack_height = |cpi_depth: usize| {
+            STACK_HEIGHT.store(
+                (TRANSACTION_LEVEL_STACK_HEIGHT + cpi_depth) as u64,
+                Ordering::SeqCst,
+            )
+        };
+
+        // The current top-level instruction is the first one, which calls `PROGRAM_A`
+        let key = solana_sdk_ids::sysvar::instructions::ID;
+        let mut lamports = 0;
+        let mut data = sysvar_data(0);
+        let sysvar = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &key,
+            false,
+            Epoch::default(),
+        );
+        let accounts = [sysvar];
+        let not_allowed = Error::from(ErrorCode::InstructionCallerNotAllowed);
+
+        set_stack_height(0);
+        assert!(require_top_level().is_ok());
+        assert_eq!(
+            require_cpi_caller(&accounts, &[PROGRAM_A]).unwrap_err(),
+            not_allowed
+        );
+
+        set_stack_height(1);
+        assert_eq!(
+            require_top_level().unwrap_err(),
+            ErrorCode::InstructionCalledViaCpi.into()
+        );
+        assert!(require_cpi_caller(&accounts, &[PROGRAM_B, PROGRAM_A]).is_ok());
+        assert_eq!(
+            require_cpi_caller(&accounts, &[PROGRAM_B]).unwrap_err(),
+            not_allowed
+        );
+        assert_eq!(
+            require_cpi_caller(&[], &[PROGRAM_A]).unwrap_err(),
+            ErrorCode::AccountNotEnoughKeys.into()
+        );
+
+        // The caller of nested CPIs can't be determined
+        set_stack_height(2);
+        assert_eq!(
+            require_top_level().unwrap_err(),
+            ErrorCode::InstructionCalledViaCpi.into()
+        );
+        assert_eq!(
+            require_cpi_caller(&accounts, &[PROGRAM_A]).unwrap_err(),
+            not_allowed
+        );
+    }
+
+    #[test]
+    fn test_verify_signatures() {
+        let signer = Pubkey::new_from_array([3; 32]);
+        let eth_address = [4; 20];
+        let ed25519_data = ed25519_instruction_data(&signer, &[5; 64], b"hello").unwrap();
+        let secp256k1_data =
+            secp256k1_instruction_data(&eth_address, &[6; 64], 1, b"hello", 1).unwrap();
+        let ixs = [
+            BorrowedInstruction {
+                program_id: &solana_sdk_ids::ed25519_program::ID,
+                accounts: vec![],
+                data: &ed25519_data,
+            },
+            BorrowedInstruction {
+                program_id: &solana_sdk_ids::secp256k1_program::ID,
+                accounts: vec![],
+                data: &secp256k1_data,
+            },
+            BorrowedInstruction {
+                program_id: &PROGRAM_A,
+                accounts: vec![],
+                data: &[1],
+            },
+        ];
+        let mut data = instructions::construct_instructions_data(&ixs);
+        instructions::store_current_index_checked(&mut data, 2).unwrap();
+
+        let key = solana_sdk_ids::sysvar::instructions::ID;
+        let mut lamports = 0;
+        let sysvar = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &key,
+            false,
+            Epoch::default(),
+        );
+        let ixs = TransactionInstructions::new(&sysvar).unwrap();
+
+        assert!(ixs.verify_ed25519(&signer, b"hello").is_ok());
+        assert!(ixs.verify_ed25519(&signer, b"world").is_err());
+        assert!(ixs.verify_ed25519(&PROGRAM_A, b"hello").is_err());
+        assert!(ixs.verify_secp256k1(&eth_address, b"hello").is_ok());
+        assert!(ixs.verify_secp256k1(&[0; 20], b"hello").is_err());
+
+        // The length of the message is stored as a `u16`
+        let message = vec![0; u16::MAX as usize + 1];
+        assert_eq!(
+            ed25519_instruction_data(&signer, &[5; 64], &message).unwrap_err(),
+            ErrorCode::InstructionDidNotSerialize.into()
+        );
+        assert_eq!(
+            secp256k1_instruction_data(&eth_address, &[6; 64], 1, &message, 1).unwrap_err(),
+            ErrorCode::InstructionDidNotSerialize.into()
+        );
+    }
+}
@@ -216,5 +216,17 @@ mod tests {
             len!(GenericStruct { t: vec![0u8; 8] })
         );
         assert!(!GenericStruct::<Vec<u8>>::SIZED);
+
+        // The `Lazy` bound is added by the derive
+        #[derive(AnchorSerialize, AnchorDeserialize)]
+        struct UnboundedStruct<T> {
+            t: Vec<T>,
+        }
+
+        assert_eq!(
+            UnboundedStruct::<u16>::size_of(&[2, 0, 0, 0, 1, 0, 2, 0]),
+            len!(UnboundedStruct { t: vec![1u16, 2] })
+        );
+        assert!(!UnboundedStruct::<u16>::SIZED);
     }
 }
@@ -0,0 +1,343 @@
+//! Program entrypoint that defers deserializing the accounts of the instruction.
+//!
+//! The standard entrypoint deserializes every account into a heap allocated `Vec<AccountInfo>`
+//! before the program runs. When the program crate enables the `lazy-entrypoint` feature, the
+//! `#[program]` macro instead generates an entrypoint that:
+//!
+//! - Only reads the account headers to locate the instruction data and the program id, without
+//!   creating any [`AccountInfo`] or resolving duplicate accounts.
+//! - Rejects instructions for a different program id, and dispatches the instruction, before
+//!   touching the accounts.
+//! - Deserializes only the accounts consumed by the `Accounts` struct of the dispatched
+//!   instruction, see [`Accounts::ACCOUNTS_LEN`], into a stack buffer (or on the heap if there are
+//!   more than [`MAX_STACK_ACCOUNTS`] accounts).
+//!
+//! Handlers are unchanged and still receive a [`Context`](crate::context::Context).
+//!
+//! All accounts are deserialized, so that `ctx.remaining_accounts` is the same as with the
+//! standard entrypoint, for handlers which use their context other than through `ctx.accounts`,
+//! `ctx.bumps` and `ctx.program_id`, e.g. by reading `ctx.remaining_accounts` or by passing `ctx`
+//! to another function. This is also the case for instructions with
+//! `#[instruction(allowed_callers = ..)]`, accounts structs with a `signers` constraint, IDL and
+//! event CPI instructions, and the fallback function.
+//!
+//! ```toml
+//! [features]
+//! lazy-entrypoint = []
+//! default = ["lazy-entrypoint"]
+//! ```
+
+use crate::solana_program::account_info::AccountInfo;
+use crate::solana_program::entrypoint::{
+    BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER,
+};
+use crate::solana_program::pubkey::Pubkey;
+#[cfg(doc)]
+use crate::Accounts;
+use std::cell::RefCell;
+use std::mem::{size_of, MaybeUninit};
+use std::rc::Rc;
+use std::slice;
+
+/// Maximum number of accounts deserialized into a stack buffer, instructions with more accounts
+/// are deserialized on the heap.
+pub const MAX_STACK_ACCOUNTS: usize = 64;
+
+/// Serialized input of the program, see the [module docs](self).
+pub struct EntrypointInput<'a> {
+    input: *mut u8,
+    num_accounts: usize,
+    program_id: &'a Pubkey,
+    instruction_data: &'a [u8],
+}
+
+impl<'a> EntrypointInput<'a> {
+    /// Reads the account headers of the serialized `input` to locate the instruction data and the
+    /// program id.
+    ///
+    /// # Safety
+    ///
+    /// `input` must point to the program input serialized by the runtime.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub unsafe fn new(input: *mut u8) -> Self {
+        let mut offset = 0;
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let num_accounts = *(input.add(offset) as *const u64) as usize;
+        offset += size_of::<u64>();
+
+        for _ in 0..num_accounts {
+            let dup_info = *input.add(offset);
+            offset += size_of::<u8>();
+            if dup_info != NON_DUP_MARKER {
+                // Padding
+                offset += 7;
+                continue;
+            }
+
+            // `is_signer`, `is_writable`, `executable`, original data length, key, owner and
+            // lamports
+            offset +=
+                3 * size_of::<u8>() + size_of::<u32>() + 2 * size_of::<Pubkey>() + size_of::<u64>();
+
+            #[allow(clippy::cast_ptr_alignment)]
+            let data_len = *(input.add(offset) as *const u64) as usize;
+            offset += size_of::<u64>() + data_len + MAX_PERMITTED_DATA_INCREASE;
+            offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128);
+
+            // Rent epoch
+            offset += size_of::<u64>();
+        }
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let instruction_data_len = *(input.add(offset) as *const u64) as usize;
+        offset += size_of::<u64>();
+        let instruction_data = slice::from_raw_parts(input.add(offset), instruction_data_len);
+        offset += instruction_data_len;
+
+        let program_id = &*(input.add(offset) as *const Pubkey);
+
+        Self {
+            input,
+            num_accounts,
+            program_id,
+            instruction_data,
+        }
+    }
+
+    /// Number of accounts passed to the instruction, including duplicates.
+    pub fn num_accounts(&self) -> usize {
+        self.num_accounts
+    }
+
+    /// Id of the program being executed.
+    pub fn program_id(&self) -> &'a Pubkey {
+        self.program_id
+    }
+
+    /// Data of the instruction.
+    pub fn instruction_data(&self) -> &'a [u8] {
+        self.instruction_data
+    }
+
+    /// Deserializes the first `len` accounts, or all of them if `len` is `None`, and calls `f`
+    /// with them.
+    pub fn with_accounts<R>(
+        self,
+        len: Option<usize>,
+        f: impl for<'info> FnOnce(&'info [AccountInfo<'info>]) -> R,
+    ) -> R {
+        let len = len.map_or(self.num_accounts, |len| len.min(self.num_accounts));
+        if len > MAX_STACK_ACCOUNTS {
+            let mut accounts = Vec::with_capacity(len);
+            accounts.resize_with(len, MaybeUninit::uninit);
+            // SAFETY: `input` is valid per `EntrypointInput::new`, `self` is consumed so the
+            // accounts are only deserialized once, and all elements are initialized by
+            // `deserialize_accounts`.
+            let accounts = unsafe {
+                deserialize_accounts(self.input, &mut accounts);
+                &*(accounts.as_slice() as *const [MaybeUninit<AccountInfo>]
+                    as *const [AccountInfo])
+            };
+            return f(accounts);
+        }
+
+        // Same as `entrypoint_no_alloc!`, the `const` is only used to initialize the array.
+        #[allow(clippy::declare_interior_mutable_const)]
+        const UNINIT_ACCOUNT_INFO: MaybeUninit<AccountInfo> = MaybeUninit::uninit();
+        let mut accounts = [UNINIT_ACCOUNT_INFO; MAX_STACK_ACCOUNTS];
+
+        // SAFETY: Same as above, for the first `len` elements.
+        let accounts = unsafe {
+            deserialize_accounts(self.input, &mut accounts[..len]);
+            &*(&accounts[..len] as *const [MaybeUninit<AccountInfo>] as *const [AccountInfo])
+        };
+        f(accounts)
+    }
+}
+
+/// Deserializes the first `accounts.len()` accounts of the serialized `input`, which is the same
+/// as `entrypoint::deserialize_into` except that the accounts after them are not read.
+///
+/// # Safety
+///
+/// `input` must point to the program input serialized by the runtime, with at least
+/// `accounts.len()` accounts.
+#[allow(clippy::arithmetic_side_effects)]
+unsafe fn deserialize_accounts<'a>(input: *mut u8, accounts: &mut [MaybeUninit<AccountInfo<'a>>]) {
+    // Number of accounts
+    let mut offset = size_of::<u64>();
+
+    for i in 0..accounts.len() {
+        let dup_info = *input.add(offset);
+        offset += size_of::<u8>();
+        if dup_info != NON_DUP_MARKER {
+            // Padding
+            offset += 7;
+
+            // Duplicates always refer to a previous account
+            let account = accounts[dup_info as usize].assume_init_ref().clone();
+            accounts[i].write(account);
+            continue;
+        }
+
+        let is_signer = *input.add(offset) != 0;
+        offset += size_of::<u8>();
+        let is_writable = *input.add(offset) != 0;
+        offset += size_of::<u8>();
+        let executable = *input.add(offset) != 0;
+        offset += size_of::<u8>();
+
+        // The original data length is stored in the padding to detect invalid reallocations, same
+        // as the standard entrypoint.
+        let original_data_len_offset = offset;
+        offset += size_of::<u32>();
+
+        let key = &*(input.add(offset) as *const Pubkey);
+        offset += size_of::<Pubkey>();
+        let owner = &*(input.add(offset) as *const Pubkey);
+        offset += size_of::<Pubkey>();
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let lamports = Rc::new(RefCell::new(&mut *(input.add(offset) as *mut u64)));
+        offset += size_of::<u64>();
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let data_len = *(input.add(offset) as *const u64) as usize;
+        offset += size_of::<u64>();
+        #[allow(clippy::cast_ptr_alignment)]
+        {
+            *(input.add(original_data_len_offset) as *mut u32) = data_len as u32;
+        }
+        let data = Rc::new(RefCell::new(slice::from_raw_parts_mut(
+            input.add(offset),
+            data_len,
+        )));
+        offset += data_len + MAX_PERMITTED_DATA_INCREASE;
+        offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128);
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let rent_epoch = *(input.add(offset) as *const u64);
+        offset += size_of::<u64>();
+
+        accounts[i].write(AccountInfo {
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            owner,
+            executable,
+            rent_epoch,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes the program input the same way as the runtime.
+    fn serialize(accounts: &[(u8, Pubkey, &[u8])], data: &[u8], program_id: &Pubkey) -> Vec<u64> {
+        let mut input = vec![];
+        input.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+        for (dup_info, key, account_data) in accounts {
+            input.push(*dup_info);
+            if *dup_info != NON_DUP_MARKER {
+                input.extend_from_slice(&[0; 7]);
+                continue;
+            }
+
+            input.extend_from_slice(&[1, 0, 0]);
+            input.extend_from_slice(&[0; 4]);
+            input.extend_from_slice(key.as_ref());
+            input.extend_from_slice(program_id.as_ref());
+            input.extend_from_slice(&1u64.to_le_bytes());
+            input.extend_from_slice(&(account_data.len() as u64).to_le_bytes());
+            input.extend_from_slice(account_data);
+            input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            input.resize(input.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+            input.extend_from_slice(&0u64.to_le_bytes());
+        }
+        input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        input.extend_from_slice(data);
+        input.extend_from_slice(program_id.as_ref());
+
+        // Use `u64`s for the buffer to be aligned the same way as the runtime input
+        input
+            .chunks(8)
+            .map(|chunk| {
+                let mut bytes = [0; 8];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(bytes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_entrypoint_input() {
+        let program_id = Pubkey::new_from_array([1; 32]);
+        let key_a = Pubkey::new_from_array([2; 32]);
+        let key_b = Pubkey::new_from_array([3; 32]);
+        let mut input = serialize(
+            &[
+                (NON_DUP_MARKER, key_a, &[1, 2, 3]),
+                (NON_DUP_MARKER, key_b, &[]),
+                (0, Pubkey::default(), &[]),
+            ],
+            &[4, 5],
+            &program_id,
+        );
+
+        let input = unsafe { EntrypointInput::new(input.as_mut_ptr() as *mut u8) };
+        assert_eq!(input.num_accounts(), 3);
+        assert_eq!(input.program_id(), &program_id);
+        assert_eq!(input.instruction_data(), &[4, 5]);
+
+        input.with_accounts(None, |accounts| {
+            assert_eq!(accounts.len(), 3);
+            assert_eq!(accounts[0].key, &key_a);
+            assert!(accounts[0].is_signer);
+            assert_eq!(&accounts[0].data.borrow()[..], &[1, 2, 3]);
+            assert_eq!(accounts[1].key, &key_b);
+            assert_eq!(accounts[2].key, &key_a);
+        });
+    }
+
+    #[test]
+    fn test_entrypoint_input_partial_accounts() {
+        let program_id = Pubkey::new_from_array([1; 32]);
+        let key_a = Pubkey::new_from_array([2; 32]);
+        let key_b = Pubkey::new_from_array([3; 32]);
+        let accounts: &[(u8, Pubkey, &[u8])] = &[
+            (NON_DUP_MARKER, key_a, &[1, 2, 3]),
+            (0, Pubkey::default(), &[]),
+            (NON_DUP_MARKER, key_b, &[4]),
+        ];
+
+        let mut input = serialize(accounts, &[4, 5], &program_id);
+        let input = unsafe { EntrypointInput::new(input.as_mut_ptr() as *mut u8) };
+        input.with_accounts(Some(2), |accounts| {
+            assert_eq!(accounts.len(), 2);
+            assert_eq!(accounts[0].key, &key_a);
+            assert_eq!(accounts[1].key, &key_a);
+        });
+
+        // The length is capped to the number of accounts
+        let mut input = serialize(accounts, &[4, 5], &program_id);
+        let input = unsafe { EntrypointInput::new(input.as_mut_ptr() as *mut u8) };
+        input.with_accounts(Some(5), |accounts| {
+            assert_eq!(accounts.len(), 3);
+            assert_eq!(&accounts[2].data.borrow()[..], &[4]);
+        });
+
+        // Accounts are deserialized on the heap if there are too many for the stack
+        let many = vec![(NON_DUP_MARKER, key_b, &[][..]); MAX_STACK_ACCOUNTS + 1];
+        let mut input = serialize(&many, &[], &program_id);
+        let input = unsafe { EntrypointInput::new(input.as_mut_ptr() as *mut u8) };
+        input.with_accounts(Some(MAX_STACK_ACCOUNTS + 1), |accounts| {
+            assert_eq!(accounts.len(), MAX_STACK_ACCOUNTS + 1);
+            assert!(accounts.iter().all(|account| account.key == &key_b));
+        });
+    }
+}
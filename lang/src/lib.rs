@@ -43,6 +43,11 @@ pub mod error;
 pub mod event;
 #[doc(hidden)]
 pub mod idl;
+pub mod introspection;
+pub mod lazy_entrypoint;
+mod loader_v4_state;
+mod placement;
+pub mod serializer;
 pub mod system_program;
 mod vec;
 
@@ -50,6 +55,7 @@ mod vec;
 mod lazy;
 
 pub use crate::bpf_upgradeable_state::*;
+pub use crate::loader_v4_state::*;
 pub use anchor_attribute_access_control::access_control;
 pub use anchor_attribute_account::{account, declare_id, pubkey, zero_copy};
 pub use anchor_attribute_constant::constant;
@@ -111,6 +117,11 @@ pub mod solana_program {
         pub use solana_sdk_ids::bpf_loader_upgradeable::{check_id, id, ID};
     }
 
+    pub mod loader_v4 {
+        pub use solana_loader_v4_interface::state::{LoaderV4State, LoaderV4Status};
+        pub use solana_sdk_ids::loader_v4::{check_id, id, ID};
+    }
+
     pub mod log {
         pub use solana_msg::{msg, sol_log};
         /// Print some slices as base64.
@@ -198,6 +209,19 @@ pub type Result<T> = std::result::Result<T, error::Error>;
 /// }
 /// ```
 pub trait Accounts<'info, B>: ToAccountMetas + ToAccountInfos<'info> + Sized {
+    /// Maximum number of accounts consumed by [`try_accounts`](Accounts::try_accounts), or
+    /// `None` if it isn't known at compile time.
+    ///
+    /// The [`lazy_entrypoint`] only deserializes this many accounts, so implementations must
+    /// not consume more accounts than this.
+    const ACCOUNTS_LEN: Option<usize> = None;
+
+    /// Discriminators of the accounts of the struct, which the [`program`] checks for
+    /// collisions.
+    #[doc(hidden)]
+    const __ANCHOR_PRIVATE_DISCRIMINATORS: __private::DiscriminatorTree =
+        __private::DiscriminatorTree::EMPTY;
+
     /// Returns the validated accounts struct. What constitutes "valid" is
     /// program dependent. However, users of these types should never have to
     /// worry about account substitution attacks. For example, if a program
@@ -216,6 +240,20 @@ pub trait Accounts<'info, B>: ToAccountMetas + ToAccountInfos<'info> + Sized {
         bumps: &mut B,
         reallocs: &mut BTreeSet<Pubkey>,
     ) -> Result<Self>;
+
+    /// Calls `visit` with the path and key of each mutable data account, which the program
+    /// entrypoint uses to reject the same account being given for more than one of them.
+    ///
+    /// Implemented by the [`Accounts`](./derive.Accounts.html) derive macro. Other
+    /// implementations don't have any accounts to visit by default.
+    #[doc(hidden)]
+    #[allow(unused_variables, clippy::ptr_arg)]
+    fn __anchor_private_visit_mut_accounts(
+        &self,
+        path: &mut Vec<&'static str>,
+        visit: &mut dyn FnMut(&[&'static str], &Pubkey),
+    ) {
+    }
 }
 
 /// Associated bump seeds for `Accounts`.
@@ -340,6 +378,27 @@ pub trait AccountSerialize {
     fn try_serialize<W: Write>(&self, _writer: &mut W) -> Result<()> {
         Ok(())
     }
+
+    /// Returns the exact length of the data written by
+    /// [`try_serialize`](Self::try_serialize), including the discriminator.
+    fn serialized_len(&self) -> Result<usize> {
+        struct Counter(usize);
+
+        impl Write for Counter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+        self.try_serialize(&mut counter)?;
+        Ok(counter.0)
+    }
 }
 
 /// A data structure that can be deserialized and stored into account storage,
@@ -416,11 +475,62 @@ pub trait Discriminator {
     const DISCRIMINATOR: &'static [u8];
 }
 
+/// The default data of accounts closed with the `tombstone` constraint.
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: &[u8] = &[255; 8];
+
+/// Data of an account after it's closed with `close = <target>, tombstone`.
+///
+/// Unlike `close`, which assigns the account to the system program, the tombstone keeps the account
+/// owned by the program, so the account can't be initialized or loaded again if it's refunded
+/// later in the same transaction.
+///
+/// `#[account]` only implements this trait when the `tombstone` argument is given, with
+/// [`CLOSED_ACCOUNT_DISCRIMINATOR`] unless a tombstone is given with `tombstone = <CONST_EXPR>`,
+/// and fails the compilation if the tombstone is zeroed or overlaps with the discriminator.
+pub trait Tombstone: Discriminator {
+    const TOMBSTONE: &'static [u8];
+}
+
 /// Defines the space of an account for initialization.
 pub trait Space {
     const INIT_SPACE: usize;
 }
 
+macro_rules! impl_space {
+    ($ty: ty, $space: expr) => {
+        impl Space for $ty {
+            const INIT_SPACE: usize = $space;
+        }
+    };
+}
+
+impl_space!(bool, 1);
+impl_space!(u8, 1);
+impl_space!(u16, 2);
+impl_space!(u32, 4);
+impl_space!(u64, 8);
+impl_space!(u128, 16);
+impl_space!(i8, 1);
+impl_space!(i16, 2);
+impl_space!(i32, 4);
+impl_space!(i64, 8);
+impl_space!(i128, 16);
+impl_space!(f32, 4);
+impl_space!(f64, 8);
+impl_space!(Pubkey, 32);
+
+impl<T: Space> Space for Option<T> {
+    const INIT_SPACE: usize = 1 + T::INIT_SPACE;
+}
+
+impl<T: Space> Space for Box<T> {
+    const INIT_SPACE: usize = T::INIT_SPACE;
+}
+
+impl<T: Space, const N: usize> Space for [T; N] {
+    const INIT_SPACE: usize = N * T::INIT_SPACE;
+}
+
 /// Bump seed for program derived addresses.
 pub trait Bump {
     fn seed(&self) -> u8;
@@ -505,8 +615,8 @@ pub mod prelude {
         solana_program::bpf_loader_upgradeable::UpgradeableLoaderState, source,
         system_program::System, zero_copy, AccountDeserialize, AccountSerialize, Accounts,
         AccountsClose, AccountsExit, AnchorDeserialize, AnchorSerialize, Discriminator, Id,
-        InitSpace, Key, Lamports, Owner, ProgramData, Result, Space, ToAccountInfo, ToAccountInfos,
-        ToAccountMetas,
+        InitSpace, Key, Lamports, LoaderV4Program, Owner, ProgramData, Result, Space,
+        ToAccountInfo, ToAccountInfos, ToAccountMetas,
     };
     pub use crate::solana_program::account_info::{next_account_info, AccountInfo};
     pub use crate::solana_program::instruction::AccountMeta;
@@ -546,8 +656,18 @@ pub mod __private {
     pub use anchor_attribute_account::ZeroCopyAccessor;
     pub use base64;
     pub use bytemuck;
-
-    pub use crate::{bpf_writer::BpfWriter, common::is_closed};
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    pub use crate::{
+        bpf_writer::BpfWriter,
+        common::{
+            check_discriminator_tree, check_duplicate_mut_accounts, check_tombstone,
+            close_tombstone, count_signers, is_closed, realloc, DiscriminatorProbe,
+            DiscriminatorProbeFallback, DiscriminatorTree, Discriminators,
+        },
+        placement::{Place, Placement, Slot, MAX_STACK_FIELD_SIZE},
+    };
 
     use crate::solana_program::pubkey::Pubkey;
 
@@ -558,6 +678,22 @@ pub mod __private {
         [a, b][(a < b) as usize]
     }
 
+    // Used to calculate the number of accounts consumed by an accounts struct from the ones
+    // consumed by its fields, which is `None` if any of them is.
+    #[doc(hidden)]
+    pub const fn accounts_len(lens: &[Option<usize>]) -> Option<usize> {
+        let mut total = 0;
+        let mut i = 0;
+        while i < lens.len() {
+            match lens[i] {
+                Some(len) => total += len,
+                None => return None,
+            }
+            i += 1;
+        }
+        Some(total)
+    }
+
     // Very experimental trait.
     #[doc(hidden)]
     pub trait ZeroCopyAccessor<Ty> {
@@ -0,0 +1,124 @@
+use crate::error::ErrorCode;
+use crate::solana_program::account_info::AccountInfo;
+use crate::solana_program::loader_v4::{self, LoaderV4State, LoaderV4Status};
+use crate::solana_program::pubkey::Pubkey;
+use crate::{AccountDeserialize, AccountSerialize, Owner, Result};
+
+/// State of a program owned by loader-v4.
+///
+/// This is the loader-v4 equivalent of [`ProgramData`](crate::ProgramData). Unlike the
+/// upgradeable loader, loader-v4 stores the state in the program account itself, so this type is
+/// used with the program account, e.g. `Account<'info, LoaderV4Program>`.
+#[derive(Clone)]
+pub struct LoaderV4Program {
+    pub slot: u64,
+    pub authority_address_or_next_version: Pubkey,
+    pub status: LoaderV4Status,
+}
+
+impl LoaderV4Program {
+    /// Returns the authority that can upgrade the program, or `None` if the program is finalized.
+    pub fn upgrade_authority_address(&self) -> Option<Pubkey> {
+        match self.status {
+            LoaderV4Status::Finalized => None,
+            _ => Some(self.authority_address_or_next_version),
+        }
+    }
+}
+
+impl AccountDeserialize for LoaderV4Program {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        LoaderV4Program::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let state = buf
+            .get(..LoaderV4State::program_data_offset())
+            .ok_or(ErrorCode::AccountNotProgramData)?;
+
+        let slot = u64::from_le_bytes(state[..8].try_into().unwrap());
+        let authority_address_or_next_version = Pubkey::try_from(&state[8..40]).unwrap();
+        let status = match u64::from_le_bytes(state[40..48].try_into().unwrap()) {
+            0 => LoaderV4Status::Retracted,
+            1 => LoaderV4Status::Deployed,
+            2 => LoaderV4Status::Finalized,
+            _ => return Err(ErrorCode::AccountNotProgramData.into()),
+        };
+
+        *buf = &buf[LoaderV4State::program_data_offset()..];
+        Ok(LoaderV4Program {
+            slot,
+            authority_address_or_next_version,
+            status,
+        })
+    }
+}
+
+impl AccountSerialize for LoaderV4Program {
+    fn try_serialize<W: std::io::Write>(&self, _writer: &mut W) -> Result<()> {
+        // no-op
+        Ok(())
+    }
+}
+
+impl Owner for LoaderV4Program {
+    fn owner() -> Pubkey {
+        loader_v4::ID
+    }
+}
+
+/// Returns whether the account is an executable program.
+///
+/// In addition to the `executable` flag, programs owned by loader-v4 are executable when they are
+/// deployed or finalized.
+pub fn is_program_executable(info: &AccountInfo) -> bool {
+    if info.executable {
+        return true;
+    }
+    if *info.owner != loader_v4::ID {
+        return false;
+    }
+
+    info.try_borrow_data()
+        .ok()
+        .and_then(|data| LoaderV4Program::try_deserialize(&mut &data[..]).ok())
+        .is_some_and(|program| program.status != LoaderV4Status::Retracted)
+}
+
+#[cfg(feature = "idl-build")]
+mod idl_build {
+    use super::*;
+
+    impl crate::IdlBuild for LoaderV4Program {}
+    impl crate::Discriminator for LoaderV4Program {
+        const DISCRIMINATOR: &'static [u8] = &[];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loader_v4_program() {
+        let authority = Pubkey::new_from_array([1; 32]);
+        let mut data = vec![];
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&[0xff; 4]);
+
+        let program = LoaderV4Program::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(program.slot, 7);
+        assert_eq!(program.status, LoaderV4Status::Deployed);
+        assert_eq!(program.upgrade_authority_address(), Some(authority));
+
+        data[40] = 2;
+        let program = LoaderV4Program::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(program.upgrade_authority_address(), None);
+
+        data[40] = 3;
+        assert!(LoaderV4Program::try_deserialize(&mut &data[..]).is_err());
+        assert!(LoaderV4Program::try_deserialize(&mut &data[..47]).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Placement of the account fields of `#[derive(Accounts)]` structs in `try_accounts`.
+//!
+//! Fields larger than [`MAX_STACK_FIELD_SIZE`] are deserialized into a [`Box`] in a separate
+//! stack frame, and only moved into the accounts struct once all constraints are checked. This
+//! keeps the stack frame of `try_accounts` small, which would otherwise hold every deserialized
+//! account along with the temporaries of the constraints.
+//!
+//! With the `auto-boxed-warnings` feature, a deprecation warning is emitted for these fields.
+
+use std::ops::{Deref, DerefMut};
+
+use crate::Result;
+
+/// Size of a field in bytes above which the field is deserialized on the heap.
+pub const MAX_STACK_FIELD_SIZE: usize = 512;
+
+/// Placement of a field, on the heap if `HEAP` is `true`.
+pub struct Placement<const HEAP: bool>;
+
+impl Placement<true> {
+    /// Warns about the field being placed on the heap, with the `auto-boxed-warnings` feature.
+    #[deprecated(
+        note = "this account field is larger than `MAX_STACK_FIELD_SIZE` bytes and it's \
+                deserialized on the heap in `try_accounts`, wrap it in `Box` to also keep it off \
+                the stack of the instruction handler and to silence this warning"
+    )]
+    pub const fn auto_boxed() {}
+}
+
+impl Placement<false> {
+    pub const fn auto_boxed() {}
+}
+
+/// Deserializes a field into its [`Slot`].
+pub trait Place<T> {
+    type Slot: Slot<T>;
+
+    fn place<F: FnOnce() -> Result<T>>(f: F) -> Result<Self::Slot>;
+}
+
+impl<T> Place<T> for Placement<true> {
+    type Slot = Box<T>;
+
+    #[inline(never)]
+    fn place<F: FnOnce() -> Result<T>>(f: F) -> Result<Self::Slot> {
+        f().map(Box::new)
+    }
+}
+
+impl<T> Place<T> for Placement<false> {
+    type Slot = Inline<T>;
+
+    #[inline(always)]
+    fn place<F: FnOnce() -> Result<T>>(f: F) -> Result<Self::Slot> {
+        f().map(Inline)
+    }
+}
+
+/// Storage of a field until it's moved into the accounts struct.
+pub trait Slot<T>: DerefMut<Target = T> {
+    fn take(self) -> T;
+}
+
+impl<T> Slot<T> for Box<T> {
+    fn take(self) -> T {
+        *self
+    }
+}
+
+/// A field stored on the stack.
+pub struct Inline<T>(T);
+
+impl<T> Deref for Inline<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Inline<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Slot<T> for Inline<T> {
+    fn take(self) -> T {
+        self.0
+    }
+}
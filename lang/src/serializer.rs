@@ -0,0 +1,87 @@
+//! Serialization formats of account data, other than the default [`borsh`] and [`bytemuck`]
+//! (zero-copy) formats.
+//!
+//! [`borsh`]: crate::prelude::borsh
+//! [`bytemuck`]: crate::__private::bytemuck
+
+use std::io::Write;
+
+#[cfg(feature = "serde")]
+use crate::error::ErrorCode;
+use crate::Result;
+
+/// A serialization format of account data, used with the `serializer` argument of
+/// [`#[account]`](crate::account):
+///
+/// ```ignore
+/// #[account(serializer = Bincode)]
+/// pub struct MyAccount {
+///     pub data: u64,
+/// }
+/// ```
+///
+/// The discriminator is handled by `#[account]`, serializers only handle the data after it.
+///
+/// The account is stored in the IDL with [`NAME`](AccountSerializer::NAME) as its custom
+/// serialization, along with the fields of the account, which describe the layout of the data.
+/// `declare_program!` implements the account traits of `Bincode` accounts, while the accounts
+/// using other serializers need to implement [`AccountSerialize`](crate::AccountSerialize) and
+/// [`AccountDeserialize`](crate::AccountDeserialize) manually. The types of the fields are also
+/// stored in the IDL, so the user-defined types used in the account need to derive
+/// [`AnchorSerialize`](crate::AnchorSerialize) with the `idl-build` feature.
+///
+/// # Example
+///
+/// A codec for a single type:
+///
+/// ```ignore
+/// pub struct MyCodec;
+///
+/// impl AccountSerializer<MyAccount> for MyCodec {
+///     const NAME: &'static str = "my_codec";
+///
+///     fn serialize<W: Write>(value: &MyAccount, writer: &mut W) -> Result<()> {
+///         writer
+///             .write_all(&value.data.to_be_bytes())
+///             .map_err(|_| ErrorCode::AccountDidNotSerialize.into())
+///     }
+///
+///     fn deserialize(buf: &mut &[u8]) -> Result<MyAccount> {
+///         ...
+///     }
+/// }
+/// ```
+pub trait AccountSerializer<T> {
+    /// Name of the format, stored in the IDL.
+    const NAME: &'static str;
+
+    /// Serialize the value into the writer.
+    fn serialize<W: Write>(value: &T, writer: &mut W) -> Result<()>;
+
+    /// Deserialize the value from the buffer, advancing the buffer past the read bytes.
+    fn deserialize(buf: &mut &[u8]) -> Result<T>;
+}
+
+/// The [`bincode`](https://docs.rs/bincode) format, for types implementing `serde` traits.
+///
+/// Unlike `borsh`, the lengths of dynamic types (e.g. `Vec`) are encoded as `u64`.
+///
+/// Requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub struct Bincode;
+
+#[cfg(feature = "serde")]
+impl<T> AccountSerializer<T> for Bincode
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    const NAME: &'static str = "bincode";
+
+    fn serialize<W: Write>(value: &T, writer: &mut W) -> Result<()> {
+        bincode::serialize_into(writer, value).map_err(|_| ErrorCode::AccountDidNotSerialize.into())
+    }
+
+    fn deserialize(buf: &mut &[u8]) -> Result<T> {
+        bincode::deserialize_from(buf).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::solana_program::account_info::AccountInfo;
 use crate::solana_program::instruction::AccountMeta;
 use crate::solana_program::pubkey::Pubkey;
-use crate::{Accounts, Result, ToAccountInfos, ToAccountMetas};
+use crate::{Accounts, AccountsExit, Result, ToAccountInfos, ToAccountMetas};
 use std::collections::BTreeSet;
 
 impl<'info, T: ToAccountInfos<'info>> ToAccountInfos<'info> for Vec<T> {
@@ -21,6 +21,10 @@ impl<T: ToAccountMetas> ToAccountMetas for Vec<T> {
 }
 
 impl<'info, B, T: Accounts<'info, B>> Accounts<'info, B> for Vec<T> {
+    const ACCOUNTS_LEN: Option<usize> = T::ACCOUNTS_LEN;
+    const __ANCHOR_PRIVATE_DISCRIMINATORS: crate::__private::DiscriminatorTree =
+        T::__ANCHOR_PRIVATE_DISCRIMINATORS;
+
     fn try_accounts(
         program_id: &Pubkey,
         accounts: &mut &'info [AccountInfo<'info>],
@@ -33,6 +37,25 @@ impl<'info, B, T: Accounts<'info, B>> Accounts<'info, B> for Vec<T> {
             .map(|item| vec.push(item))?;
         Ok(vec)
     }
+
+    fn __anchor_private_visit_mut_accounts(
+        &self,
+        path: &mut Vec<&'static str>,
+        visit: &mut dyn FnMut(&[&'static str], &Pubkey),
+    ) {
+        for item in self {
+            T::__anchor_private_visit_mut_accounts(item, path, visit);
+        }
+    }
+}
+
+impl<'info, T: AccountsExit<'info>> AccountsExit<'info> for Vec<T> {
+    fn exit(&self, program_id: &Pubkey) -> Result<()> {
+        for item in self {
+            item.exit(program_id)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -41,6 +64,7 @@ mod tests {
     use crate::solana_program::pubkey::Pubkey;
 
     use super::*;
+    use crate::AnchorDeserialize;
 
     #[derive(Accounts)]
     pub struct Test<'info> {
@@ -48,6 +72,72 @@ mod tests {
         test: AccountInfo<'info>,
     }
 
+    #[derive(Accounts)]
+    #[instruction(len: u8)]
+    pub struct TestRemaining<'info> {
+        #[account(remaining, len = len, mut)]
+        listed: Vec<AccountInfo<'info>>,
+        #[account(remaining)]
+        rest: Vec<AccountInfo<'info>>,
+    }
+
+    #[test]
+    fn test_remaining_accounts() {
+        let program_id = Pubkey::default();
+        let key = Pubkey::default();
+        let owner = Pubkey::default();
+        let mut lamports = [0; 3];
+        let mut data = [[0; 10]; 3];
+        let [l1, l2, l3] = &mut lamports;
+        let [d1, d2, d3] = &mut data;
+        let account1 = AccountInfo::new(&key, false, true, l1, d1, &owner, false, Epoch::default());
+        let account2 = AccountInfo::new(&key, false, true, l2, d2, &owner, false, Epoch::default());
+        let account3 =
+            AccountInfo::new(&key, false, false, l3, d3, &owner, false, Epoch::default());
+        let infos = [account1, account2, account3];
+
+        let mut bumps = TestRemainingBumps::default();
+        let mut reallocs = std::collections::BTreeSet::new();
+        let mut accounts = &infos[..];
+        let parsed = TestRemaining::try_accounts(
+            &program_id,
+            &mut accounts,
+            &[2],
+            &mut bumps,
+            &mut reallocs,
+        )
+        .unwrap();
+        assert_eq!(parsed.listed.len(), 2);
+        assert_eq!(parsed.rest.len(), 1);
+        assert!(accounts.is_empty());
+
+        // The third account isn't writable.
+        let mut accounts = &infos[..];
+        let err = TestRemaining::try_accounts(
+            &program_id,
+            &mut accounts,
+            &[3],
+            &mut bumps,
+            &mut reallocs,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err, crate::error::ErrorCode::ConstraintMut.into());
+
+        // Not enough accounts for the given length.
+        let mut accounts = &infos[..];
+        let err = TestRemaining::try_accounts(
+            &program_id,
+            &mut accounts,
+            &[4],
+            &mut bumps,
+            &mut reallocs,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err, crate::error::ErrorCode::AccountNotEnoughKeys.into());
+    }
+
     #[test]
     fn test_accounts_trait_for_vec() {
         let program_id = Pubkey::default();
@@ -67,6 +67,11 @@ pub fn generate(
                         #docs
                         pub #name: Option<Pubkey>
                     }
+                } else if f.constraints.is_remaining() {
+                    quote! {
+                        #docs
+                        pub #name: Vec<Pubkey>
+                    }
                 } else {
                     quote! {
                         #docs
@@ -109,6 +114,12 @@ pub fn generate(
                             account_metas.push(anchor_lang::solana_program::instruction::AccountMeta::new_readonly(#program_id, false));
                         }
                     }
+                } else if f.constraints.is_remaining() {
+                    quote! {
+                        for #name in &self.#name {
+                            account_metas.push(#meta(*#name, #is_signer));
+                        }
+                    }
                 } else {
                     quote! {
                         account_metas.push(#meta(self.#name, #is_signer));
@@ -68,6 +68,11 @@ pub fn generate(
                         #docs
                         pub #name: Option<anchor_lang::solana_program::account_info::AccountInfo<'info>>
                     }
+                } else if f.constraints.is_remaining() {
+                    quote! {
+                        #docs
+                        pub #name: Vec<anchor_lang::solana_program::account_info::AccountInfo<'info>>
+                    }
                 } else {
                     quote! {
                         #docs
@@ -110,6 +115,12 @@ pub fn generate(
                             account_metas.push(anchor_lang::solana_program::instruction::AccountMeta::new_readonly(#program_id, false));
                         }
                     }
+                } else if f.constraints.is_remaining() {
+                    quote! {
+                        for #name in &self.#name {
+                            account_metas.push(#meta(anchor_lang::Key::key(#name), #is_signer));
+                        }
+                    }
                 } else {
                     quote! {
                         account_metas.push(#meta(anchor_lang::Key::key(&self.#name), #is_signer));
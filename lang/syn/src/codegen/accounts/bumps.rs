@@ -34,6 +34,8 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                     let constraints = constraints::linearize(&f.constraints);
                     let (bump_field, bump_default_field) = if f.is_optional {
                         (quote!(pub #ident: Option<u8>), quote!(#ident: None))
+                    } else if f.constraints.is_remaining() {
+                        (quote!(pub #ident: Vec<u8>), quote!(#ident: Vec::new()))
                     } else {
                         (quote!(pub #ident: u8), quote!(#ident: u8::MAX))
                     };
@@ -49,6 +49,20 @@ pub fn generate(f: &Field, accs: &AccountsStruct) -> proc_macro2::TokenStream {
         };
     }
 
+    // Remaining accounts are checked one element at a time, with the position of the element
+    // available as `<field>_index` e.g. for use in seeds.
+    if f.constraints.is_remaining() && !constraints.is_empty() {
+        let ident = &f.ident;
+        let index = format_ident!("{}_index", ident);
+        all_checks = quote! {
+            for (__index, #ident) in #ident.iter().enumerate() {
+                #[allow(unused_variables)]
+                let #index = __index;
+                #all_checks
+            }
+        };
+    }
+
     quote! {
         #rent
         #all_checks
@@ -89,6 +103,11 @@ pub fn linearize(c_group: &ConstraintGroup) -> Vec<Constraint> {
         token_account,
         mint,
         realloc,
+        instructions,
+        upgrade_authority,
+        signers,
+        remaining: _,
+        dup: _,
     } = c_group.clone();
 
     let mut constraints = Vec::new();
@@ -137,6 +156,18 @@ pub fn linearize(c_group: &ConstraintGroup) -> Vec<Constraint> {
     if let Some(c) = mint {
         constraints.push(Constraint::Mint(c));
     }
+    constraints.append(
+        &mut instructions
+            .into_iter()
+            .map(Constraint::Instructions)
+            .collect(),
+    );
+    if let Some(c) = upgrade_authority {
+        constraints.push(Constraint::UpgradeAuthority(c));
+    }
+    if let Some(c) = signers {
+        constraints.push(Constraint::Signers(c));
+    }
     constraints
 }
 
@@ -162,6 +193,9 @@ fn generate_constraint(
         Constraint::TokenAccount(c) => generate_constraint_token_account(f, c, accs),
         Constraint::Mint(c) => generate_constraint_mint(f, c, accs),
         Constraint::Realloc(c) => generate_constraint_realloc(f, c, accs),
+        Constraint::Instructions(c) => generate_constraint_instructions(f, c),
+        Constraint::UpgradeAuthority(c) => generate_constraint_upgrade_authority(f, c, accs),
+        Constraint::Signers(c) => generate_constraint_signers(f, c),
     }
 }
 
@@ -192,6 +226,115 @@ fn generate_constraint_address(f: &Field, c: &ConstraintAddress) -> proc_macro2:
     }
 }
 
+fn generate_constraint_instructions(
+    f: &Field,
+    c: &ConstraintInstructions,
+) -> proc_macro2::TokenStream {
+    let ident = &f.ident;
+    let name_str = ident.to_string();
+    let account_ref = generate_account_ref(f);
+    let matcher = &c.matcher;
+    let (position, error) = match c.position {
+        InstructionsPosition::Before => (
+            quote! { position_before(#matcher)?.is_none() },
+            quote! { ConstraintInstructionsMissing },
+        ),
+        InstructionsPosition::After => (
+            quote! { position_after(#matcher)?.is_none() },
+            quote! { ConstraintInstructionsMissing },
+        ),
+        InstructionsPosition::NotBefore => (
+            quote! { position_before(#matcher)?.is_some() },
+            quote! { ConstraintInstructionsForbidden },
+        ),
+        InstructionsPosition::NotAfter => (
+            quote! { position_after(#matcher)?.is_some() },
+            quote! { ConstraintInstructionsForbidden },
+        ),
+    };
+    let error = generate_custom_error(ident, &c.error, error, &None);
+
+    quote! {
+        {
+            let instructions = anchor_lang::introspection::TransactionInstructions::new(#account_ref)
+                .map_err(|e| e.with_account_name(#name_str))?;
+            if instructions.#position {
+                return #error;
+            }
+        }
+    }
+}
+
+fn generate_constraint_upgrade_authority(
+    f: &Field,
+    c: &ConstraintUpgradeAuthority,
+    accs: &AccountsStruct,
+) -> proc_macro2::TokenStream {
+    let ident = &f.ident;
+    let name_str = ident.to_string();
+    let account_ref = generate_account_ref(f);
+    let program_data = &c.program_data;
+    let program_data_optional_check =
+        OptionalCheckScope::new_with_field(accs, ident).generate_check(program_data);
+    let error = generate_custom_error(
+        ident,
+        &c.error,
+        quote! { ConstraintUpgradeAuthority },
+        &None,
+    );
+
+    quote! {
+        {
+            #program_data_optional_check
+            if !#account_ref.is_signer {
+                return Err(anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintSigner).with_account_name(#name_str));
+            }
+
+            let actual = #program_data.key();
+            let expected = anchor_lang::solana_program::bpf_loader_upgradeable::get_program_data_address(__program_id);
+            if actual != expected {
+                return Err(anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintUpgradeAuthority)
+                    .with_account_name(#name_str)
+                    .with_pubkeys((actual, expected)));
+            }
+
+            if #program_data.upgrade_authority_address != Some(*#account_ref.key) {
+                return #error;
+            }
+        }
+    }
+}
+
+// The signers are looked up in all the accounts given to the struct, including the remaining
+// accounts, see `try_accounts`. A threshold of 0 is rejected, since it would pass without any
+// signer, e.g. for a multisig which isn't initialized.
+fn generate_constraint_signers(f: &Field, c: &ConstraintSignersGroup) -> proc_macro2::TokenStream {
+    let name_str = f.ident.to_string();
+    let signers = &c.signers;
+    let threshold = &c.threshold;
+    let error = match &c.error {
+        Some(error) => quote! { anchor_lang::error::Error::from(#error) },
+        None => quote! {
+            anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintSignerThreshold)
+        },
+    };
+
+    quote! {
+        {
+            let found = anchor_lang::__private::count_signers(
+                AsRef::<[anchor_lang::solana_program::pubkey::Pubkey]>::as_ref(&(#signers)),
+                __all_accounts,
+            );
+            let required = (#threshold) as usize;
+            if required == 0 || found < required {
+                return Err(#error
+                    .with_account_name(#name_str)
+                    .with_values((found, required)));
+            }
+        }
+    }
+}
+
 pub fn generate_constraint_init(
     f: &Field,
     c: &ConstraintInitGroup,
@@ -414,10 +557,22 @@ fn generate_constraint_realloc(
 ) -> proc_macro2::TokenStream {
     let field = &f.ident;
     let account_name = field.to_string();
-    let new_space = &c.space;
     let payer = &c.payer;
     let zero = &c.zero;
 
+    let new_space = match &c.space {
+        Some(space) => space,
+        // Resized on exit, once the data is final.
+        None => {
+            return quote! {
+                if __reallocs.contains(&#field.key()) {
+                    return Err(anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::AccountDuplicateReallocs).with_account_name(#account_name));
+                }
+                __reallocs.insert(#field.key());
+            }
+        }
+    };
+
     let mut optional_check_scope = OptionalCheckScope::new_with_field(accs, field);
     let payer_optional_check = optional_check_scope.generate_check(payer);
     let system_program_optional_check =
@@ -1164,10 +1319,12 @@ fn generate_constraint_seeds(f: &Field, c: &ConstraintSeedsGroup) -> proc_macro2
         let maybe_seeds_plus_comma = (!s.is_empty()).then(|| {
             quote! { #s, }
         });
-        let bump = if f.is_optional {
-            quote!(Some(__bump))
+        let set_bump = if f.is_optional {
+            quote!(__bumps.#name = Some(__bump);)
+        } else if f.constraints.is_remaining() {
+            quote!(__bumps.#name.push(__bump);)
         } else {
-            quote!(__bump)
+            quote!(__bumps.#name = __bump;)
         };
 
         // Not init here, so do all the checks.
@@ -1178,7 +1335,7 @@ fn generate_constraint_seeds(f: &Field, c: &ConstraintSeedsGroup) -> proc_macro2
                     &[#maybe_seeds_plus_comma],
                     &#deriving_program_id,
                 );
-                __bumps.#name = #bump;
+                #set_bump
             },
             // Bump target given. Use it.
             Some(b) => quote! {
@@ -1688,7 +1845,7 @@ pub fn generate_constraint_executable(
     // because we are only acting on the field, we know it isnt optional at this point
     // as it was unwrapped in `generate_constraint`
     quote! {
-        if !#account_ref.executable {
+        if !anchor_lang::is_program_executable(#account_ref) {
             return Err(anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintExecutable).with_account_name(#name_str));
         }
     }
@@ -0,0 +1,90 @@
+use crate::codegen::accounts::{bumps, generics, ParsedGenerics};
+use crate::{AccountField, AccountTy, AccountsStruct, InterfaceAccountTy, Ty};
+use quote::quote;
+
+// Generates the `Accounts` trait method visiting the mutable accounts of the struct, which is
+// used by the program entrypoint to reject the same account being given for multiple mutable
+// fields.
+//
+// Only the accounts that are deserialized and written back on exit are visited, and fields with
+// the `dup` constraint are skipped.
+pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
+    let ParsedGenerics { trait_generics, .. } = generics(accs);
+
+    let visits = accs.fields.iter().map(|af| match af {
+        AccountField::CompositeField(s) => {
+            let ident = &s.ident;
+            let name_str = ident.to_string();
+            let ty = &s.raw_field.ty;
+            let bumps = bumps::generate_bumps_name(&s.symbol);
+            quote! {
+                path.push(#name_str);
+                <#ty as anchor_lang::Accounts<#trait_generics, #bumps>>::__anchor_private_visit_mut_accounts(
+                    &self.#ident,
+                    path,
+                    visit,
+                );
+                path.pop();
+            }
+        }
+        AccountField::Field(f) => {
+            let is_data_account = matches!(
+                f.ty,
+                Ty::Account(_)
+                    | Ty::LazyAccount(_)
+                    | Ty::AccountLoader(_)
+                    | Ty::InterfaceAccount(_)
+            );
+            let is_mutable = f.constraints.is_mutable() || f.constraints.init.is_some();
+            if !is_data_account || !is_mutable || f.constraints.is_dup() {
+                return quote! {};
+            }
+
+            let ident = &f.ident;
+            let name_str = ident.to_string();
+            let key = match &f.ty {
+                Ty::Account(AccountTy { boxed: true, .. })
+                | Ty::InterfaceAccount(InterfaceAccountTy { boxed: true, .. }) => {
+                    quote! { anchor_lang::Key::key(account.as_ref()) }
+                }
+                _ => quote! { anchor_lang::Key::key(account) },
+            };
+            let visit = if f.is_optional {
+                quote! {
+                    if let Some(account) = &self.#ident {
+                        visit(path, &#key);
+                    }
+                }
+            } else if f.constraints.is_remaining() {
+                quote! {
+                    for account in &self.#ident {
+                        visit(path, &#key);
+                    }
+                }
+            } else {
+                quote! {
+                    {
+                        let account = &self.#ident;
+                        visit(path, &#key);
+                    }
+                }
+            };
+            quote! {
+                path.push(#name_str);
+                #visit
+                path.pop();
+            }
+        }
+    });
+
+    quote! {
+        #[allow(unused_variables, clippy::ptr_arg)]
+        fn __anchor_private_visit_mut_accounts(
+            &self,
+            path: &mut Vec<&'static str>,
+            visit: &mut dyn FnMut(&[&'static str], &anchor_lang::solana_program::pubkey::Pubkey),
+        ) {
+            #(#visits)*
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::accounts_codegen::constraints::OptionalCheckScope;
 use crate::codegen::accounts::{generics, ParsedGenerics};
-use crate::{AccountField, AccountsStruct, Ty};
+use crate::{
+    AccountField, AccountLoaderTy, AccountTy, AccountsStruct, ConstraintReallocGroup,
+    LazyAccountTy, Ty,
+};
 use quote::quote;
 
 // Generates the `Exit` trait implementation.
@@ -29,19 +32,103 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                 let ident = &f.ident;
                 let name_str = ident.to_string();
                 if f.constraints.is_close() {
-                    let close_target = &f.constraints.close.as_ref().unwrap().sol_dest;
+                    let close = f.constraints.close.as_ref().unwrap();
+                    let close_target = &close.sol_dest;
                     let close_target_optional_check =
                         OptionalCheckScope::new(accs).generate_check(close_target);
 
+                    let close = if close.tombstone {
+                        let account_ty = match &f.ty {
+                            Ty::Account(AccountTy {
+                                account_type_path, ..
+                            })
+                            | Ty::LazyAccount(LazyAccountTy { account_type_path })
+                            | Ty::AccountLoader(AccountLoaderTy { account_type_path }) => {
+                                account_type_path
+                            }
+                            _ => unreachable!("Close is only allowed on program accounts"),
+                        };
+                        let close = quote! {
+                            anchor_lang::__private::close_tombstone(
+                                #ident.to_account_info(),
+                                #close_target.to_account_info(),
+                                <#account_ty as anchor_lang::Tombstone>::TOMBSTONE,
+                            ).map_err(|e| e.with_account_name(#name_str))?;
+                        };
+                        if f.is_optional {
+                            quote! {
+                                if let Some(#ident) = &self.#ident {
+                                    #close
+                                }
+                            }
+                        } else {
+                            quote! {
+                                let #ident = &self.#ident;
+                                #close
+                            }
+                        }
+                    } else {
+                        quote! {
+                            anchor_lang::AccountsClose::close(
+                                &self.#ident,
+                                #close_target.to_account_info(),
+                            ).map_err(|e| e.with_account_name(#name_str))?;
+                        }
+                    };
+
                     quote! {
                         {
                             let #close_target = &self.#close_target;
                             #close_target_optional_check
-                            anchor_lang::AccountsClose::close(
-                                &self.#ident,
-                                #close_target.to_account_info(),
+                            #close
+                        }
+                    }
+                } else if let Some(ConstraintReallocGroup {
+                    payer, space: None, ..
+                }) = &f.constraints.realloc
+                {
+                    let mut optional_check_scope = OptionalCheckScope::new(accs);
+                    let payer_optional_check = optional_check_scope.generate_check(payer);
+                    let system_program_optional_check =
+                        optional_check_scope.generate_check(quote! {system_program});
+                    let data = match &f.ty {
+                        Ty::Account(AccountTy { boxed: true, .. }) => quote! { &***#ident },
+                        _ => quote! { &**#ident },
+                    };
+                    let resize = quote! {
+                        if !anchor_lang::__private::is_closed(&#ident.to_account_info()) {
+                            let #payer = &self.#payer;
+                            let system_program = &self.system_program;
+                            #payer_optional_check
+                            #system_program_optional_check
+                            anchor_lang::__private::realloc(
+                                &#ident.to_account_info(),
+                                anchor_lang::AccountSerialize::serialized_len(#data)?,
+                                &#payer.to_account_info(),
+                                &system_program.to_account_info(),
                             ).map_err(|e| e.with_account_name(#name_str))?;
                         }
+                    };
+                    let resize = if f.is_optional {
+                        quote! {
+                            if let Some(#ident) = &self.#ident {
+                                #resize
+                            }
+                        }
+                    } else {
+                        quote! {
+                            {
+                                let #ident = &self.#ident;
+                                #resize
+                            }
+                        }
+                    };
+
+                    quote! {
+                        // Resize the account to fit the data before writing it.
+                        #resize
+                        anchor_lang::AccountsExit::exit(&self.#ident, program_id)
+                            .map_err(|e| e.with_account_name(#name_str))?;
                     }
                 } else {
                     match f.constraints.is_mutable() {
@@ -9,9 +9,12 @@ pub mod __client_accounts;
 pub mod __cpi_client_accounts;
 mod bumps;
 mod constraints;
+mod duplicate_mut;
 mod exit;
+mod pda;
 mod to_account_infos;
 mod to_account_metas;
+pub(crate) mod transfer_hook;
 mod try_accounts;
 
 pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
@@ -19,7 +22,9 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
     let impl_to_account_infos = to_account_infos::generate(accs);
     let impl_to_account_metas = to_account_metas::generate(accs);
     let impl_exit = exit::generate(accs);
+    let impl_pda = pda::generate(accs);
     let bumps_struct = bumps::generate(accs);
+    let impl_transfer_hook_accounts = transfer_hook::generate(accs);
 
     let __client_accounts_mod = __client_accounts::generate(accs, quote!(crate::ID));
     let __cpi_client_accounts_mod = __cpi_client_accounts::generate(accs, quote!(crate::ID));
@@ -29,7 +34,9 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
         #impl_to_account_infos
         #impl_to_account_metas
         #impl_exit
+        #impl_pda
         #bumps_struct
+        #impl_transfer_hook_accounts
 
         #__client_accounts_mod
         #__cpi_client_accounts_mod
@@ -0,0 +1,230 @@
+use crate::{AccountField, AccountTy, AccountsStruct, Field, InterfaceAccountTy, Ty};
+use proc_macro2::{Delimiter, Group, Ident, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+
+// Generates the `find_<field>_address` and `create_<field>_address` associated functions from the
+// `seeds` constraints of the struct.
+//
+// The accounts and instruction arguments used in the seeds become the parameters of the
+// functions, after the `program_id` deriving the PDA unless `seeds::program` is used:
+//
+// - `account.key()` and `account.key` take the key of the account, as `&Pubkey`
+// - `account.field` takes the data of the account, as a reference to the account type
+// - `arg` takes the instruction argument, by value
+//
+// Constants are used as they are. No functions are generated for the fields with other seeds,
+// e.g. seeds calling methods on an account.
+pub fn generate(accs: &AccountsStruct) -> TokenStream {
+    let name = &accs.ident;
+    let (impl_generics, ty_generics, where_clause) = accs.generics.split_for_impl();
+
+    let helpers = accs
+        .fields
+        .iter()
+        .filter_map(|af| match af {
+            AccountField::Field(f) => generate_helpers(f, accs),
+            AccountField::CompositeField(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if helpers.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#helpers)*
+        }
+    }
+}
+
+/// The kind of a parameter of the generated functions.
+#[derive(PartialEq)]
+enum Param {
+    /// Instruction argument, taken by value.
+    Arg(Box<syn::Type>),
+    /// Key of an account.
+    Key,
+    /// Data of an account.
+    Data(Box<syn::TypePath>),
+}
+
+fn generate_helpers(f: &Field, accs: &AccountsStruct) -> Option<TokenStream> {
+    let c = f.constraints.seeds.as_ref()?;
+    // The seeds of remaining accounts depend on the position of the element.
+    if f.constraints.is_remaining() {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let seeds = c
+        .seeds
+        .iter()
+        .map(|seed| rewrite(seed.to_token_stream(), f, accs, &mut params))
+        .collect::<Option<Vec<_>>>()?;
+    // The PDA is derived from the program given to the functions unless `seeds::program` is used.
+    let (program_id, program_param) = match &c.program_seed {
+        Some(program) => {
+            let program = rewrite(quote! { #program.key() }, f, accs, &mut params)?;
+            (quote! { &#program }, None)
+        }
+        // The seeds can't use another parameter with the same name.
+        None if params.iter().any(|(ident, _)| ident == "program_id") => return None,
+        None => (
+            quote! { program_id },
+            Some(quote! { program_id: &anchor_lang::solana_program::pubkey::Pubkey }),
+        ),
+    };
+
+    let params = program_param
+        .into_iter()
+        .chain(params.into_iter().map(|(ident, param)| match param {
+            Param::Arg(ty) => quote! { #ident: #ty },
+            Param::Key => quote! { #ident: &anchor_lang::solana_program::pubkey::Pubkey },
+            Param::Data(ty) => quote! { #ident: &#ty },
+        }))
+        .collect::<Vec<_>>();
+
+    let name = &f.ident;
+    let name_str = name.to_string();
+    let find_fn = format_ident!("find_{}_address", name);
+    let create_fn = format_ident!("create_{}_address", name);
+    let find_doc = format!(" Finds the address and bump of the `{name_str}` PDA.");
+    let create_doc = format!(
+        " Creates the address of the `{name_str}` PDA with the given bump, e.g. a stored one."
+    );
+
+    Some(quote! {
+        #[doc = #find_doc]
+        #[allow(clippy::too_many_arguments)]
+        pub fn #find_fn(
+            #(#params,)*
+        ) -> (anchor_lang::solana_program::pubkey::Pubkey, u8) {
+            anchor_lang::solana_program::pubkey::Pubkey::find_program_address(
+                &[#(#seeds),*],
+                #program_id,
+            )
+        }
+
+        #[doc = #create_doc]
+        #[allow(clippy::too_many_arguments)]
+        pub fn #create_fn(
+            #(#params,)*
+            bump: u8,
+        ) -> anchor_lang::Result<anchor_lang::solana_program::pubkey::Pubkey> {
+            anchor_lang::solana_program::pubkey::Pubkey::create_program_address(
+                &[#(#seeds,)* &[bump][..]],
+                #program_id,
+            )
+            .map_err(|_| {
+                anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::ConstraintSeeds)
+                    .with_account_name(#name_str)
+            })
+        }
+    })
+}
+
+/// Rewrites a seed expression to use the parameters of the generated functions, adding the
+/// accounts and instruction arguments it uses to `params`.
+///
+/// Returns `None` if the seed uses an account in an unsupported way.
+fn rewrite(
+    tokens: TokenStream,
+    f: &Field,
+    accs: &AccountsStruct,
+    params: &mut Vec<(Ident, Param)>,
+) -> Option<TokenStream> {
+    let args = accs.instruction_args().unwrap_or_default();
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let is_dot =
+        |tt: Option<&TokenTree>| matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == '.');
+
+    let mut output = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let tt = &tokens[i];
+        i += 1;
+
+        let ident = match tt {
+            TokenTree::Group(group) => {
+                let stream = rewrite(group.stream(), f, accs, params)?;
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+                output.push(TokenTree::Group(new_group));
+                continue;
+            }
+            TokenTree::Ident(ident) => ident,
+            _ => {
+                output.push(tt.clone());
+                continue;
+            }
+        };
+
+        // Skip paths and fields, e.g. `MY_CONST` of `Self::MY_CONST` or `value` of `x.value`.
+        let is_path_or_member = i >= 2
+            && matches!(
+                &tokens[i - 2],
+                TokenTree::Punct(p) if p.as_char() == '.'
+                    || (p.as_char() == ':' && p.spacing() == Spacing::Alone)
+            );
+        let name = ident.to_string();
+        let param = if is_path_or_member {
+            None
+        } else if args.contains_key(&name) {
+            Some(Param::Arg(Box::new(syn::parse_str(&args[&name]).ok()?)))
+        } else if let Some(field) = accs.fields.iter().find(|af| *af.ident() == name) {
+            let field = match field {
+                AccountField::Field(field) if field.ident != f.ident => field,
+                _ => return None,
+            };
+            let member = match (is_dot(tokens.get(i)), tokens.get(i + 1)) {
+                (true, Some(TokenTree::Ident(member))) => member.to_string(),
+                _ => return None,
+            };
+            if member == "key" {
+                // Use the key directly, i.e. `account.key()` and `account.key` become `account`.
+                i += 2;
+                if is_parens(tokens.get(i), true) {
+                    i += 1;
+                }
+                Some(Param::Key)
+            } else if is_parens(tokens.get(i + 2), false) {
+                // Methods of the account, e.g. `account.load()?.value`
+                return None;
+            } else {
+                match &field.ty {
+                    Ty::Account(AccountTy {
+                        account_type_path, ..
+                    })
+                    | Ty::InterfaceAccount(InterfaceAccountTy {
+                        account_type_path, ..
+                    }) => Some(Param::Data(Box::new(account_type_path.clone()))),
+                    _ => return None,
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some(param) = param {
+            match params.iter().find(|(existing, _)| *existing == name) {
+                // The same account can't be used both by key and by data.
+                Some((_, existing)) if *existing != param => return None,
+                Some(_) => {}
+                None => params.push((ident.clone(), param)),
+            }
+        }
+        output.push(tt.clone());
+    }
+
+    Some(output.into_iter().collect())
+}
+
+fn is_parens(tt: Option<&TokenTree>, empty: bool) -> bool {
+    match tt {
+        Some(TokenTree::Group(g)) => {
+            g.delimiter() == Delimiter::Parenthesis && (!empty || g.stream().is_empty())
+        }
+        _ => false,
+    }
+}
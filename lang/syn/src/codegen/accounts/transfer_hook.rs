@@ -0,0 +1,358 @@
+use crate::{AccountField, AccountsStruct, Field, Ty};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::Expr;
+
+/// Number of accounts required by the transfer hook interface `Execute` instruction.
+const INTERFACE_ACCOUNTS_LEN: usize = 5;
+
+/// Length of the `Execute` instruction discriminator, which prefixes the instruction data.
+const DISCRIMINATOR_LEN: usize = 8;
+
+/// Extra account of the transfer hook `Execute` instruction.
+pub(crate) struct ExtraAccountMeta<'a> {
+    #[cfg_attr(not(feature = "idl-build"), allow(dead_code))]
+    pub field: &'a Field,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub address: ExtraAccountAddress,
+}
+
+/// How the token program resolves the address of an extra account.
+pub(crate) enum ExtraAccountAddress {
+    /// Fixed address.
+    Pubkey(proc_macro2::TokenStream),
+    /// PDA of the transfer hook program, or of the program at `program_index` of the accounts.
+    Seeds {
+        seeds: Vec<ExtraAccountSeed>,
+        program_index: Option<u8>,
+    },
+}
+
+/// Seed of an extra account PDA.
+pub(crate) enum ExtraAccountSeed {
+    /// Constant bytes.
+    Literal(Box<Expr>),
+    /// Slice of the `Execute` instruction data.
+    InstructionData { index: u8, length: u8 },
+    /// Key of the account at `index` of the accounts.
+    AccountKey { index: u8 },
+}
+
+/// Generates the `TransferHookAccounts` implementation, which derives the extra account metas of
+/// the `Execute` instruction from the fields after the interface accounts.
+pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
+    if !accs.transfer_hook {
+        return quote! {};
+    }
+
+    let name = &accs.ident;
+    let (impl_generics, ty_generics, where_clause) = accs.generics.split_for_impl();
+    let metas = match extra_account_metas(accs) {
+        Ok(metas) => metas
+            .iter()
+            .map(generate_extra_account_meta)
+            .collect::<Vec<_>>(),
+        Err(e) => return e.to_compile_error(),
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::anchor_spl::token_interface::TransferHookAccounts for #name #ty_generics #where_clause {
+            fn extra_account_metas() -> anchor_lang::Result<Vec<::anchor_spl::token_interface::spl_tlv_account_resolution::account::ExtraAccountMeta>> {
+                use ::anchor_spl::token_interface::spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+                Ok(vec![#(#metas),*])
+            }
+        }
+    }
+}
+
+/// Generates the validation of the interface accounts, executed after all constraints in
+/// `try_accounts`.
+pub fn generate_execute_check(accs: &AccountsStruct) -> proc_macro2::TokenStream {
+    if !accs.transfer_hook {
+        return quote! {};
+    }
+
+    let fields = plain_fields(accs);
+    let source = &fields[0].ident;
+    let mint = &fields[1].ident;
+    let destination = &fields[2].ident;
+    let extra_account_meta_list = &fields[4].ident;
+    quote! {
+        ::anchor_spl::token_interface::check_transfer_hook_execute_accounts(
+            __program_id,
+            &#source.to_account_info(),
+            &#mint.to_account_info(),
+            &#destination.to_account_info(),
+            &#extra_account_meta_list.to_account_info(),
+        )?;
+    }
+}
+
+fn plain_fields(accs: &AccountsStruct) -> Vec<&Field> {
+    accs.fields
+        .iter()
+        .filter_map(|af| match af {
+            AccountField::Field(f) => Some(f),
+            // Rejected by the parser
+            AccountField::CompositeField(_) => None,
+        })
+        .collect()
+}
+
+/// Resolves the extra accounts of a `#[transfer_hook]` accounts struct, which are the fields
+/// after the interface accounts.
+pub(crate) fn extra_account_metas(accs: &AccountsStruct) -> syn::Result<Vec<ExtraAccountMeta<'_>>> {
+    let fields = plain_fields(accs);
+    fields
+        .iter()
+        .enumerate()
+        .skip(INTERFACE_ACCOUNTS_LEN)
+        .map(|(i, f)| parse_extra_account_meta(accs, &fields[..i], f))
+        .collect()
+}
+
+fn parse_extra_account_meta<'a>(
+    accs: &AccountsStruct,
+    prev_fields: &[&Field],
+    f: &'a Field,
+) -> syn::Result<ExtraAccountMeta<'a>> {
+    let address = if let Some(address) = &f.constraints.address {
+        let address = &address.address;
+        ExtraAccountAddress::Pubkey(quote! { #address })
+    } else if let Some(seeds_group) = &f.constraints.seeds {
+        let seeds = seeds_group
+            .seeds
+            .iter()
+            .map(|seed| parse_seed(accs, prev_fields, seed))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let program_index = seeds_group
+            .program_seed
+            .as_ref()
+            .map(|program_seed| {
+                let index =
+                    field_key_index(prev_fields, strip_seed(program_seed)).ok_or_else(|| {
+                        syn::Error::new(
+                            program_seed.span(),
+                            "`seeds::program` of an extra transfer hook account must be a previous account",
+                        )
+                    })?;
+                to_u8(index, program_seed, "account index")
+            })
+            .transpose()?;
+        ExtraAccountAddress::Seeds {
+            seeds,
+            program_index,
+        }
+    } else {
+        match &f.ty {
+            Ty::Program(ty) => {
+                let program = &ty.account_type_path;
+                ExtraAccountAddress::Pubkey(quote! { <#program as anchor_lang::Id>::id() })
+            }
+            // Rejected by the parser
+            _ => {
+                return Err(syn::Error::new(
+                    f.ident.span(),
+                    "unresolvable extra transfer hook account",
+                ))
+            }
+        }
+    };
+
+    Ok(ExtraAccountMeta {
+        field: f,
+        is_signer: matches!(f.ty, Ty::Signer) || f.constraints.is_signer(),
+        is_writable: f.constraints.is_mutable(),
+        address,
+    })
+}
+
+fn generate_extra_account_meta(meta: &ExtraAccountMeta) -> proc_macro2::TokenStream {
+    let is_signer = meta.is_signer;
+    let is_writable = meta.is_writable;
+    match &meta.address {
+        ExtraAccountAddress::Pubkey(address) => quote! {
+            ExtraAccountMeta::new_with_pubkey(&#address, #is_signer, #is_writable)?
+        },
+        ExtraAccountAddress::Seeds {
+            seeds,
+            program_index,
+        } => {
+            let seeds = seeds.iter().map(generate_seed);
+            match program_index {
+                None => quote! {
+                    ExtraAccountMeta::new_with_seeds(&[#(#seeds),*], #is_signer, #is_writable)?
+                },
+                Some(program_index) => quote! {
+                    ExtraAccountMeta::new_external_pda_with_seeds(
+                        #program_index,
+                        &[#(#seeds),*],
+                        #is_signer,
+                        #is_writable,
+                    )?
+                },
+            }
+        }
+    }
+}
+
+fn generate_seed(seed: &ExtraAccountSeed) -> proc_macro2::TokenStream {
+    match seed {
+        ExtraAccountSeed::Literal(seed) => quote! {
+            Seed::Literal {
+                bytes: {
+                    let seed: &[u8] = #seed;
+                    seed.to_vec()
+                },
+            }
+        },
+        ExtraAccountSeed::InstructionData { index, length } => {
+            quote! { Seed::InstructionData { index: #index, length: #length } }
+        }
+        ExtraAccountSeed::AccountKey { index } => quote! { Seed::AccountKey { index: #index } },
+    }
+}
+
+/// Converts a PDA seed to a seed of the extra account meta list.
+fn parse_seed(
+    accs: &AccountsStruct,
+    prev_fields: &[&Field],
+    seed: &Expr,
+) -> syn::Result<ExtraAccountSeed> {
+    let stripped = strip_seed(seed);
+
+    // Account key, e.g. `mint.key().as_ref()`
+    if let Some(index) = field_key_index(prev_fields, stripped) {
+        return Ok(ExtraAccountSeed::AccountKey {
+            index: to_u8(index, seed, "account index")?,
+        });
+    }
+
+    // Instruction argument, e.g. `amount.to_le_bytes().as_ref()`
+    let args = instruction_args(accs);
+    if let Some(name) = path_ident(stripped) {
+        let mut offset = DISCRIMINATOR_LEN;
+        for (arg_name, arg_ty) in &args {
+            let len = fixed_size(arg_ty);
+            if *arg_name == name {
+                let len = len.ok_or_else(|| {
+                    syn::Error::new(
+                        seed.span(),
+                        "only fixed size instruction arguments can be used as transfer hook seeds",
+                    )
+                })?;
+                return Ok(ExtraAccountSeed::InstructionData {
+                    index: to_u8(offset, seed, "instruction data offset")?,
+                    length: to_u8(len, seed, "instruction data length")?,
+                });
+            }
+            match len {
+                Some(len) => offset += len,
+                None => break,
+            }
+        }
+    }
+
+    // Constant bytes, e.g. `b"counter".as_ref()`
+    let idents = seed
+        .to_token_stream()
+        .into_iter()
+        .filter_map(|tt| match tt {
+            proc_macro2::TokenTree::Ident(ident) => Some(ident.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let references_accounts = accs.fields.iter().any(|af| {
+        let name = match af {
+            AccountField::Field(f) => f.ident.to_string(),
+            AccountField::CompositeField(s) => s.ident.to_string(),
+        };
+        idents.contains(&name)
+    });
+    let references_args = args.iter().any(|(name, _)| idents.contains(name));
+    if !references_accounts && !references_args {
+        return Ok(ExtraAccountSeed::Literal(Box::new(seed.clone())));
+    }
+
+    Err(syn::Error::new(
+        seed.span(),
+        "unsupported transfer hook seed: seeds must be constant bytes, the key of a previous \
+        account or a fixed size instruction argument",
+    ))
+}
+
+/// Strips the reference and conversions of a seed, e.g. `&amount.to_le_bytes()` -> `amount`.
+fn strip_seed(seed: &Expr) -> &Expr {
+    match seed {
+        Expr::Reference(r) => strip_seed(&r.expr),
+        Expr::Paren(p) => strip_seed(&p.expr),
+        Expr::MethodCall(m)
+            if m.args.is_empty()
+                && ["as_ref", "to_le_bytes", "to_bytes"]
+                    .contains(&m.method.to_string().as_str()) =>
+        {
+            strip_seed(&m.receiver)
+        }
+        _ => seed,
+    }
+}
+
+/// Returns the index of the account if the expression is `<account>.key()` or `<account>`.
+fn field_key_index(prev_fields: &[&Field], expr: &Expr) -> Option<usize> {
+    let expr = match expr {
+        Expr::MethodCall(m) if m.args.is_empty() && m.method == "key" => &*m.receiver,
+        _ => expr,
+    };
+    let name = path_ident(expr)?;
+    prev_fields.iter().position(|f| f.ident == name)
+}
+
+/// Extra account metas store the indices and lengths of seeds as `u8`.
+fn to_u8(value: usize, seed: &Expr, what: &str) -> syn::Result<u8> {
+    u8::try_from(value).map_err(|_| {
+        syn::Error::new(
+            seed.span(),
+            format!("the {what} of the transfer hook seed is larger than 255"),
+        )
+    })
+}
+
+fn path_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(p) => p.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    }
+}
+
+fn instruction_args(accs: &AccountsStruct) -> Vec<(String, String)> {
+    accs.instruction_api
+        .as_ref()
+        .map(|instruction_api| {
+            instruction_api
+                .iter()
+                .filter_map(|expr| match expr {
+                    Expr::Type(expr_type) => Some((
+                        expr_type.expr.to_token_stream().to_string(),
+                        expr_type.ty.to_token_stream().to_string(),
+                    )),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Borsh serialized size of fixed size instruction argument types.
+fn fixed_size(ty: &str) -> Option<usize> {
+    match ty {
+        "u8" | "i8" | "bool" => Some(1),
+        "u16" | "i16" => Some(2),
+        "u32" | "i32" | "f32" => Some(4),
+        "u64" | "i64" | "f64" => Some(8),
+        "u128" | "i128" => Some(16),
+        "Pubkey" => Some(32),
+        _ => None,
+    }
+}
@@ -1,6 +1,12 @@
-use crate::codegen::accounts::{bumps, constraints, generics, ParsedGenerics};
-use crate::{AccountField, AccountsStruct};
-use quote::quote;
+use crate::codegen::accounts::{
+    bumps, constraints, duplicate_mut, generics, transfer_hook, ParsedGenerics,
+};
+use crate::{
+    AccountField, AccountLoaderTy, AccountTy, AccountsStruct, Field, InterfaceAccountTy,
+    LazyAccountTy, Ty,
+};
+use proc_macro2::{Group, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
 use syn::Expr;
 
 // Generates the `Accounts` trait implementation.
@@ -22,10 +28,21 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                 AccountField::CompositeField(s) => {
                     let name = &s.ident;
                     let ty = &s.raw_field.ty;
+                    // Forwarded instruction arguments are serialized as the instruction data
+                    // of the composite field, which otherwise gets the instruction data as is.
+                    let ix_data = match &s.instruction_args {
+                        Some(args) => {
+                            let args = args.iter();
+                            quote! {
+                                &<#ty>::__anchor_private_serialize_ix_args(#(&(#args)),*)?
+                            }
+                        }
+                        None => quote! { __ix_data },
+                    };
                     quote! {
                         #[cfg(feature = "anchor-debug")]
                         ::solana_program::log::sol_log(stringify!(#name));
-                        let #name: #ty = anchor_lang::Accounts::try_accounts(__program_id, __accounts, __ix_data, &mut __bumps.#name, __reallocs)?;
+                        let #name: #ty = anchor_lang::Accounts::try_accounts(__program_id, __accounts, #ix_data, &mut __bumps.#name, __reallocs)?;
                     }
                 }
                 AccountField::Field(f) => {
@@ -65,6 +82,64 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                                 *__accounts = &__accounts[1..];
                             }
                         }
+                    } else if let Some(remaining) = &f.constraints.remaining {
+                        let name = f.ident.to_string();
+                        let typed_name = f.typed_ident();
+                        // Without a length, all of the accounts left are consumed.
+                        let len = match &remaining.len {
+                            Some(len) => quote! { (#len) as usize },
+                            None => quote! { __accounts.len() },
+                        };
+                        quote! {
+                            #[cfg(feature = "anchor-debug")]
+                            ::solana_program::log::sol_log(stringify!(#typed_name));
+                            let #typed_name = {
+                                let __len = #len;
+                                if __accounts.len() < __len {
+                                    return Err(anchor_lang::error::Error::from(anchor_lang::error::ErrorCode::AccountNotEnoughKeys).with_account_name(#name));
+                                }
+                                let mut __items = Vec::with_capacity(__len);
+                                for _ in 0..__len {
+                                    __items.push(
+                                        anchor_lang::Accounts::try_accounts(__program_id, __accounts, __ix_data, __bumps, __reallocs)
+                                            .map_err(|e| e.with_account_name(#name))?
+                                    );
+                                }
+                                __items
+                            };
+                        }
+                    } else if is_placed(accs, f) {
+                        let ident = &f.ident;
+                        let name = ident.to_string();
+                        let typed_name = f.typed_ident();
+                        let ty = f.ty_decl(false);
+                        let slot = slot_ident(f);
+                        // The size of the type doesn't depend on its lifetimes, which can't be
+                        // used in the const argument
+                        let static_ty = with_static_lifetimes(ty.clone());
+                        let placement = quote! {
+                            anchor_lang::__private::Placement<{
+                                core::mem::size_of::<#static_ty>() > anchor_lang::__private::MAX_STACK_FIELD_SIZE
+                            }>
+                        };
+                        // The warning is opt-in since it would fail the builds denying warnings
+                        let warning = if cfg!(feature = "auto-boxed-warnings") {
+                            quote_spanned! {ident.span()=>
+                                <#placement>::auto_boxed();
+                            }
+                        } else {
+                            quote! {}
+                        };
+                        quote! {
+                            #[cfg(feature = "anchor-debug")]
+                            ::solana_program::log::sol_log(stringify!(#typed_name));
+                            #warning
+                            let mut #slot = <#placement as anchor_lang::__private::Place<#ty>>::place(|| {
+                                anchor_lang::Accounts::try_accounts(__program_id, __accounts, __ix_data, __bumps, __reallocs)
+                            })
+                            .map_err(|e| e.with_account_name(#name))?;
+                            let #ident: &mut #ty = &mut #slot;
+                        }
                     } else {
                         let name = f.ident.to_string();
                         let typed_name = f.typed_ident();
@@ -81,6 +156,7 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
         .collect();
 
     let constraints = generate_constraints(accs);
+    let transfer_hook_check = transfer_hook::generate_execute_check(accs);
     let accounts_instance = generate_accounts_instance(accs);
     let bumps_struct_name = bumps::generate_bumps_name(&accs.ident);
 
@@ -100,23 +176,96 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                     _ => panic!("Invalid instruction declaration"),
                 })
                 .collect();
+            // Composite fields get the instruction data from the start as well
             quote! {
-                let mut __ix_data = __ix_data;
                 #[derive(anchor_lang::AnchorSerialize, anchor_lang::AnchorDeserialize)]
                 struct __Args {
                     #strct_inner
                 }
                 let __Args {
                     #(#field_names),*
-                } = __Args::deserialize(&mut __ix_data)
+                } = __Args::deserialize(&mut &__ix_data[..])
                     .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
             }
         }
     };
+    let impl_serialize_ix_args = generate_serialize_ix_args(accs);
+
+    // `signers` constraints look up the signers in all the accounts, including the remaining ones.
+    // In composite structs, these are the accounts from the struct on, since `try_accounts` only
+    // gets the accounts which aren't consumed by the parent yet.
+    let all_accounts = if accs.fields.iter().any(|af| match af {
+        AccountField::Field(f) => f.constraints.signers.is_some(),
+        AccountField::CompositeField(_) => false,
+    }) {
+        quote! { let __all_accounts = *__accounts; }
+    } else {
+        quote! {}
+    };
+
+    // Number of accounts consumed by each field, which is unknown for `remaining` fields, and for
+    // all of them with a `signers` constraint since it looks up the remaining accounts.
+    let accounts_len = if all_accounts.is_empty() {
+        let lens = accs.fields.iter().map(|af| match af {
+            AccountField::CompositeField(s) => {
+                let ty = &s.raw_field.ty;
+                let bumps = bumps::generate_bumps_name(&s.symbol);
+                quote! { <#ty as anchor_lang::Accounts<#trait_generics, #bumps>>::ACCOUNTS_LEN }
+            }
+            AccountField::Field(f) if f.constraints.remaining.is_some() => quote! { None },
+            AccountField::Field(_) => quote! { Some(1) },
+        });
+        quote! { anchor_lang::__private::accounts_len(&[#(#lens),*]) }
+    } else {
+        quote! { None }
+    };
+
+    // Discriminators of the account types of the fields, and of the nested structs.
+    let discriminator_items = accs.fields.iter().filter_map(|af| match af {
+        AccountField::Field(f) => {
+            let path = match &f.ty {
+                Ty::Account(AccountTy {
+                    account_type_path, ..
+                })
+                | Ty::AccountLoader(AccountLoaderTy { account_type_path })
+                | Ty::LazyAccount(LazyAccountTy { account_type_path })
+                | Ty::InterfaceAccount(InterfaceAccountTy {
+                    account_type_path, ..
+                }) => account_type_path,
+                _ => return None,
+            };
+            let name = path.path.segments.last()?.ident.to_string();
+            Some(quote! {
+                (#name, anchor_lang::__private::DiscriminatorProbe::<#path>::DISCRIMINATOR)
+            })
+        }
+        AccountField::CompositeField(_) => None,
+    });
+    let discriminator_nested = accs.fields.iter().filter_map(|af| match af {
+        AccountField::CompositeField(s) => {
+            let ty = &s.raw_field.ty;
+            let bumps = bumps::generate_bumps_name(&s.symbol);
+            Some(quote! {
+                &<#ty as anchor_lang::Accounts<#trait_generics, #bumps>>::__ANCHOR_PRIVATE_DISCRIMINATORS
+            })
+        }
+        AccountField::Field(_) => None,
+    });
+
+    let visit_mut_accounts = duplicate_mut::generate(accs);
 
     quote! {
         #[automatically_derived]
         impl<#combined_generics> anchor_lang::Accounts<#trait_generics, #bumps_struct_name> for #name<#struct_generics> #where_clause {
+            const ACCOUNTS_LEN: Option<usize> = #accounts_len;
+            const __ANCHOR_PRIVATE_DISCRIMINATORS: anchor_lang::__private::DiscriminatorTree = {
+                use anchor_lang::__private::DiscriminatorProbeFallback as _;
+                anchor_lang::__private::DiscriminatorTree {
+                    items: &[#(#discriminator_items),*],
+                    nested: &[#(#discriminator_nested),*],
+                }
+            };
+
             #[inline(never)]
             fn try_accounts(
                 __program_id: &anchor_lang::solana_program::pubkey::Pubkey,
@@ -125,15 +274,60 @@ pub fn generate(accs: &AccountsStruct) -> proc_macro2::TokenStream {
                 __bumps: &mut #bumps_struct_name,
                 __reallocs: &mut std::collections::BTreeSet<anchor_lang::solana_program::pubkey::Pubkey>,
             ) -> anchor_lang::Result<Self> {
+                #all_accounts
                 // Deserialize instruction, if declared.
                 #ix_de
                 // Deserialize each account.
                 #(#deser_fields)*
                 // Execute accounts constraints.
                 #constraints
+                #transfer_hook_check
                 // Success. Return the validated accounts.
                 Ok(#accounts_instance)
             }
+
+            #visit_mut_accounts
+        }
+
+        #impl_serialize_ix_args
+    }
+}
+
+// Generates the function serializing the instruction arguments forwarded to the struct when it's
+// a composite field, see `#[instruction(..)]` on composite fields.
+fn generate_serialize_ix_args(accs: &AccountsStruct) -> proc_macro2::TokenStream {
+    let ix_api = match &accs.instruction_api {
+        Some(ix_api) => ix_api,
+        None => return quote! {},
+    };
+    let (params, names): (Vec<_>, Vec<_>) = ix_api
+        .iter()
+        .map(|expr: &Expr| match expr {
+            Expr::Type(expr_type) => {
+                let name = &expr_type.expr;
+                let ty = &expr_type.ty;
+                (quote! { #name: &#ty }, name)
+            }
+            _ => panic!("Invalid instruction declaration"),
+        })
+        .unzip();
+
+    let name = &accs.ident;
+    let (impl_generics, ty_generics, where_clause) = accs.generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc(hidden)]
+            pub fn __anchor_private_serialize_ix_args(
+                #(#params),*
+            ) -> anchor_lang::Result<Vec<u8>> {
+                let mut __ix_data = Vec::new();
+                #(
+                    anchor_lang::AnchorSerialize::serialize(#names, &mut __ix_data)
+                        .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotSerialize)?;
+                )*
+                Ok(__ix_data)
+            }
         }
     }
 }
@@ -178,13 +372,19 @@ pub fn generate_accounts_instance(accs: &AccountsStruct) -> proc_macro2::TokenSt
     let return_tys: Vec<proc_macro2::TokenStream> = accs
         .fields
         .iter()
-        .map(|f: &AccountField| {
-            let name = match f {
-                AccountField::CompositeField(s) => &s.ident,
-                AccountField::Field(f) => &f.ident,
-            };
-            quote! {
-                #name
+        .map(|f: &AccountField| match f {
+            AccountField::CompositeField(s) => {
+                let name = &s.ident;
+                quote! { #name }
+            }
+            AccountField::Field(f) if is_placed(accs, f) => {
+                let name = &f.ident;
+                let slot = slot_ident(f);
+                quote! { #name: anchor_lang::__private::Slot::take(#slot) }
+            }
+            AccountField::Field(f) => {
+                let name = &f.ident;
+                quote! { #name }
             }
         })
         .collect();
@@ -202,3 +402,55 @@ fn is_init(af: &AccountField) -> bool {
         AccountField::Field(f) => f.constraints.init.is_some(),
     }
 }
+
+/// Whether the field is deserialized into a slot, which is on the heap if the field is large.
+///
+/// Only `Account` and `InterfaceAccount` fields can be large without being boxed. The size of the
+/// field can't be known if the struct has generic types.
+fn is_placed(accs: &AccountsStruct, f: &Field) -> bool {
+    let has_generic_types = accs
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+
+    !has_generic_types
+        && f.constraints.init.is_none()
+        && f.constraints.zeroed.is_none()
+        && f.constraints.remaining.is_none()
+        && matches!(
+            f.ty,
+            Ty::Account(AccountTy { boxed: false, .. })
+                | Ty::InterfaceAccount(InterfaceAccountTy { boxed: false, .. })
+        )
+}
+
+fn slot_ident(f: &Field) -> syn::Ident {
+    format_ident!("__{}_slot", f.ident)
+}
+
+/// Replaces all lifetimes of the type with `'static`.
+fn with_static_lifetimes(ty: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut is_lifetime = false;
+    ty.into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), with_static_lifetimes(group.stream()));
+                new.set_span(group.span());
+                TokenTree::Group(new)
+            }
+            TokenTree::Punct(punct) => {
+                is_lifetime = punct.as_char() == '\'';
+                TokenTree::Punct(punct)
+            }
+            TokenTree::Ident(ident) if is_lifetime => {
+                is_lifetime = false;
+                TokenTree::Ident(proc_macro2::Ident::new("static", ident.span()))
+            }
+            token => {
+                is_lifetime = false;
+                token
+            }
+        })
+        .collect()
+}
@@ -4,16 +4,15 @@ use quote::quote;
 pub fn generate(error: Error) -> proc_macro2::TokenStream {
     let error_enum = &error.raw_enum;
     let enum_name = &error.ident;
+    let has_fields = error.codes.iter().any(|code| code.fields.is_some());
+
     // Each arm of the `match` statement for implementing `std::fmt::Display`
     // on the user defined error code.
     let display_variant_dispatch: Vec<proc_macro2::TokenStream> = error
-        .raw_enum
-        .variants
+        .codes
         .iter()
-        .enumerate()
-        .map(|(idx, variant)| {
-            let ident = &variant.ident;
-            let error_code = &error.codes[idx];
+        .map(|error_code| {
+            let ident = &error_code.ident;
             let display_msg = match &error_code.msg {
                 None => {
                     quote! {
@@ -26,8 +25,14 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
                     }
                 }
             };
+            // Bind the fields so that they can be used in the message.
+            let field_idents = error_code
+                .fields
+                .iter()
+                .flat_map(|fields| &fields.named)
+                .map(|field| &field.ident);
             quote! {
-                #enum_name::#ident => #display_msg
+                #enum_name::#ident { #(#field_idents,)* .. } => #display_msg
             }
         })
         .collect();
@@ -35,14 +40,84 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
     // Each arm of the `match` statement for implementing the `name` function
     // on the user defined error code.
     let name_variant_dispatch: Vec<proc_macro2::TokenStream> = error
-        .raw_enum
-        .variants
+        .codes
         .iter()
-        .map(|variant| {
-            let ident = &variant.ident;
+        .map(|error_code| {
+            let ident = &error_code.ident;
             let ident_name = ident.to_string();
             quote! {
-                #enum_name::#ident => #ident_name.to_string()
+                #enum_name::#ident { .. } => #ident_name.to_string()
+            }
+        })
+        .collect();
+
+    // Each arm of the `match` statement for converting the user defined error
+    // code into its number.
+    let id_variant_dispatch: Vec<proc_macro2::TokenStream> = error
+        .codes
+        .iter()
+        .map(|error_code| {
+            let ident = &error_code.ident;
+            let id = error_code.id;
+            quote! {
+                #enum_name::#ident { .. } => #id
+            }
+        })
+        .collect();
+
+    // Each arm of the `match` statement for implementing the `data` function
+    // on the user defined error code.
+    let data_variant_dispatch: Vec<proc_macro2::TokenStream> = error
+        .codes
+        .iter()
+        .map(|error_code| {
+            let ident = &error_code.ident;
+            match &error_code.fields {
+                None => quote! { #enum_name::#ident { .. } => None },
+                Some(fields) => {
+                    let field_idents = fields
+                        .named
+                        .iter()
+                        .map(|field| &field.ident)
+                        .collect::<Vec<_>>();
+                    quote! {
+                        #enum_name::#ident { #(#field_idents),* } => {
+                            let mut data = Vec::new();
+                            #(
+                                anchor_lang::AnchorSerialize::serialize(#field_idents, &mut data)
+                                    .ok()?;
+                            )*
+                            Some(data)
+                        }
+                    }
+                }
+            }
+        })
+        .collect();
+
+    // Each arm of the `match` statement for implementing `DecodeError` on the
+    // user defined error code.
+    let decode_variant_dispatch: Vec<proc_macro2::TokenStream> = error
+        .codes
+        .iter()
+        .map(|error_code| {
+            let ident = &error_code.ident;
+            let id = error_code.id;
+            match &error_code.fields {
+                None => quote! { #id => Some(#enum_name::#ident) },
+                Some(fields) => {
+                    let field_idents = fields.named.iter().map(|field| &field.ident);
+                    quote! {
+                        #id => Some(#enum_name::#ident {
+                            #(
+                                #field_idents: anchor_lang::AnchorDeserialize::deserialize(
+                                    &mut error_data
+                                )
+                                .ok()?,
+                            )*
+                        })
+                    }
+                }
             }
         })
         .collect();
@@ -55,8 +130,34 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
         }
     };
 
+    // Variants with fields can't be `Copy` in general.
+    let derive = if has_fields {
+        quote! { #[derive(std::fmt::Debug, Clone)] }
+    } else {
+        quote! { #[derive(std::fmt::Debug, Clone, Copy)] }
+    };
+
+    let data_body = if has_fields {
+        quote! {
+            match self {
+                #(#data_variant_dispatch),*
+            }
+        }
+    } else {
+        quote! { None }
+    };
+
+    let (error_data_param, error_data_cursor) = if has_fields {
+        (
+            quote! { error_data },
+            quote! { let mut error_data = error_data; },
+        )
+    } else {
+        (quote! { _error_data }, quote! {})
+    };
+
     let ret = quote! {
-        #[derive(std::fmt::Debug, Clone, Copy)]
+        #derive
         #[repr(u32)]
         #error_enum
 
@@ -67,11 +168,20 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
                     #(#name_variant_dispatch),*
                 }
             }
+
+            /// Gets the Borsh serialized fields of this [#enum_name], or `None` if the variant
+            /// doesn't have fields.
+            pub fn data(&self) -> Option<Vec<u8>> {
+                #data_body
+            }
         }
 
         impl From<#enum_name> for u32 {
             fn from(e: #enum_name) -> u32 {
-                e as u32 + #offset
+                let id: u32 = match e {
+                    #(#id_variant_dispatch),*
+                };
+                id + #offset
             }
         }
 
@@ -80,16 +190,28 @@ pub fn generate(error: Error) -> proc_macro2::TokenStream {
                 anchor_lang::error::Error::from(
                     anchor_lang::error::AnchorError {
                         error_name: error_code.name(),
-                        error_code_number: error_code.into(),
                         error_msg: error_code.to_string(),
+                        error_data: error_code.data(),
                         error_origin: None,
-                        compared_values: None
+                        compared_values: None,
+                        error_code_number: error_code.into(),
                     }
                 )
             }
         }
 
+        impl anchor_lang::error::DecodeError for #enum_name {
+            fn decode_error(error_code_number: u32, #error_data_param: &[u8]) -> Option<Self> {
+                #error_data_cursor
+                match error_code_number.checked_sub(#offset)? {
+                    #(#decode_variant_dispatch,)*
+                    _ => None,
+                }
+            }
+        }
+
         impl std::fmt::Display for #enum_name {
+            #[allow(unused_variables)]
             fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
                 match self {
                     #(#display_variant_dispatch),*
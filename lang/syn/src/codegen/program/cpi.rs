@@ -4,6 +4,12 @@ use heck::SnakeCase;
 use quote::{quote, ToTokens};
 
 pub fn generate(program: &Program) -> proc_macro2::TokenStream {
+    // Map the errors of the program back to its error type, if it's given
+    let error_ty = match &program.args {
+        Some(args) => args.error.to_token_stream(),
+        None => quote! { anchor_lang::error::ErrorCode },
+    };
+
     // Generate cpi methods for global methods.
     let global_cpi_methods: Vec<proc_macro2::TokenStream> = program
         .ixs
@@ -55,7 +61,10 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                             &acc_infos,
                             ctx.signer_seeds,
                         ).map_or_else(
-                            |e| Err(Into::into(e)),
+                            |e| Err(anchor_lang::error::Error::from_cpi::<#error_ty>(
+                                ix.program_id,
+                                e,
+                            )),
                             // Maybe handle Solana return data.
                             |_| { #maybe_return }
                         )
@@ -1,10 +1,226 @@
-use crate::Program;
+use crate::codegen::program::common::{sighash, SIGHASH_GLOBAL_NAMESPACE};
+use crate::{Ix, Program};
 use heck::CamelCase;
-use quote::quote;
+use proc_macro2::TokenTree;
+use quote::{quote, ToTokens};
 
 pub fn generate(program: &Program) -> proc_macro2::TokenStream {
-    // Dispatch all global instructions.
-    let global_ixs = program.ixs.iter().map(|ix| {
+    let dispatch = generate_dispatch(program, false);
+    let lazy_dispatch = generate_dispatch(program, true);
+
+    // Instructions are dispatched after the IDL and event CPI instructions, and custom
+    // discriminators are checked in order, so none of them can be a prefix of another.
+    let check_discriminators = program.ixs.iter().map(|ix| {
+        let ix_name = ix.raw_method.sig.ident.to_string();
+        let ix_name_camel: proc_macro2::TokenStream = ix_name
+            .to_camel_case()
+            .parse()
+            .expect("Failed to parse ix method name in camel as `TokenStream`");
+        let name = format!("instruction `{ix_name}`");
+        let ix_cfgs = &ix.cfgs;
+
+        quote! {
+            #(#ix_cfgs)*
+            let discriminators = discriminators.with(
+                #name,
+                instruction::#ix_name_camel::DISCRIMINATOR,
+            );
+        }
+    });
+
+    // Accounts are checked like in the IDL, against the other accounts of the instructions only.
+    let accounts_discriminators = program.ixs.iter().map(|ix| {
+        let anchor_ident = &ix.anchor_ident;
+        let anchor_generics = &ix.anchor_generics;
+        let anchor = quote! { #anchor_ident #anchor_generics };
+        let ix_cfgs = &ix.cfgs;
+        quote! {
+            #(#ix_cfgs)*
+            &<#anchor as anchor_lang::Accounts<
+                '_,
+                <#anchor as anchor_lang::Bumps>::Bumps,
+            >>::__ANCHOR_PRIVATE_DISCRIMINATORS
+        }
+    });
+
+    quote! {
+        /// Performs method dispatch.
+        ///
+        /// Instructions with the default 8 byte discriminators, as well as IDL and event CPI
+        /// instructions, are dispatched by matching the first 8 bytes of the instruction data.
+        /// Instructions with custom discriminators are then checked in order until the given
+        /// instruction data starts with the discriminator.
+        ///
+        /// If a match is found, the instruction handler is called using the given instruction data
+        /// excluding the prepended discriminator bytes.
+        ///
+        /// If no match is found, the fallback function is executed if it exists, or an error is
+        /// returned if it doesn't exist.
+        fn dispatch<'info>(
+            program_id: &Pubkey,
+            accounts: &'info [AccountInfo<'info>],
+            data: &[u8],
+        ) -> anchor_lang::Result<()> {
+            #dispatch
+        }
+
+        /// Performs method dispatch for the lazy entrypoint, same as [`dispatch`], then only
+        /// deserializes the accounts consumed by the `Accounts` struct of the instruction.
+        #[cfg(feature = "lazy-entrypoint")]
+        fn lazy_dispatch(
+            program_id: &Pubkey,
+            input: anchor_lang::lazy_entrypoint::EntrypointInput,
+            data: &[u8],
+        ) -> anchor_lang::Result<()> {
+            #lazy_dispatch
+        }
+
+        // Fail the compilation if the discriminators of the program are ambiguous
+        const _: () = {
+            let discriminators = anchor_lang::__private::Discriminators::new(
+                "the IDL instruction tag",
+                anchor_lang::idl::IDL_IX_TAG_LE,
+            );
+            let discriminators = discriminators.with(
+                "the event CPI instruction tag",
+                anchor_lang::event::EVENT_IX_TAG_LE,
+            );
+            #(#check_discriminators)*
+
+            const ACCOUNTS: anchor_lang::__private::DiscriminatorTree =
+                anchor_lang::__private::DiscriminatorTree {
+                    items: &[],
+                    nested: &[#(#accounts_discriminators),*],
+                };
+            anchor_lang::__private::check_discriminator_tree::<{ ACCOUNTS.count() }>(
+                "account",
+                &ACCOUNTS,
+            );
+        };
+    }
+}
+
+/// Generates the check of the discriminators of the events emitted with `emit!` and `emit_cpi!`
+/// in the program module, which is added to the module so that the events resolve the same.
+pub fn generate_events_check(program: &Program) -> proc_macro2::TokenStream {
+    let mut events = Vec::new();
+    find_emitted_events(program.program_mod.to_token_stream(), &mut events);
+    if events.is_empty() {
+        return quote! {};
+    }
+
+    let items = events.iter().map(|path| {
+        let name = path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+        quote! { (#name, Some(<#path as anchor_lang::Discriminator>::DISCRIMINATOR)) }
+    });
+    quote! {
+        // Fail the compilation if the discriminators of the emitted events are ambiguous
+        const _: () = {
+            const EVENTS: anchor_lang::__private::DiscriminatorTree =
+                anchor_lang::__private::DiscriminatorTree {
+                    items: &[#(#items),*],
+                    nested: &[],
+                };
+            anchor_lang::__private::check_discriminator_tree::<{ EVENTS.count() }>(
+                "event",
+                &EVENTS,
+            );
+        };
+    }
+}
+
+/// Adds the paths of the events in the `emit!` and `emit_cpi!` invocations of `tokens` that are
+/// given as struct expressions, e.g. `emit!(MyEvent { .. })`, to `events`.
+fn find_emitted_events(tokens: proc_macro2::TokenStream, events: &mut Vec<syn::Path>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    for (i, tt) in tokens.iter().enumerate() {
+        let TokenTree::Group(group) = tt else {
+            continue;
+        };
+        let is_emit = i >= 2
+            && matches!(&tokens[i - 2], TokenTree::Ident(ident) if ident == "emit" || ident == "emit_cpi")
+            && matches!(&tokens[i - 1], TokenTree::Punct(punct) if punct.as_char() == '!');
+        if !is_emit {
+            find_emitted_events(group.stream(), events);
+            continue;
+        }
+
+        if let Ok(expr) = syn::parse2::<syn::ExprStruct>(group.stream()) {
+            if !events.contains(&expr.path) {
+                events.push(expr.path);
+            }
+        }
+    }
+}
+
+/// Generates the body of the dispatch function.
+///
+/// The lazy dispatch gets the serialized `input` instead of the `accounts`, and deserializes the
+/// accounts once the handler is found.
+fn generate_dispatch(program: &Program, lazy: bool) -> proc_macro2::TokenStream {
+    // Calls the handler with the deserialized `accounts`, which are deserialized up to `len` for
+    // the lazy dispatch.
+    let with_accounts = |len: proc_macro2::TokenStream, call: proc_macro2::TokenStream| {
+        if lazy {
+            quote! { input.with_accounts(#len, |accounts| #call) }
+        } else {
+            call
+        }
+    };
+    // Instructions checking the caller look up the instructions sysvar in all the accounts, and
+    // handlers which may read the remaining accounts get all of them.
+    let ix_accounts_len = |ix: &Ix| match &ix.overrides {
+        Some(overrides) if overrides.allowed_callers.is_some() => quote! { None },
+        _ if may_use_remaining_accounts(ix) => quote! { None },
+        _ => {
+            let anchor_ident = &ix.anchor_ident;
+            let anchor_generics = &ix.anchor_generics;
+            let anchor = quote! { #anchor_ident #anchor_generics };
+            quote! {
+                <#anchor as anchor_lang::Accounts<
+                    '_,
+                    <#anchor as anchor_lang::Bumps>::Bumps,
+                >>::ACCOUNTS_LEN
+            }
+        }
+    };
+
+    // Instructions with an 8 byte discriminator known at compile time are dispatched with a single
+    // `match` on the discriminator as `u64`, others are checked in order with `starts_with`.
+    let (known_ixs, custom_ixs): (Vec<_>, Vec<_>) = program
+        .ixs
+        .iter()
+        .map(|ix| (ix, known_discriminator(ix)))
+        .partition(|(_, discriminator)| discriminator.is_some());
+
+    let known_ixs = known_ixs.into_iter().map(|(ix, discriminator)| {
+        let ix_method_name = &ix.raw_method.sig.ident;
+        let discriminator = proc_macro2::Literal::u64_suffixed(u64::from_le_bytes(
+            discriminator.expect("Known discriminator"),
+        ));
+        let ix_cfgs = &ix.cfgs;
+        let call = with_accounts(
+            ix_accounts_len(ix),
+            quote! {
+                __private::__global::#ix_method_name(
+                    program_id,
+                    accounts,
+                    &data[8..],
+                )
+            },
+        );
+
+        quote! {
+            #(#ix_cfgs)*
+            #discriminator => return #call,
+        }
+    });
+
+    let custom_ixs = custom_ixs.into_iter().map(|(ix, _)| {
         let ix_method_name = &ix.raw_method.sig.ident;
         let ix_name_camel: proc_macro2::TokenStream = ix_method_name
             .to_string()
@@ -13,29 +229,54 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
             .expect("Failed to parse ix method name in camel as `TokenStream`");
         let discriminator = quote! { instruction::#ix_name_camel::DISCRIMINATOR };
         let ix_cfgs = &ix.cfgs;
-
-        quote! {
-            #(#ix_cfgs)*
-            if data.starts_with(#discriminator) {
-                return __private::__global::#ix_method_name(
+        let call = with_accounts(
+            ix_accounts_len(ix),
+            quote! {
+                __private::__global::#ix_method_name(
                     program_id,
                     accounts,
                     &data[#discriminator.len()..],
                 )
+            },
+        );
+
+        quote! {
+            #(#ix_cfgs)*
+            if data.starts_with(#discriminator) {
+                return #call;
             }
         }
     });
 
-    // Generate the event-cpi instruction handler based on whether the `event-cpi` feature is enabled.
-    let event_cpi_handler = {
-        #[cfg(feature = "event-cpi")]
+    let idl_handler = with_accounts(
+        quote! { None },
         quote! {
-            // `event-cpi` feature is enabled, dispatch self-cpi instruction
-            __private::__events::__event_dispatch(
+            __private::__idl::__idl_dispatch(
                 program_id,
                 accounts,
-                &data[anchor_lang::event::EVENT_IX_TAG_LE.len()..]
+                &data[anchor_lang::idl::IDL_IX_TAG_LE.len()..],
             )
+        },
+    );
+
+    // Generate the event-cpi instruction handler based on whether the `event-cpi` feature is enabled.
+    let event_cpi_handler = {
+        #[cfg(feature = "event-cpi")]
+        {
+            let call = with_accounts(
+                quote! { None },
+                quote! {
+                    __private::__events::__event_dispatch(
+                        program_id,
+                        accounts,
+                        &data[anchor_lang::event::EVENT_IX_TAG_LE.len()..]
+                    )
+                },
+            );
+            quote! {
+                // `event-cpi` feature is enabled, dispatch self-cpi instruction
+                #call
+            }
         }
         #[cfg(not(feature = "event-cpi"))]
         quote! {
@@ -50,9 +291,10 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         .map(|fallback_fn| {
             let program_name = &program.name;
             let fn_name = &fallback_fn.raw_method.sig.ident;
-            quote! {
-                #program_name::#fn_name(program_id, accounts, data)
-            }
+            with_accounts(
+                quote! { None },
+                quote! { #program_name::#fn_name(program_id, accounts, data) },
+            )
         })
         .unwrap_or_else(|| {
             quote! {
@@ -61,44 +303,70 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
         });
 
     quote! {
-        /// Performs method dispatch.
-        ///
-        /// Each instruction's discriminator is checked until the given instruction data starts with
-        /// the current discriminator.
-        ///
-        /// If a match is found, the instruction handler is called using the given instruction data
-        /// excluding the prepended discriminator bytes.
-        ///
-        /// If no match is found, the fallback function is executed if it exists, or an error is
-        /// returned if it doesn't exist.
-        fn dispatch<'info>(
-            program_id: &Pubkey,
-            accounts: &'info [AccountInfo<'info>],
-            data: &[u8],
-        ) -> anchor_lang::Result<()> {
-            #(#global_ixs)*
-
-            // Dispatch IDL instructions
-            if data.starts_with(anchor_lang::idl::IDL_IX_TAG_LE) {
-                // If the method identifier is the IDL tag, then execute an IDL
-                // instruction, injected into all Anchor programs unless they have
-                // `no-idl` feature enabled
-                #[cfg(not(feature = "no-idl"))]
-                return __private::__idl::__idl_dispatch(
-                    program_id,
-                    accounts,
-                    &data[anchor_lang::idl::IDL_IX_TAG_LE.len()..],
-                );
-                #[cfg(feature = "no-idl")]
-                return Err(anchor_lang::error::ErrorCode::IdlInstructionStub.into());
+        if let Some(tag) = data.get(..8) {
+            let tag = u64::from_le_bytes(tag.try_into().unwrap());
+            match tag {
+                #(#known_ixs)*
+                // Dispatch IDL instructions
+                anchor_lang::idl::IDL_IX_TAG => {
+                    // If the method identifier is the IDL tag, then execute an IDL
+                    // instruction, injected into all Anchor programs unless they have
+                    // `no-idl` feature enabled
+                    #[cfg(not(feature = "no-idl"))]
+                    return #idl_handler;
+                    #[cfg(feature = "no-idl")]
+                    return Err(anchor_lang::error::ErrorCode::IdlInstructionStub.into());
+                }
+                // Dispatch Event CPI instruction
+                anchor_lang::event::EVENT_IX_TAG => return #event_cpi_handler,
+                _ => {}
             }
+        }
 
-            // Dispatch Event CPI instruction
-            if data.starts_with(anchor_lang::event::EVENT_IX_TAG_LE) {
-                return #event_cpi_handler;
-            }
+        #(#custom_ixs)*
 
-            #fallback_fn
-        }
+        #fallback_fn
+    }
+}
+
+/// Returns the discriminator of the instruction if it is 8 bytes and known at compile time, i.e.
+/// not set with `#[instruction(discriminator = ...)]`.
+fn known_discriminator(ix: &Ix) -> Option<[u8; 8]> {
+    match &ix.overrides {
+        Some(overrides) if overrides.discriminator.is_some() => None,
+        _ => Some(ix.interface_discriminator.unwrap_or_else(|| {
+            sighash(
+                SIGHASH_GLOBAL_NAMESPACE,
+                &ix.raw_method.sig.ident.to_string(),
+            )
+        })),
+    }
+}
+
+/// Whether the handler may read `ctx.remaining_accounts`, i.e. uses its context other than through
+/// the `accounts`, `bumps` and `program_id` fields, e.g. by passing `ctx` to another function.
+fn may_use_remaining_accounts(ix: &Ix) -> bool {
+    let ctx = match ix.raw_method.sig.inputs.first() {
+        Some(syn::FnArg::Typed(pat_type)) => match &*pat_type.pat {
+            syn::Pat::Ident(pat_ident) => &pat_ident.ident,
+            syn::Pat::Wild(_) => return false,
+            _ => return true,
+        },
+        _ => return true,
+    };
+
+    fn uses_context(tokens: proc_macro2::TokenStream, ctx: &syn::Ident) -> bool {
+        let tokens = tokens.into_iter().collect::<Vec<_>>();
+        tokens.iter().enumerate().any(|(i, tt)| match tt {
+            TokenTree::Group(group) => uses_context(group.stream(), ctx),
+            TokenTree::Ident(ident) if ident == ctx => !matches!(
+                (tokens.get(i + 1), tokens.get(i + 2)),
+                (Some(TokenTree::Punct(dot)), Some(TokenTree::Ident(field)))
+                    if dot.as_char() == '.'
+                        && ["accounts", "bumps", "program_id"].contains(&field.to_string().as_str())
+            ),
+            _ => false,
+        })
     }
+    uses_context(ix.raw_method.block.to_token_stream(), ctx)
 }
@@ -5,8 +5,26 @@ use quote::quote;
 pub fn generate(program: &Program) -> proc_macro2::TokenStream {
     let name: proc_macro2::TokenStream = program.name.to_string().to_camel_case().parse().unwrap();
     quote! {
-        #[cfg(not(feature = "no-entrypoint"))]
+        #[cfg(all(not(feature = "no-entrypoint"), not(feature = "lazy-entrypoint")))]
         anchor_lang::solana_program::entrypoint!(entry);
+
+        /// Lazy entrypoint of the program, see [`anchor_lang::lazy_entrypoint`].
+        ///
+        /// # Safety
+        #[cfg(all(not(feature = "no-entrypoint"), feature = "lazy-entrypoint"))]
+        #[no_mangle]
+        pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+            let input = anchor_lang::lazy_entrypoint::EntrypointInput::new(input);
+            match lazy_entry(input) {
+                Ok(()) => anchor_lang::solana_program::entrypoint::SUCCESS,
+                Err(error) => error.into(),
+            }
+        }
+        #[cfg(all(not(feature = "no-entrypoint"), feature = "lazy-entrypoint"))]
+        anchor_lang::solana_program::entrypoint::custom_heap_default!();
+        #[cfg(all(not(feature = "no-entrypoint"), feature = "lazy-entrypoint"))]
+        anchor_lang::solana_program::entrypoint::custom_panic_default!();
+
         /// The Anchor codegen exposes a programming model where a user defines
         /// a set of methods inside of a `#[program]` module in a way similar
         /// to writing RPC request handlers. The macro then generates a bunch of
@@ -43,6 +61,31 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
             })
         }
 
+        /// Entry of the lazy entrypoint, which checks the program id and dispatches the instruction
+        /// before deserializing the accounts.
+        #[cfg(feature = "lazy-entrypoint")]
+        pub fn lazy_entry(input: anchor_lang::lazy_entrypoint::EntrypointInput) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+            try_lazy_entry(input).map_err(|e| {
+                e.log();
+                e.into()
+            })
+        }
+
+        #[cfg(feature = "lazy-entrypoint")]
+        fn try_lazy_entry(input: anchor_lang::lazy_entrypoint::EntrypointInput) -> anchor_lang::Result<()> {
+            #[cfg(feature = "anchor-debug")]
+            {
+                msg!("anchor-debug is active");
+            }
+            let program_id = input.program_id();
+            if *program_id != ID {
+                return Err(anchor_lang::error::ErrorCode::DeclaredProgramIdMismatch.into());
+            }
+
+            let data = input.instruction_data();
+            lazy_dispatch(program_id, input, data)
+        }
+
         fn try_entry<'info>(program_id: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> anchor_lang::Result<()> {
             #[cfg(feature = "anchor-debug")]
             {
@@ -102,7 +102,9 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
             let ix_name = generate_ix_variant_name(&ix_method_name_str);
             let variant_arm = generate_ix_variant(&ix_method_name_str, &ix.args);
             let ix_name_log = format!("Instruction: {ix_name}");
-            let anchor = &ix.anchor_ident;
+            let anchor_ident = &ix.anchor_ident;
+            let anchor_generics = &ix.anchor_generics;
+            let anchor = quote! { #anchor_ident #anchor_generics };
             let ret_type = &ix.returns.ty.to_token_stream();
             let cfgs = &ix.cfgs;
             let maybe_set_return_data = match ret_type.to_string().as_str() {
@@ -113,6 +115,21 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                     anchor_lang::solana_program::program::set_return_data(&return_data);
                 },
             };
+            let caller_check = match ix.overrides.as_ref() {
+                Some(overrides) if overrides.no_cpi.is_some() => quote! {
+                    anchor_lang::introspection::require_top_level()?;
+                },
+                Some(overrides) => match &overrides.allowed_callers {
+                    Some(callers) => quote! {
+                        anchor_lang::introspection::require_cpi_caller(
+                            __accounts,
+                            &[#(#callers),*],
+                        )?;
+                    },
+                    None => quote! {},
+                },
+                None => quote! {},
+            };
             quote! {
                 #(#cfgs)*
                 #[inline(never)]
@@ -124,6 +141,9 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                     #[cfg(not(feature = "no-log-ix-name"))]
                     anchor_lang::prelude::msg!(#ix_name_log);
 
+                    // Check how the instruction is called.
+                    #caller_check
+
                     // Deserialize data.
                     let ix = instruction::#ix_name::deserialize(&mut &__ix_data[..])
                         .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
@@ -136,7 +156,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
 
                     // Deserialize accounts.
                     let mut __remaining_accounts: &[AccountInfo] = __accounts;
-                    let mut __accounts = #anchor::try_accounts(
+                    let mut __accounts = <#anchor>::try_accounts(
                         __program_id,
                         &mut __remaining_accounts,
                         __ix_data,
@@ -144,6 +164,15 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
                         &mut __reallocs,
                     )?;
 
+                    // Reject the same account being given for multiple mutable fields.
+                    anchor_lang::__private::check_duplicate_mut_accounts(|visit| {
+                        anchor_lang::Accounts::__anchor_private_visit_mut_accounts(
+                            &__accounts,
+                            &mut Vec::new(),
+                            visit,
+                        )
+                    })?;
+
                     // Invoke user defined handler.
                     let result = #program_name::#ix_method_name(
                         anchor_lang::context::Context::new(
@@ -9,6 +9,7 @@ mod entry;
 mod handlers;
 mod idl;
 mod instruction;
+mod transfer_hook;
 
 pub fn generate(program: &Program) -> proc_macro2::TokenStream {
     let mod_name = &program.name;
@@ -16,10 +17,16 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
     let entry = entry::generate(program);
     let dispatch = dispatch::generate(program);
     let handlers = handlers::generate(program);
-    let user_defined_program = &program.program_mod;
+    let mut user_defined_program = program.program_mod.clone();
+    if let Some((_, items)) = &mut user_defined_program.content {
+        items.push(syn::Item::Verbatim(dispatch::generate_events_check(
+            program,
+        )));
+    }
     let instruction = instruction::generate(program);
     let cpi = cpi::generate(program);
     let accounts = accounts::generate(program);
+    let transfer_hook = transfer_hook::generate(program);
 
     #[allow(clippy::let_and_return)]
     let ret = {
@@ -34,6 +41,7 @@ pub fn generate(program: &Program) -> proc_macro2::TokenStream {
             #instruction
             #cpi
             #accounts
+            #transfer_hook
         }
     };
 
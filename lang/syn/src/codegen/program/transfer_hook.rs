@@ -0,0 +1,46 @@
+use crate::Program;
+use quote::quote;
+
+/// Generate the accounts structs of the extra account meta list instructions that are added to
+/// the program when an instruction is marked with `#[instruction(transfer_hook)]`.
+pub fn generate(program: &Program) -> proc_macro2::TokenStream {
+    let execute_ix = program.ixs.iter().find(|ix| {
+        ix.overrides
+            .as_ref()
+            .is_some_and(|overrides| overrides.transfer_hook.is_some())
+    });
+    let cfgs = match execute_ix {
+        Some(ix) => &ix.cfgs,
+        None => return quote! {},
+    };
+
+    quote! {
+        #(#cfgs)*
+        /// Accounts of the transfer hook `InitializeExtraAccountMetaList` instruction.
+        #[derive(anchor_lang::Accounts)]
+        pub struct InitializeExtraAccountMetaList<'info> {
+            /// Account that stores the extra accounts of the `Execute` instruction, which must be
+            /// funded to be rent exempt before the instruction.
+            /// CHECK: Initialized in the instruction handler.
+            #[account(mut, seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+            pub extra_account_meta_list: UncheckedAccount<'info>,
+            #[account(mint::authority = authority)]
+            pub mint: InterfaceAccount<'info, ::anchor_spl::token_interface::Mint>,
+            pub authority: Signer<'info>,
+            pub system_program: Program<'info, System>,
+        }
+
+        #(#cfgs)*
+        /// Accounts of the transfer hook `UpdateExtraAccountMetaList` instruction.
+        #[derive(anchor_lang::Accounts)]
+        pub struct UpdateExtraAccountMetaList<'info> {
+            /// Account that stores the extra accounts of the `Execute` instruction.
+            /// CHECK: Owned by this program and derived from the mint.
+            #[account(mut, seeds = [b"extra-account-metas", mint.key().as_ref()], bump, owner = crate::ID)]
+            pub extra_account_meta_list: UncheckedAccount<'info>,
+            #[account(mint::authority = authority)]
+            pub mint: InterfaceAccount<'info, ::anchor_spl::token_interface::Mint>,
+            pub authority: Signer<'info>,
+        }
+    }
+}
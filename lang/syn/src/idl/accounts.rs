@@ -3,6 +3,9 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use super::common::{get_idl_module_path, get_no_docs};
+use crate::codegen::accounts::transfer_hook::{
+    self, ExtraAccountAddress, ExtraAccountMeta, ExtraAccountSeed,
+};
 use crate::{AccountField, AccountsStruct, ConstraintSeedsGroup, Field, InitKind, Ty};
 
 /// Generate the IDL build impl for the Accounts struct.
@@ -13,8 +16,22 @@ pub fn gen_idl_build_impl_accounts_struct(accounts: &AccountsStruct) -> TokenStr
     let no_docs = get_no_docs();
     let idl = get_idl_module_path();
 
+    let create_transfer_hook = match get_transfer_hook(accounts) {
+        Ok(create_transfer_hook) => create_transfer_hook,
+        Err(e) => return e.to_compile_error(),
+    };
+
     let ident = &accounts.ident;
-    let (impl_generics, ty_generics, where_clause) = accounts.generics.split_for_impl();
+    let generics = &accounts.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let composites = accounts
+        .fields
+        .iter()
+        .filter_map(|acc| match acc {
+            AccountField::CompositeField(comp_f) => Some(&comp_f.raw_field.ty),
+            AccountField::Field(_) => None,
+        })
+        .collect::<Vec<_>>();
 
     let (accounts, defined) = accounts
         .fields
@@ -34,14 +51,19 @@ pub fn gen_idl_build_impl_accounts_struct(accounts: &AccountsStruct) -> TokenStr
                 };
 
                 let (address, pda, relations) = if resolution {
-                    (
-                        get_address(acc),
-                        get_pda(acc, accounts),
-                        get_relations(acc, accounts),
-                    )
+                    // Seeds of remaining accounts can depend on the index of the element, which
+                    // can't be expressed in the IDL.
+                    let pda = if acc.constraints.is_remaining() {
+                        quote! { None }
+                    } else {
+                        get_pda(acc, accounts)
+                    };
+                    (get_address(acc), pda, get_relations(acc, accounts))
                 } else {
                     (quote! { None }, quote! { None }, quote! { vec![] })
                 };
+                let remaining = get_remaining(acc, accounts);
+                let signers = get_signers(acc, accounts);
 
                 let acc_type_path = match &acc.ty {
                     Ty::Account(ty)
@@ -76,39 +98,22 @@ pub fn gen_idl_build_impl_accounts_struct(accounts: &AccountsStruct) -> TokenStr
                             address: #address,
                             pda: #pda,
                             relations: #relations,
+                            remaining: #remaining,
+                            signers: #signers,
                         })
                     },
                     acc_type_path,
                 )
             }
             AccountField::CompositeField(comp_f) => {
-                let ty = if let syn::Type::Path(path) = &comp_f.raw_field.ty {
-                    // some::path::Foo<'info> -> some::path::Foo
-                    let mut res = syn::Path {
-                        leading_colon: path.path.leading_colon,
-                        segments: syn::punctuated::Punctuated::new(),
-                    };
-                    for segment in &path.path.segments {
-                        let s = syn::PathSegment {
-                            ident: segment.ident.clone(),
-                            arguments: syn::PathArguments::None,
-                        };
-                        res.segments.push(s);
-                    }
-                    res
-                } else {
-                    panic!(
-                        "Compose field type must be a path but received: {:?}",
-                        comp_f.raw_field.ty
-                    )
-                };
+                let ty = &comp_f.raw_field.ty;
                 let name = comp_f.ident.to_string();
 
                 (
                     quote! {
                         #idl::IdlInstructionAccountItem::Composite(#idl::IdlInstructionAccounts {
                             name: #name.into(),
-                            accounts: <#ty>::__anchor_private_gen_idl_accounts(accounts, types),
+                            accounts: <#ty as anchor_lang::idl::IdlBuildAccounts>::create_accounts(accounts, types),
                         })
                     },
                     None,
@@ -118,9 +123,35 @@ pub fn gen_idl_build_impl_accounts_struct(accounts: &AccountsStruct) -> TokenStr
         .unzip::<_, _, Vec<_>, Vec<_>>();
     let defined = defined.into_iter().flatten().collect::<Vec<_>>();
 
+    // Account types and composite fields that depend on the generic types of the struct are only
+    // known to be included in the IDL once the struct is instantiated
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect::<Vec<_>>();
+    let is_generic = |ty: &dyn ToTokens| {
+        ty.to_token_stream()
+            .to_string()
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| type_params.iter().any(|param| param == word))
+    };
+    let mut where_clause = where_clause
+        .cloned()
+        .unwrap_or_else(|| syn::parse_quote!(where));
+    for ty in defined.iter().filter(|ty| is_generic(ty)) {
+        where_clause.predicates.push(syn::parse_quote! {
+            #ty: anchor_lang::IdlBuild + anchor_lang::Discriminator
+        });
+    }
+    for ty in composites.into_iter().filter(|ty| is_generic(ty)) {
+        where_clause.predicates.push(syn::parse_quote! {
+            #ty: anchor_lang::idl::IdlBuildAccounts
+        });
+    }
+
     quote! {
-        impl #impl_generics #ident #ty_generics #where_clause {
-            pub fn __anchor_private_gen_idl_accounts(
+        impl #impl_generics anchor_lang::idl::IdlBuildAccounts for #ident #ty_generics #where_clause {
+            fn create_accounts(
                 accounts: &mut std::collections::BTreeMap<String, #idl::IdlAccount>,
                 types: &mut std::collections::BTreeMap<String, #idl::IdlTypeDef>,
             ) -> Vec<#idl::IdlInstructionAccountItem> {
@@ -128,7 +159,7 @@ pub fn gen_idl_build_impl_accounts_struct(accounts: &AccountsStruct) -> TokenStr
                     if let Some(ty) = <#defined>::create_type() {
                         let account = #idl::IdlAccount {
                             name: ty.name.clone(),
-                            discriminator: #defined::DISCRIMINATOR.into(),
+                            discriminator: <#defined>::DISCRIMINATOR.into(),
                         };
                         accounts.insert(account.name.clone(), account);
                         types.insert(ty.name.clone(), ty);
@@ -138,10 +169,90 @@ pub fn gen_idl_build_impl_accounts_struct(accounts: &AccountsStruct) -> TokenStr
 
                 vec![#(#accounts),*]
             }
+
+            #create_transfer_hook
         }
     }
 }
 
+/// Generate the `create_transfer_hook` method for `#[transfer_hook]` accounts structs.
+fn get_transfer_hook(accounts: &AccountsStruct) -> syn::Result<TokenStream> {
+    if !accounts.transfer_hook {
+        return Ok(quote! {});
+    }
+
+    let idl = get_idl_module_path();
+    let extra_accounts = transfer_hook::extra_account_metas(accounts)?
+        .iter()
+        .map(|meta| {
+            let ExtraAccountMeta {
+                field,
+                is_signer,
+                is_writable,
+                address,
+            } = meta;
+            let name = field.ident.to_string();
+            let (address, pda) = match address {
+                ExtraAccountAddress::Pubkey(address) => {
+                    (quote! { Some(#address.to_string()) }, quote! { None })
+                }
+                ExtraAccountAddress::Seeds {
+                    seeds,
+                    program_index,
+                } => {
+                    let seeds = seeds.iter().map(|seed| match seed {
+                        ExtraAccountSeed::Literal(seed) => quote! {
+                            #idl::IdlExtraAccountSeed::Const {
+                                value: {
+                                    let seed: &[u8] = #seed;
+                                    seed.to_vec()
+                                },
+                            }
+                        },
+                        ExtraAccountSeed::InstructionData { index, length } => quote! {
+                            #idl::IdlExtraAccountSeed::Data { index: #index, length: #length }
+                        },
+                        ExtraAccountSeed::AccountKey { index } => quote! {
+                            #idl::IdlExtraAccountSeed::Account { index: #index }
+                        },
+                    });
+                    let program = match program_index {
+                        Some(index) => quote! { Some(#index) },
+                        None => quote! { None },
+                    };
+                    (
+                        quote! { None },
+                        quote! {
+                            Some(#idl::IdlExtraAccountPda {
+                                seeds: vec![#(#seeds),*],
+                                program: #program,
+                            })
+                        },
+                    )
+                }
+            };
+
+            quote! {
+                #idl::IdlExtraAccount {
+                    name: #name.into(),
+                    writable: #is_writable,
+                    signer: #is_signer,
+                    address: #address,
+                    pda: #pda,
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        fn create_transfer_hook() -> Option<#idl::IdlTransferHook> {
+            Some(#idl::IdlTransferHook {
+                accounts: vec![#(#extra_accounts),*],
+            })
+        }
+    })
+}
+
 fn get_address(acc: &Field) -> TokenStream {
     match &acc.ty {
         Ty::Program(_) | Ty::Sysvar(_) => {
@@ -180,6 +291,108 @@ fn get_address(acc: &Field) -> TokenStream {
     }
 }
 
+fn get_remaining(acc: &Field, accounts: &AccountsStruct) -> TokenStream {
+    let idl = get_idl_module_path();
+    let remaining = match &acc.constraints.remaining {
+        Some(remaining) => remaining,
+        None => return quote! { None },
+    };
+
+    let args = accounts.instruction_args().unwrap_or_default();
+    let len = match &remaining.len {
+        Some(syn::Expr::Lit(lit)) => Some(quote! {
+            #idl::IdlRemainingAccountsLen::Const { value: (#lit) as u64 }
+        }),
+        Some(syn::Expr::Path(path)) => match path.path.get_ident() {
+            Some(ident) if args.contains_key(&ident.to_string()) => Some(quote! {
+                #idl::IdlRemainingAccountsLen::Arg { path: stringify!(#ident).into() }
+            }),
+            // Allow constants (assume the identifier follows the Rust naming convention)
+            _ if path
+                .path
+                .segments
+                .last()
+                .unwrap()
+                .ident
+                .to_string()
+                .chars()
+                .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_') =>
+            {
+                Some(quote! {
+                    #idl::IdlRemainingAccountsLen::Const { value: (#path) as u64 }
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    let len = len
+        .map(|len| quote! { Some(#len) })
+        .unwrap_or_else(|| quote! { None });
+
+    quote! { Some(#idl::IdlRemainingAccounts { len: #len }) }
+}
+
+fn get_signers(acc: &Field, accounts: &AccountsStruct) -> TokenStream {
+    let idl = get_idl_module_path();
+    let signers = match &acc.constraints.signers {
+        Some(signers) => signers,
+        None => return quote! { None },
+    };
+
+    let to_path = |expr: &syn::Expr| {
+        expr.to_token_stream()
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+    };
+    let path = to_path(&signers.signers);
+
+    let args = accounts.instruction_args().unwrap_or_default();
+    let threshold = match &signers.threshold {
+        syn::Expr::Lit(lit) => Some(quote! {
+            #idl::IdlSignersThreshold::Const { value: (#lit) as u64 }
+        }),
+        syn::Expr::Path(path) => match path.path.get_ident() {
+            Some(ident) if args.contains_key(&ident.to_string()) => Some(quote! {
+                #idl::IdlSignersThreshold::Arg { path: stringify!(#ident).into() }
+            }),
+            // Allow constants (assume the identifier follows the Rust naming convention)
+            _ if path
+                .path
+                .segments
+                .last()
+                .unwrap()
+                .ident
+                .to_string()
+                .chars()
+                .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_') =>
+            {
+                Some(quote! {
+                    #idl::IdlSignersThreshold::Const { value: (#path) as u64 }
+                })
+            }
+            _ => None,
+        },
+        expr @ syn::Expr::Field(_) => {
+            let path = to_path(expr);
+            Some(quote! { #idl::IdlSignersThreshold::Account { path: #path.into() } })
+        }
+        _ => None,
+    };
+    let threshold = threshold
+        .map(|threshold| quote! { Some(#threshold) })
+        .unwrap_or_else(|| quote! { None });
+
+    quote! {
+        Some(#idl::IdlSigners {
+            path: #path.into(),
+            threshold: #threshold,
+        })
+    }
+}
+
 fn get_pda(acc: &Field, accounts: &AccountsStruct) -> TokenStream {
     let idl = get_idl_module_path();
     let parse_default = |expr: &syn::Expr| parse_seed(expr, accounts);
@@ -266,6 +479,40 @@ fn get_pda(acc: &Field, accounts: &AccountsStruct) -> TokenStream {
         return pda;
     }
 
+    // Program data account of an `upgrade_authority` constraint
+    let is_program_data = accounts.fields.iter().any(|field| match field {
+        AccountField::Field(field) => field
+            .constraints
+            .upgrade_authority
+            .as_ref()
+            .is_some_and(|c| acc.ident == c.program_data.to_token_stream().to_string()),
+        _ => false,
+    });
+    if is_program_data {
+        return quote! {
+            Some(
+                #idl::IdlPda {
+                    seeds: vec![
+                        #idl::IdlSeed::Const(
+                            #idl::IdlSeedConst {
+                                value: crate::ID.as_ref().into(),
+                            }
+                        )
+                    ],
+                    program: Some(
+                        #idl::IdlSeed::Const(
+                            #idl::IdlSeedConst {
+                                value: anchor_lang::solana_program::bpf_loader_upgradeable::ID
+                                    .as_ref()
+                                    .into(),
+                            }
+                        )
+                    ),
+                }
+            )
+        };
+    }
+
     quote! { None }
 }
 
@@ -214,6 +214,21 @@ where
                 None
             }
         })
+        .or_else(|| {
+            // Custom serializers are passed with the `#[serializer(<PATH>)]` attribute
+            attrs
+                .iter()
+                .find(|attr| attr.path.is_ident("serializer"))
+                .and_then(|attr| attr.parse_args::<syn::Path>().ok())
+                .map(|serializer| {
+                    quote! {
+                        #idl::IdlSerialization::Custom(
+                            <#serializer as anchor_lang::serializer::AccountSerializer<Self>>::NAME
+                                .into()
+                        )
+                    }
+                })
+        })
         .unwrap_or_else(|| quote! { #idl::IdlSerialization::default() });
 
     let repr = get_attr_str("repr", attrs)
@@ -323,7 +338,7 @@ fn get_attr_str(name: impl AsRef<str>, attrs: &[syn::Attribute]) -> Option<Strin
         })
 }
 
-fn gen_idl_field(
+pub fn gen_idl_field(
     field: &syn::Field,
     generic_params: &[syn::Ident],
     no_docs: bool,
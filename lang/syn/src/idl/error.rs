@@ -2,17 +2,31 @@ use heck::SnakeCase;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-use super::common::{gen_print_section, get_idl_module_path};
+use super::{
+    common::{gen_print_section, get_idl_module_path, get_no_docs, get_serde_json_module_path},
+    defined::gen_idl_field,
+};
 use crate::Error;
 
 pub fn gen_idl_print_fn_error(error: &Error) -> TokenStream {
     let idl = get_idl_module_path();
+    let serde_json = get_serde_json_module_path();
+    let no_docs = get_no_docs();
 
     let fn_name = format_ident!(
         "__anchor_private_print_idl_error_{}",
         error.ident.to_string().to_snake_case()
     );
 
+    let offset = match &error.args {
+        Some(args) => {
+            let offset = &args.offset;
+            quote! { #offset }
+        }
+        None => quote! { anchor_lang::error::ERROR_CODE_OFFSET },
+    };
+
+    let mut defined = vec![];
     let error_codes = error
         .codes
         .iter()
@@ -23,21 +37,64 @@ pub fn gen_idl_print_fn_error(error: &Error) -> TokenStream {
                 Some(msg) => quote! { Some(#msg.into()) },
                 None => quote! { None },
             };
+            let fields = code.fields.as_ref().and_then(|fields| {
+                fields
+                    .named
+                    .iter()
+                    .map(|field| gen_idl_field(field, &[], no_docs))
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+            });
+            let fields = match fields {
+                Some(fields) => {
+                    let fields = fields.into_iter().map(|(field, field_defined)| {
+                        defined.extend(field_defined);
+                        field
+                    });
+                    quote! { Some(#idl::IdlDefinedFields::Named(vec![#(#fields),*])) }
+                }
+                None => quote! { None },
+            };
 
             quote! {
                 #idl::IdlErrorCode {
-                    code: anchor_lang::error::ERROR_CODE_OFFSET + #id,
+                    code: #offset + #id,
                     name: #name.into(),
                     msg: #msg,
+                    fields: #fields,
                 }
             }
         })
         .collect::<Vec<_>>();
-    let fn_body = gen_print_section("errors", quote! { vec![#(#error_codes),*] });
+    // The errors are printed as is unless they have fields with defined types, which keeps the
+    // output compatible with older versions of the IDL build
+    let fn_body = gen_print_section(
+        "errors",
+        quote! {
+            let errors = vec![#(#error_codes),*];
+            if types.is_empty() {
+                #serde_json::json!(errors)
+            } else {
+                #serde_json::json!({
+                    "errors": errors,
+                    "types": types.into_values().collect::<Vec<_>>()
+                })
+            }
+        },
+    );
 
     quote! {
         #[test]
         pub fn #fn_name() {
+            let mut types: std::collections::BTreeMap<String, #idl::IdlTypeDef> =
+                std::collections::BTreeMap::new();
+            #(
+                if let Some(ty) = <#defined>::create_type() {
+                    types.insert(<#defined>::get_full_path(), ty);
+                    <#defined>::insert_types(&mut types);
+                }
+            );*
+
             #fn_body
         }
     }
@@ -33,6 +33,7 @@ pub fn gen_idl_print_fn_program(program: &Program) -> TokenStream {
             let name = ix.ident.to_string();
             let name_pascal = format_ident!("{}", name.to_camel_case());
             let ctx_ident = &ix.anchor_ident;
+            let ctx_generics = &ix.anchor_generics;
             let cfgs = &ix.cfgs;
 
             let docs = match &ix.docs {
@@ -75,6 +76,21 @@ pub fn gen_idl_print_fn_program(program: &Program) -> TokenStream {
                 _ => quote! { None },
             };
 
+            let caller = match ix.overrides.as_ref() {
+                Some(overrides) if overrides.no_cpi.is_some() => {
+                    quote! { Some(#idl::IdlInstructionCaller::Transaction) }
+                }
+                Some(overrides) => match &overrides.allowed_callers {
+                    Some(callers) => quote! {
+                        Some(#idl::IdlInstructionCaller::Cpi {
+                            programs: vec![#(#callers.to_string()),*],
+                        })
+                    },
+                    None => quote! { None },
+                },
+                None => quote! { None },
+            };
+
             Ok((
                 quote! {
                     #(#cfgs)*
@@ -82,12 +98,14 @@ pub fn gen_idl_print_fn_program(program: &Program) -> TokenStream {
                         name: #name.into(),
                         docs: #docs,
                         discriminator: crate::instruction::#name_pascal::DISCRIMINATOR.into(),
-                        accounts: #ctx_ident::__anchor_private_gen_idl_accounts(
+                        accounts: <#ctx_ident #ctx_generics as anchor_lang::idl::IdlBuildAccounts>::create_accounts(
                             &mut accounts,
                             &mut types,
                         ),
                         args: vec![#(#args),*],
                         returns: #returns,
+                        caller: #caller,
+                        transfer_hook: <#ctx_ident #ctx_generics as anchor_lang::idl::IdlBuildAccounts>::create_transfer_hook(),
                     }
                 },
                 defined,
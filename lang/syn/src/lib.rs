@@ -16,8 +16,8 @@ use codegen::program as program_codegen;
 use parser::accounts as accounts_parser;
 use parser::program as program_parser;
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
 use quote::ToTokens;
+use quote::{quote, quote_spanned};
 use std::collections::HashMap;
 use std::ops::Deref;
 use syn::ext::IdentExt;
@@ -28,8 +28,8 @@ use syn::token::Comma;
 use syn::Attribute;
 use syn::Lit;
 use syn::{
-    Expr, Generics, Ident, ItemEnum, ItemFn, ItemMod, ItemStruct, LitInt, PatType, Token, Type,
-    TypePath,
+    Expr, FieldsNamed, Generics, Ident, ItemEnum, ItemFn, ItemMod, ItemStruct, LitInt, PatType,
+    Path, Token, Type, TypePath,
 };
 
 #[derive(Debug)]
@@ -39,6 +39,28 @@ pub struct Program {
     pub docs: Option<Vec<String>>,
     pub program_mod: ItemMod,
     pub fallback_fn: Option<FallbackFn>,
+    pub args: Option<ProgramArgs>,
+}
+
+/// Arguments of the `#[program]` attribute.
+#[derive(Debug)]
+pub struct ProgramArgs {
+    // Error type of the program, which errors of the CPI helpers are decoded into.
+    pub error: Path,
+}
+
+impl Parse for ProgramArgs {
+    fn parse(stream: ParseStream) -> ParseResult<Self> {
+        let error_span = stream.span();
+        let error = stream.call(Ident::parse_any)?;
+        if error.to_string().as_str() != "error" {
+            return Err(ParseError::new(error_span, "expected keyword error"));
+        }
+        stream.parse::<Token![=]>()?;
+        Ok(ProgramArgs {
+            error: stream.parse()?,
+        })
+    }
 }
 
 impl Parse for Program {
@@ -70,6 +92,8 @@ pub struct Ix {
     pub returns: IxReturn,
     // The ident for the struct deriving Accounts.
     pub anchor_ident: Ident,
+    // The generic arguments of the struct deriving Accounts, with elided lifetimes.
+    pub anchor_generics: syn::PathArguments,
     // The discriminator based on the `#[interface]` attribute.
     // TODO: Remove and use `overrides`
     pub interface_discriminator: Option<[u8; 8]>,
@@ -82,7 +106,24 @@ pub struct Ix {
 pub struct Overrides {
     /// Override the default 8-byte discriminator
     pub discriminator: Option<TokenStream>,
-}
+    /// Mark the instruction as the SPL transfer hook `Execute` handler (instructions only)
+    pub transfer_hook: Option<Ident>,
+    /// Reject calls via CPI (instructions only)
+    pub no_cpi: Option<Ident>,
+    /// Only allow calls via CPI from the given programs (instructions only)
+    pub allowed_callers: Option<Vec<Expr>>,
+    /// Serialization format of the account data (accounts only)
+    pub serializer: Option<syn::Path>,
+    /// Data of the account after it's closed with a tombstone (accounts only)
+    pub tombstone: Option<TokenStream>,
+}
+
+/// Error of `transfer_hook` attributes when the `transfer-hook` feature isn't enabled, as the
+/// generated code depends on `anchor-spl`.
+pub(crate) const TRANSFER_HOOK_FEATURE_ERROR: &str =
+    "transfer_hook requires that anchor-lang be imported with the transfer-hook cargo feature \
+    enabled, and that the program depends on anchor-spl with the token_2022_extensions feature \
+    enabled.";
 
 impl Parse for Overrides {
     fn parse(input: ParseStream) -> ParseResult<Self> {
@@ -90,16 +131,69 @@ impl Parse for Overrides {
         let args = input.parse_terminated::<_, Comma>(NamedArg::parse)?;
         for arg in args {
             match arg.name.to_string().as_str() {
+                "transfer_hook" if arg.value.is_none() => {
+                    if cfg!(not(feature = "transfer-hook")) {
+                        return Err(ParseError::new(
+                            arg.name.span(),
+                            TRANSFER_HOOK_FEATURE_ERROR,
+                        ));
+                    }
+                    attr.transfer_hook.replace(arg.name);
+                }
+                "no_cpi" if arg.value.is_none() => {
+                    if attr.allowed_callers.is_some() {
+                        return Err(ParseError::new(
+                            arg.name.span(),
+                            "`no_cpi` cannot be combined with `allowed_callers`",
+                        ));
+                    }
+                    attr.no_cpi.replace(arg.name);
+                }
+                "allowed_callers" => {
+                    if attr.no_cpi.is_some() {
+                        return Err(ParseError::new(
+                            arg.name.span(),
+                            "`allowed_callers` cannot be combined with `no_cpi`",
+                        ));
+                    }
+                    let callers = match &arg.value {
+                        Some(Expr::Array(arr)) => arr.elems.iter().cloned().collect(),
+                        _ => {
+                            return Err(ParseError::new(
+                                arg.name.span(),
+                                "Expected `allowed_callers = [<PROGRAM_ID>, ...]`",
+                            ))
+                        }
+                    };
+                    attr.allowed_callers.replace(callers);
+                }
                 "discriminator" => {
+                    let value = arg.value.as_ref().ok_or_else(|| {
+                        ParseError::new(arg.name.span(), "Expected `discriminator = <VALUE>`")
+                    })?;
+                    attr.discriminator.replace(parse_bytes(value));
+                }
+                "tombstone" => {
                     let value = match &arg.value {
-                        // Allow `discriminator = 42`
-                        Expr::Lit(lit) if matches!(lit.lit, Lit::Int(_)) => quote! { &[#lit] },
-                        // Allow `discriminator = [0, 1, 2, 3]`
-                        Expr::Array(arr) => quote! { &#arr },
-                        expr => expr.to_token_stream(),
+                        Some(value) => parse_bytes(value),
+                        None => {
+                            let span = arg.name.span();
+                            quote_spanned! { span=> anchor_lang::CLOSED_ACCOUNT_DISCRIMINATOR }
+                        }
                     };
-                    attr.discriminator.replace(value)
+                    attr.tombstone.replace(value);
                 }
+                "serializer" => match &arg.value {
+                    Some(Expr::Path(path)) => {
+                        attr.serializer.replace(path.path.clone());
+                    }
+                    _ => {
+                        return Err(ParseError::new(
+                            arg.name.span(),
+                            "Expected `serializer = <PATH>`",
+                        ))
+                    }
+                },
                 _ => return Err(ParseError::new(arg.name.span(), "Invalid argument")),
             };
         }
@@ -108,20 +202,30 @@ impl Parse for Overrides {
     }
 }
 
+/// Parses a byte slice, allowing `42` and `[0, 1, 2, 3]` as shortcuts.
+fn parse_bytes(expr: &Expr) -> TokenStream {
+    match expr {
+        Expr::Lit(lit) if matches!(lit.lit, Lit::Int(_)) => quote! { &[#lit] },
+        Expr::Array(arr) => quote! { &#arr },
+        expr => expr.to_token_stream(),
+    }
+}
+
 struct NamedArg {
     name: Ident,
-    #[allow(dead_code)]
-    eq_token: Token![=],
-    value: Expr,
+    value: Option<Expr>,
 }
 
 impl Parse for NamedArg {
     fn parse(input: ParseStream) -> ParseResult<Self> {
-        Ok(Self {
-            name: input.parse()?,
-            eq_token: input.parse()?,
-            value: input.parse()?,
-        })
+        let name = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { name, value })
     }
 }
 
@@ -152,6 +256,8 @@ pub struct AccountsStruct {
     pub fields: Vec<AccountField>,
     // Instruction data api expression.
     instruction_api: Option<Punctuated<Expr, Comma>>,
+    // Whether the struct is the accounts of a transfer hook `Execute` instruction.
+    transfer_hook: bool,
 }
 
 impl Parse for AccountsStruct {
@@ -178,6 +284,7 @@ impl AccountsStruct {
         strct: ItemStruct,
         fields: Vec<AccountField>,
         instruction_api: Option<Punctuated<Expr, Comma>>,
+        transfer_hook: bool,
     ) -> Self {
         let ident = strct.ident.clone();
         let generics = strct.generics;
@@ -186,6 +293,7 @@ impl AccountsStruct {
             generics,
             fields,
             instruction_api,
+            transfer_hook,
         }
     }
 
@@ -351,6 +459,10 @@ impl Field {
             quote! {
                 Option<#inner_ty>
             }
+        } else if self.constraints.is_remaining() {
+            quote! {
+                Vec<#inner_ty>
+            }
         } else {
             quote! {
                 #inner_ty
@@ -562,6 +674,8 @@ pub struct CompositeField {
     pub constraints: ConstraintGroup,
     pub symbol: String,
     pub raw_field: syn::Field,
+    /// Instruction arguments forwarded with `#[instruction(..)]`
+    pub instruction_args: Option<Punctuated<Expr, Comma>>,
     /// IDL Doc comment
     pub docs: Option<Vec<String>>,
 }
@@ -670,6 +784,8 @@ pub struct ErrorCode {
     pub id: u32,
     pub ident: Ident,
     pub msg: Option<String>,
+    // Named fields of the variant, `None` for unit variants.
+    pub fields: Option<FieldsNamed>,
 }
 
 // All well formed constraints on a single `Accounts` field.
@@ -691,6 +807,11 @@ pub struct ConstraintGroup {
     pub token_account: Option<ConstraintTokenAccountGroup>,
     pub mint: Option<ConstraintTokenMintGroup>,
     pub realloc: Option<ConstraintReallocGroup>,
+    pub instructions: Vec<ConstraintInstructions>,
+    pub upgrade_authority: Option<ConstraintUpgradeAuthority>,
+    pub signers: Option<ConstraintSignersGroup>,
+    pub remaining: Option<ConstraintRemainingGroup>,
+    pub dup: Option<ConstraintDup>,
 }
 
 impl ConstraintGroup {
@@ -709,6 +830,14 @@ impl ConstraintGroup {
     pub fn is_close(&self) -> bool {
         self.close.is_some()
     }
+
+    pub fn is_remaining(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    pub fn is_dup(&self) -> bool {
+        self.dup.is_some()
+    }
 }
 
 // A single account constraint *after* merging all tokens into a well formed
@@ -733,6 +862,9 @@ pub enum Constraint {
     TokenAccount(ConstraintTokenAccountGroup),
     Mint(ConstraintTokenMintGroup),
     Realloc(ConstraintReallocGroup),
+    Instructions(ConstraintInstructions),
+    UpgradeAuthority(ConstraintUpgradeAuthority),
+    Signers(ConstraintSignersGroup),
 }
 
 // Constraint token is a single keyword in a `#[account(<TOKEN>)]` attribute.
@@ -768,6 +900,14 @@ pub enum ConstraintToken {
     Realloc(Context<ConstraintRealloc>),
     ReallocPayer(Context<ConstraintReallocPayer>),
     ReallocZero(Context<ConstraintReallocZero>),
+    Instructions(Context<ConstraintInstructions>),
+    UpgradeAuthority(Context<ConstraintUpgradeAuthority>),
+    Signers(Context<ConstraintSigners>),
+    Threshold(Context<ConstraintThreshold>),
+    Remaining(Context<ConstraintRemaining>),
+    RemainingLen(Context<ConstraintRemainingLen>),
+    Dup(Context<ConstraintDup>),
+    Tombstone(Context<ConstraintTombstone>),
     // extensions
     ExtensionGroupPointerAuthority(Context<ConstraintExtensionAuthority>),
     ExtensionGroupPointerGroupAddress(Context<ConstraintExtensionGroupPointerGroupAddress>),
@@ -810,13 +950,13 @@ pub struct ConstraintMut {
 #[derive(Debug, Clone)]
 pub struct ConstraintReallocGroup {
     pub payer: Expr,
-    pub space: Expr,
+    pub space: Option<Expr>, // None => `auto`, resized on exit to fit the data.
     pub zero: Expr,
 }
 
 #[derive(Debug, Clone)]
 pub struct ConstraintRealloc {
-    pub space: Expr,
+    pub space: Option<Expr>, // None => `auto`, resized on exit to fit the data.
 }
 
 #[derive(Debug, Clone)]
@@ -858,6 +998,65 @@ pub struct ConstraintAddress {
     pub error: Option<Expr>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ConstraintInstructions {
+    pub position: InstructionsPosition,
+    pub matcher: Expr,
+    pub error: Option<Expr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintUpgradeAuthority {
+    pub program_data: Expr,
+    pub error: Option<Expr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintSigners {
+    pub signers: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintThreshold {
+    pub threshold: Expr,
+    pub error: Option<Expr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintSignersGroup {
+    pub signers: Expr,
+    pub threshold: Expr,
+    pub error: Option<Expr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintRemaining {}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintRemainingLen {
+    pub len: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintDup {}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintTombstone {}
+
+#[derive(Debug, Clone)]
+pub struct ConstraintRemainingGroup {
+    pub len: Option<Expr>, // None => consume all of the remaining accounts.
+}
+
+/// Where an instruction must (or must not) be relative to the current instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionsPosition {
+    Before,
+    After,
+    NotBefore,
+    NotAfter,
+}
+
 #[derive(Debug, Clone)]
 pub enum ConstraintRentExempt {
     Enforce,
@@ -973,6 +1172,9 @@ pub enum InitKind {
 #[derive(Debug, Clone)]
 pub struct ConstraintClose {
     pub sol_dest: Ident,
+    /// Replace the data with the tombstone of the account instead of assigning it to the system
+    /// program.
+    pub tombstone: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -50,6 +50,13 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
         "executable" => {
             ConstraintToken::Executable(Context::new(ident.span(), ConstraintExecutable {}))
         }
+        "remaining" => {
+            ConstraintToken::Remaining(Context::new(ident.span(), ConstraintRemaining {}))
+        }
+        "dup" => ConstraintToken::Dup(Context::new(ident.span(), ConstraintDup {})),
+        "tombstone" => {
+            ConstraintToken::Tombstone(Context::new(ident.span(), ConstraintTombstone {}))
+        }
         "mint" => {
             stream.parse::<Token![:]>()?;
             stream.parse::<Token![:]>()?;
@@ -381,10 +388,12 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                     .span()
                     .join(stream.span())
                     .unwrap_or_else(|| ident.span());
+                let space: Expr = stream.parse()?;
+                let is_auto = matches!(&space, Expr::Path(path) if path.path.is_ident("auto"));
                 ConstraintToken::Realloc(Context::new(
                     span,
                     ConstraintRealloc {
-                        space: stream.parse()?,
+                        space: (!is_auto).then_some(space),
                     },
                 ))
             } else {
@@ -415,6 +424,33 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                 }
             }
         }
+        "instructions" => {
+            stream.parse::<Token![:]>()?;
+            stream.parse::<Token![:]>()?;
+            let kw = stream.call(Ident::parse_any)?.to_string();
+            stream.parse::<Token![=]>()?;
+
+            let span = ident
+                .span()
+                .join(stream.span())
+                .unwrap_or_else(|| ident.span());
+
+            let position = match kw.as_str() {
+                "before" => InstructionsPosition::Before,
+                "after" => InstructionsPosition::After,
+                "not_before" => InstructionsPosition::NotBefore,
+                "not_after" => InstructionsPosition::NotAfter,
+                _ => return Err(ParseError::new(ident.span(), "Invalid attribute. instructions::before, instructions::after, instructions::not_before and instructions::not_after are the only valid attributes")),
+            };
+            ConstraintToken::Instructions(Context::new(
+                span,
+                ConstraintInstructions {
+                    position,
+                    matcher: stream.parse()?,
+                    error: parse_optional_custom_error(&stream)?,
+                },
+            ))
+        }
         _ => {
             stream.parse::<Token![=]>()?;
             let span = ident
@@ -472,6 +508,7 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                     span,
                     ConstraintClose {
                         sol_dest: stream.parse()?,
+                        tombstone: false,
                     },
                 )),
                 "address" => ConstraintToken::Address(Context::new(
@@ -481,6 +518,32 @@ pub fn parse_token(stream: ParseStream) -> ParseResult<ConstraintToken> {
                         error: parse_optional_custom_error(&stream)?,
                     },
                 )),
+                "upgrade_authority" => ConstraintToken::UpgradeAuthority(Context::new(
+                    span,
+                    ConstraintUpgradeAuthority {
+                        program_data: stream.parse()?,
+                        error: parse_optional_custom_error(&stream)?,
+                    },
+                )),
+                "signers" => ConstraintToken::Signers(Context::new(
+                    span,
+                    ConstraintSigners {
+                        signers: stream.parse()?,
+                    },
+                )),
+                "threshold" => ConstraintToken::Threshold(Context::new(
+                    span,
+                    ConstraintThreshold {
+                        threshold: stream.parse()?,
+                        error: parse_optional_custom_error(&stream)?,
+                    },
+                )),
+                "len" => ConstraintToken::RemainingLen(Context::new(
+                    span,
+                    ConstraintRemainingLen {
+                        len: stream.parse()?,
+                    },
+                )),
                 _ => return Err(ParseError::new(ident.span(), "Invalid attribute")),
             }
         }
@@ -543,6 +606,14 @@ pub struct ConstraintGroupBuilder<'ty> {
     pub realloc: Option<Context<ConstraintRealloc>>,
     pub realloc_payer: Option<Context<ConstraintReallocPayer>>,
     pub realloc_zero: Option<Context<ConstraintReallocZero>>,
+    pub instructions: Vec<Context<ConstraintInstructions>>,
+    pub upgrade_authority: Option<Context<ConstraintUpgradeAuthority>>,
+    pub signers: Option<Context<ConstraintSigners>>,
+    pub threshold: Option<Context<ConstraintThreshold>>,
+    pub remaining: Option<Context<ConstraintRemaining>>,
+    pub remaining_len: Option<Context<ConstraintRemainingLen>>,
+    pub dup: Option<Context<ConstraintDup>>,
+    pub tombstone: Option<Context<ConstraintTombstone>>,
 }
 
 impl<'ty> ConstraintGroupBuilder<'ty> {
@@ -588,10 +659,89 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             realloc: None,
             realloc_payer: None,
             realloc_zero: None,
+            instructions: Vec::new(),
+            upgrade_authority: None,
+            signers: None,
+            threshold: None,
+            remaining: None,
+            remaining_len: None,
+            dup: None,
+            tombstone: None,
         }
     }
 
     pub fn build(mut self) -> ParseResult<ConstraintGroup> {
+        // Dup.
+        if let Some(d) = &self.dup {
+            if self.mutable.is_none() && self.init.is_none() {
+                return Err(ParseError::new(
+                    d.span(),
+                    "dup can only be used with mut or init",
+                ));
+            }
+        }
+
+        // Tombstone.
+        if let Some(t) = &self.tombstone {
+            if self.close.is_none() {
+                return Err(ParseError::new(
+                    t.span(),
+                    "tombstone must be used with close",
+                ));
+            }
+        }
+
+        // Signers.
+        match (&self.signers, &self.threshold) {
+            (Some(s), None) => {
+                return Err(ParseError::new(
+                    s.span(),
+                    "threshold must be provided with signers",
+                ))
+            }
+            (None, Some(t)) => {
+                return Err(ParseError::new(
+                    t.span(),
+                    "signers must be provided with threshold",
+                ))
+            }
+            _ => (),
+        }
+
+        // Remaining.
+        if let Some(l) = &self.remaining_len {
+            if self.remaining.is_none() {
+                return Err(ParseError::new(
+                    l.span(),
+                    "len can only be provided with remaining",
+                ));
+            }
+        }
+        if let Some(r) = &self.remaining {
+            if self.init.is_some()
+                || self.zeroed.is_some()
+                || self.close.is_some()
+                || self.realloc.is_some()
+            {
+                return Err(ParseError::new(
+                    r.span(),
+                    "remaining cannot be used with init, zero, close or realloc",
+                ));
+            }
+            if self.token_mint.is_some()
+                || self.token_authority.is_some()
+                || self.associated_token_mint.is_some()
+                || self.associated_token_authority.is_some()
+                || self.mint_authority.is_some()
+                || self.mint_decimals.is_some()
+            {
+                return Err(ParseError::new(
+                    r.span(),
+                    "remaining cannot be used with token, associated_token or mint constraints",
+                ));
+            }
+        }
+
         // Init.
         if let Some(i) = &self.init {
             if cfg!(not(feature = "init-if-needed")) && i.if_needed {
@@ -691,7 +841,7 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
                     "realloc::payer must be provided when using realloc",
                 ));
             }
-            if self.realloc_zero.is_none() {
+            if self.realloc_zero.is_none() && r.space.is_some() {
                 return Err(ParseError::new(
                     r.span(),
                     "realloc::zero must be provided when using realloc",
@@ -800,6 +950,14 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             realloc,
             realloc_payer,
             realloc_zero,
+            instructions,
+            upgrade_authority,
+            signers,
+            threshold,
+            remaining,
+            remaining_len,
+            dup,
+            tombstone,
         } = self;
 
         // Converts Option<Context<T>> -> Option<T>.
@@ -1015,7 +1173,10 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             realloc: realloc.as_ref().map(|r| ConstraintReallocGroup {
                 payer: into_inner!(realloc_payer).unwrap().target,
                 space: r.space.clone(),
-                zero: into_inner!(realloc_zero).unwrap().zero,
+                // The data written on exit overwrites the whole account with `auto`.
+                zero: into_inner!(realloc_zero)
+                    .map(|c| c.zero)
+                    .unwrap_or_else(|| syn::parse_quote!(false)),
             }),
             zeroed: into_inner!(zeroed),
             mutable: into_inner!(mutable),
@@ -1025,12 +1186,30 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             owner: into_inner!(owner),
             rent_exempt: into_inner!(rent_exempt),
             executable: into_inner!(executable),
-            close: into_inner!(close),
+            close: into_inner!(close).map(|close| ConstraintClose {
+                tombstone: tombstone.is_some(),
+                ..close
+            }),
             address: into_inner!(address),
             associated_token: if !is_init { associated_token } else { None },
             seeds,
             token_account: if !is_init {token_account} else {None},
             mint: if !is_init {mint} else {None},
+            instructions: into_inner_vec!(instructions),
+            upgrade_authority: into_inner!(upgrade_authority),
+            signers: signers.map(|s| {
+                let threshold = into_inner!(threshold)
+                    .expect("threshold must be provided with signers");
+                ConstraintSignersGroup {
+                    signers: s.into_inner().signers,
+                    threshold: threshold.threshold,
+                    error: threshold.error,
+                }
+            }),
+            remaining: remaining.map(|_| ConstraintRemainingGroup {
+                len: into_inner!(remaining_len).map(|l| l.len),
+            }),
+            dup: into_inner!(dup),
         })
     }
 
@@ -1067,6 +1246,14 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
             ConstraintToken::Realloc(c) => self.add_realloc(c),
             ConstraintToken::ReallocPayer(c) => self.add_realloc_payer(c),
             ConstraintToken::ReallocZero(c) => self.add_realloc_zero(c),
+            ConstraintToken::Instructions(c) => self.add_instructions(c),
+            ConstraintToken::UpgradeAuthority(c) => self.add_upgrade_authority(c),
+            ConstraintToken::Signers(c) => self.add_signers(c),
+            ConstraintToken::Threshold(c) => self.add_threshold(c),
+            ConstraintToken::Remaining(c) => self.add_remaining(c),
+            ConstraintToken::RemainingLen(c) => self.add_remaining_len(c),
+            ConstraintToken::Dup(c) => self.add_dup(c),
+            ConstraintToken::Tombstone(c) => self.add_tombstone(c),
             ConstraintToken::ExtensionGroupPointerAuthority(c) => {
                 self.add_extension_group_pointer_authority(c)
             }
@@ -1200,6 +1387,12 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
                 "realloc must be on an Account, LazyAccount or AccountLoader",
             ));
         }
+        if c.space.is_none() && !matches!(self.f_ty, Some(Ty::Account(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "realloc = auto must be on an Account",
+            ));
+        }
         if self.mutable.is_none() {
             return Err(ParseError::new(
                 c.span(),
@@ -1241,6 +1434,82 @@ impl<'ty> ConstraintGroupBuilder<'ty> {
         Ok(())
     }
 
+    fn add_instructions(&mut self, c: Context<ConstraintInstructions>) -> ParseResult<()> {
+        if !matches!(self.f_ty, Some(Ty::UncheckedAccount | Ty::AccountInfo)) {
+            return Err(ParseError::new(
+                c.span(),
+                "instructions constraints must be on an UncheckedAccount or AccountInfo of the Instructions sysvar",
+            ));
+        }
+        self.instructions.push(c);
+        Ok(())
+    }
+
+    fn add_upgrade_authority(&mut self, c: Context<ConstraintUpgradeAuthority>) -> ParseResult<()> {
+        if self.upgrade_authority.is_some() {
+            return Err(ParseError::new(
+                c.span(),
+                "upgrade_authority already provided",
+            ));
+        }
+        self.upgrade_authority.replace(c);
+        Ok(())
+    }
+
+    fn add_signers(&mut self, c: Context<ConstraintSigners>) -> ParseResult<()> {
+        if self.signers.is_some() {
+            return Err(ParseError::new(c.span(), "signers already provided"));
+        }
+        self.signers.replace(c);
+        Ok(())
+    }
+
+    fn add_threshold(&mut self, c: Context<ConstraintThreshold>) -> ParseResult<()> {
+        if self.threshold.is_some() {
+            return Err(ParseError::new(c.span(), "threshold already provided"));
+        }
+        self.threshold.replace(c);
+        Ok(())
+    }
+
+    fn add_remaining(&mut self, c: Context<ConstraintRemaining>) -> ParseResult<()> {
+        if matches!(self.f_ty, None | Some(Ty::LazyAccount(_))) {
+            return Err(ParseError::new(
+                c.span(),
+                "remaining must be on a Vec of accounts other than LazyAccount",
+            ));
+        }
+        if self.remaining.is_some() {
+            return Err(ParseError::new(c.span(), "remaining already provided"));
+        }
+        self.remaining.replace(c);
+        Ok(())
+    }
+
+    fn add_remaining_len(&mut self, c: Context<ConstraintRemainingLen>) -> ParseResult<()> {
+        if self.remaining_len.is_some() {
+            return Err(ParseError::new(c.span(), "len already provided"));
+        }
+        self.remaining_len.replace(c);
+        Ok(())
+    }
+
+    fn add_dup(&mut self, c: Context<ConstraintDup>) -> ParseResult<()> {
+        if self.dup.is_some() {
+            return Err(ParseError::new(c.span(), "dup already provided"));
+        }
+        self.dup.replace(c);
+        Ok(())
+    }
+
+    fn add_tombstone(&mut self, c: Context<ConstraintTombstone>) -> ParseResult<()> {
+        if self.tombstone.is_some() {
+            return Err(ParseError::new(c.span(), "tombstone already provided"));
+        }
+        self.tombstone.replace(c);
+        Ok(())
+    }
+
     fn add_close(&mut self, c: Context<ConstraintClose>) -> ParseResult<()> {
         if !matches!(self.f_ty, Some(Ty::Account(_)))
             && !matches!(self.f_ty, Some(Ty::LazyAccount(_)))
@@ -8,16 +8,18 @@ use syn::parse::{Error as ParseError, Result as ParseResult};
 use syn::Path;
 
 pub fn parse(accounts_struct: &syn::ItemStruct) -> ParseResult<AccountsStruct> {
-    let instruction_api: Option<Punctuated<Expr, Comma>> = accounts_struct
+    let instruction_api = parse_instruction_attr(&accounts_struct.attrs)?;
+
+    let transfer_hook_attr = accounts_struct
         .attrs
         .iter()
-        .find(|a| {
-            a.path
-                .get_ident()
-                .is_some_and(|ident| ident == "instruction")
-        })
-        .map(|ix_attr| ix_attr.parse_args_with(Punctuated::<Expr, Comma>::parse_terminated))
-        .transpose()?;
+        .find(|attr| attr.path.is_ident("transfer_hook"));
+    if let Some(attr) = transfer_hook_attr {
+        if cfg!(not(feature = "transfer-hook")) {
+            return Err(ParseError::new_spanned(attr, TRANSFER_HOOK_FEATURE_ERROR));
+        }
+    }
+    let transfer_hook = transfer_hook_attr.is_some();
 
     #[cfg(feature = "event-cpi")]
     let accounts_struct = {
@@ -50,14 +52,96 @@ pub fn parse(accounts_struct: &syn::ItemStruct) -> ParseResult<AccountsStruct> {
     };
 
     constraints_cross_checks(&fields)?;
+    if transfer_hook {
+        transfer_hook_cross_checks(&accounts_struct, &fields)?;
+    }
 
     Ok(AccountsStruct::new(
         accounts_struct,
         fields,
         instruction_api,
+        transfer_hook,
     ))
 }
 
+// Parses the `#[instruction(..)]` attribute, which declares the instruction arguments on structs
+// and forwards instruction arguments on composite fields.
+fn parse_instruction_attr(
+    attrs: &[syn::Attribute],
+) -> ParseResult<Option<Punctuated<Expr, Comma>>> {
+    attrs
+        .iter()
+        .find(|a| {
+            a.path
+                .get_ident()
+                .is_some_and(|ident| ident == "instruction")
+        })
+        .map(|ix_attr| ix_attr.parse_args_with(Punctuated::<Expr, Comma>::parse_terminated))
+        .transpose()
+}
+
+fn transfer_hook_cross_checks(
+    accounts_struct: &syn::ItemStruct,
+    fields: &[AccountField],
+) -> ParseResult<()> {
+    const INTERFACE_ACCOUNTS: [&str; 5] = [
+        "source token account",
+        "mint",
+        "destination token account",
+        "authority",
+        "extra account meta list",
+    ];
+
+    if fields.len() < INTERFACE_ACCOUNTS.len() {
+        return Err(ParseError::new_spanned(
+            &accounts_struct.ident,
+            format!(
+                "transfer hook accounts must start with the {} interface accounts: {}",
+                INTERFACE_ACCOUNTS.len(),
+                INTERFACE_ACCOUNTS.join(", ")
+            ),
+        ));
+    }
+
+    for (i, field) in fields.iter().enumerate() {
+        let field = match field {
+            AccountField::Field(field) => field,
+            AccountField::CompositeField(field) => {
+                return Err(ParseError::new(
+                    field.ident.span(),
+                    "composite fields are not allowed in transfer hook accounts",
+                ))
+            }
+        };
+        if field.is_optional {
+            return Err(ParseError::new(
+                field.ident.span(),
+                "optional accounts are not allowed in transfer hook accounts",
+            ));
+        }
+        if field.constraints.init.is_some() {
+            return Err(ParseError::new(
+                field.ident.span(),
+                "init is not allowed in transfer hook accounts",
+            ));
+        }
+
+        // Extra accounts must be resolvable by the token program
+        if i >= INTERFACE_ACCOUNTS.len()
+            && field.constraints.seeds.is_none()
+            && field.constraints.address.is_none()
+            && !matches!(field.ty, Ty::Program(_))
+        {
+            return Err(ParseError::new(
+                field.ident.span(),
+                "extra transfer hook accounts require a `seeds` or `address` constraint, or the `Program` type",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn constraints_cross_checks(fields: &[AccountField]) -> ParseResult<()> {
     // COMMON ERROR MESSAGE
     let message = |constraint: &str, field: &str, required: bool| {
@@ -300,16 +384,51 @@ fn constraints_cross_checks(fields: &[AccountField]) -> ParseResult<()> {
         }
     }
 
+    // REMAINING
+    // A remaining accounts field without a length consumes all of the accounts left, so no other
+    // field can come after it.
+    if let Some((pos, field)) = fields.iter().enumerate().find_map(|(pos, f)| match f {
+        AccountField::Field(field)
+            if field
+                .constraints
+                .remaining
+                .as_ref()
+                .is_some_and(|r| r.len.is_none()) =>
+        {
+            Some((pos, field))
+        }
+        _ => None,
+    }) {
+        if pos != fields.len() - 1 {
+            return Err(ParseError::new(
+                field.ident.span(),
+                "remaining accounts without a len must be the last field",
+            ));
+        }
+    }
+
     Ok(())
 }
 
 pub fn parse_account_field(f: &syn::Field) -> ParseResult<AccountField> {
     let ident = f.ident.clone().unwrap();
     let docs = docs::parse(&f.attrs);
+    let instruction_args = parse_instruction_attr(&f.attrs)?;
+    if let Some(field) = parse_remaining_field(f)? {
+        check_no_instruction_args(&instruction_args)?;
+        return Ok(AccountField::Field(field));
+    }
     let account_field = match is_field_primitive(f)? {
         true => {
+            check_no_instruction_args(&instruction_args)?;
             let (ty, is_optional) = parse_ty(f)?;
             let account_constraints = constraints::parse(f, Some(&ty))?;
+            if account_constraints.is_remaining() {
+                return Err(ParseError::new(
+                    f.ty.span(),
+                    "remaining must be on a Vec of accounts",
+                ));
+            }
             AccountField::Field(Field {
                 ident,
                 ty,
@@ -332,6 +451,7 @@ pub fn parse_account_field(f: &syn::Field) -> ParseResult<AccountField> {
                 constraints: account_constraints,
                 symbol: ident_string(f)?.0,
                 raw_field: f.clone(),
+                instruction_args,
                 docs,
             })
         }
@@ -339,6 +459,68 @@ pub fn parse_account_field(f: &syn::Field) -> ParseResult<AccountField> {
     Ok(account_field)
 }
 
+fn check_no_instruction_args(
+    instruction_args: &Option<Punctuated<Expr, Comma>>,
+) -> ParseResult<()> {
+    match instruction_args {
+        Some(args) => Err(ParseError::new(
+            args.span(),
+            "instruction arguments can only be forwarded to composite fields",
+        )),
+        None => Ok(()),
+    }
+}
+
+// Parses a `#[account(remaining)]` field, i.e. a `Vec` of accounts whose constraints apply to each
+// element. Returns `None` for any other field, including a `Vec` without `remaining`, which is
+// handled as a composite field.
+fn parse_remaining_field(f: &syn::Field) -> ParseResult<Option<Field>> {
+    let inner_ty = match &f.ty {
+        syn::Type::Path(ty_path) if ty_path.path.segments.len() == 1 => {
+            let segment = &ty_path.path.segments[0];
+            match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args)
+                    if segment.ident == "Vec" && args.args.len() == 1 =>
+                {
+                    match &args.args[0] {
+                        syn::GenericArgument::Type(ty) => ty.clone(),
+                        _ => return Ok(None),
+                    }
+                }
+                _ => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    let inner = syn::Field {
+        ty: inner_ty,
+        ..f.clone()
+    };
+    if !is_field_primitive(&inner).unwrap_or_default() {
+        return Ok(None);
+    }
+
+    let (ty, is_optional) = parse_ty(&inner)?;
+    let constraints = constraints::parse(f, Some(&ty))?;
+    if !constraints.is_remaining() {
+        return Ok(None);
+    }
+    if is_optional {
+        return Err(ParseError::new(
+            inner.ty.span(),
+            "remaining accounts cannot be optional",
+        ));
+    }
+
+    Ok(Some(Field {
+        ident: f.ident.clone().unwrap(),
+        ty,
+        is_optional,
+        constraints,
+        docs: docs::parse(&f.attrs),
+    }))
+}
+
 fn is_field_primitive(f: &syn::Field) -> ParseResult<bool> {
     let r = matches!(
         ident_string(f)?.0.as_str(),
@@ -26,12 +26,25 @@ pub fn parse(error_enum: &mut syn::ItemEnum, args: Option<ErrorArgs>) -> Error {
             };
             last_discriminant = id + 1;
 
+            let fields = match &variant.fields {
+                syn::Fields::Unit => None,
+                syn::Fields::Named(fields) => Some(fields.clone()),
+                syn::Fields::Unnamed(_) => {
+                    panic!("Error variants with unnamed fields are not supported, use named fields")
+                }
+            };
+
             // Remove any non-doc attributes on the error variant.
             variant
                 .attrs
                 .retain(|attr| attr.path.segments[0].ident == "doc");
 
-            ErrorCode { id, ident, msg }
+            ErrorCode {
+                id,
+                ident,
+                msg,
+                fields,
+            }
         })
         .collect();
     Error {
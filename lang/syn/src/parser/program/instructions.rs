@@ -1,5 +1,5 @@
 use crate::parser::docs;
-use crate::parser::program::ctx_accounts_ident;
+use crate::parser::program::{ctx_accounts_ident, ctx_accounts_ty, transfer_hook};
 use crate::parser::spl_interface;
 use crate::{FallbackFn, Ix, IxArg, IxReturn, Overrides};
 use syn::parse::{Error as ParseError, Result as ParseResult};
@@ -26,12 +26,25 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<(Vec<Ix>, Option<Fallbac
         })
         .map(|method: &syn::ItemFn| {
             let (ctx, args) = parse_args(method)?;
-            let overrides = parse_overrides(&method.attrs)?;
+            let mut overrides = parse_overrides(&method.attrs)?;
+            if let Some(overrides) = overrides.as_mut() {
+                if let Some(transfer_hook) = &overrides.transfer_hook {
+                    if overrides.discriminator.is_some() {
+                        return Err(ParseError::new(
+                            transfer_hook.span(),
+                            "`transfer_hook` cannot be combined with `discriminator`",
+                        ));
+                    }
+                    overrides
+                        .discriminator
+                        .replace(transfer_hook::execute_discriminator());
+                }
+            }
             let interface_discriminator = spl_interface::parse(&method.attrs);
             let docs = docs::parse(&method.attrs);
             let cfgs = parse_cfg(method);
             let returns = parse_return(method)?;
-            let anchor_ident = ctx_accounts_ident(&ctx.raw_arg)?;
+            let (anchor_ident, anchor_generics) = ctx_accounts_ty(&ctx.raw_arg)?;
             Ok(Ix {
                 raw_method: method.clone(),
                 ident: method.sig.ident.clone(),
@@ -39,6 +52,7 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<(Vec<Ix>, Option<Fallbac
                 cfgs,
                 args,
                 anchor_ident,
+                anchor_generics,
                 returns,
                 interface_discriminator,
                 overrides,
@@ -78,14 +92,28 @@ pub fn parse(program_mod: &syn::ItemMod) -> ParseResult<(Vec<Ix>, Option<Fallbac
 
 /// Parse overrides from the `#[instruction]` attribute proc-macro.
 fn parse_overrides(attrs: &[syn::Attribute]) -> ParseResult<Option<Overrides>> {
-    attrs
+    let overrides = attrs
         .iter()
         .find(|attr| match attr.path.segments.last() {
             Some(seg) => seg.ident == "instruction",
             _ => false,
         })
-        .map(|attr| attr.parse_args())
-        .transpose()
+        .map(|attr| attr.parse_args::<Overrides>())
+        .transpose()?;
+    if let Some(serializer) = overrides.as_ref().and_then(|ov| ov.serializer.as_ref()) {
+        return Err(ParseError::new(
+            serializer.span(),
+            "`serializer` is only allowed on accounts",
+        ));
+    }
+    if let Some(tombstone) = overrides.as_ref().and_then(|ov| ov.tombstone.as_ref()) {
+        return Err(ParseError::new_spanned(
+            tombstone,
+            "`tombstone` is only allowed on accounts",
+        ));
+    }
+
+    Ok(overrides)
 }
 
 pub fn parse_args(method: &syn::ItemFn) -> ParseResult<(IxArg, Vec<IxArg>)> {
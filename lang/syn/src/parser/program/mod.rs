@@ -4,20 +4,30 @@ use syn::parse::{Error as ParseError, Result as ParseResult};
 use syn::spanned::Spanned;
 
 mod instructions;
+pub mod transfer_hook;
 
-pub fn parse(program_mod: syn::ItemMod) -> ParseResult<Program> {
+pub fn parse(mut program_mod: syn::ItemMod) -> ParseResult<Program> {
     let docs = docs::parse(&program_mod.attrs);
-    let (ixs, fallback_fn) = instructions::parse(&program_mod)?;
+    let (mut ixs, fallback_fn) = instructions::parse(&program_mod)?;
+    transfer_hook::add_extra_account_meta_list_ixs(&mut program_mod, &mut ixs)?;
     Ok(Program {
         ixs,
         name: program_mod.ident.clone(),
         docs,
         program_mod,
         fallback_fn,
+        args: None,
     })
 }
 
 fn ctx_accounts_ident(path_ty: &syn::PatType) -> ParseResult<proc_macro2::Ident> {
+    ctx_accounts_ty(path_ty).map(|(ident, _)| ident)
+}
+
+// Parses the ident and the generic arguments of the accounts struct of the context.
+fn ctx_accounts_ty(
+    path_ty: &syn::PatType,
+) -> ParseResult<(proc_macro2::Ident, syn::PathArguments)> {
     let p = match &*path_ty.ty {
         syn::Type::Path(p) => &p.path,
         _ => return Err(ParseError::new(path_ty.ty.span(), "invalid type")),
@@ -50,5 +60,27 @@ fn ctx_accounts_ident(path_ty: &syn::PatType) -> ParseResult<proc_macro2::Ident>
             ))
         }
     };
-    Ok(path.segments[0].ident.clone())
+    let mut generics = path.segments[0].arguments.clone();
+    elide_lifetimes(&mut generics);
+    Ok((path.segments[0].ident.clone(), generics))
+}
+
+// Replaces the lifetimes of the generic arguments with `'_`, since the lifetimes of the handler
+// are not in scope of the generated code.
+fn elide_lifetimes(args: &mut syn::PathArguments) {
+    if let syn::PathArguments::AngleBracketed(args) = args {
+        for arg in args.args.iter_mut() {
+            match arg {
+                syn::GenericArgument::Lifetime(lifetime) => {
+                    *lifetime = syn::Lifetime::new("'_", lifetime.span());
+                }
+                syn::GenericArgument::Type(syn::Type::Path(ty_path)) => ty_path
+                    .path
+                    .segments
+                    .iter_mut()
+                    .for_each(|segment| elide_lifetimes(&mut segment.arguments)),
+                _ => {}
+            }
+        }
+    }
 }
@@ -0,0 +1,131 @@
+use crate::parser::program::instructions::parse_return;
+use crate::{Ix, Overrides};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Error as ParseError, Result as ParseResult};
+use syn::spanned::Spanned;
+
+/// Discriminator of the transfer hook interface `Execute` instruction.
+const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+/// Discriminator of the transfer hook interface `InitializeExtraAccountMetaList` instruction.
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [43, 34, 13, 49, 167, 88, 235, 235];
+/// Discriminator of the transfer hook interface `UpdateExtraAccountMetaList` instruction.
+const UPDATE_DISCRIMINATOR: [u8; 8] = [157, 105, 42, 146, 102, 85, 241, 174];
+
+pub fn execute_discriminator() -> TokenStream {
+    let discriminator = EXECUTE_DISCRIMINATOR;
+    quote! { &[#(#discriminator),*] }
+}
+
+/// Add the `initialize_extra_account_meta_list` and `update_extra_account_meta_list`
+/// instructions of the transfer hook interface if an instruction is marked with
+/// `#[instruction(transfer_hook)]`.
+pub fn add_extra_account_meta_list_ixs(
+    program_mod: &mut syn::ItemMod,
+    ixs: &mut Vec<Ix>,
+) -> ParseResult<()> {
+    let mut execute_ixs = ixs.iter().filter(|ix| {
+        ix.overrides
+            .as_ref()
+            .is_some_and(|overrides| overrides.transfer_hook.is_some())
+    });
+    let execute_ix = match execute_ixs.next() {
+        Some(ix) => ix,
+        None => return Ok(()),
+    };
+    if let Some(ix) = execute_ixs.next() {
+        return Err(ParseError::new(
+            ix.raw_method.span(),
+            "Only one instruction can be marked with `transfer_hook`",
+        ));
+    }
+
+    let execute_accounts = &execute_ix.anchor_ident;
+    let execute_generics = &execute_ix.anchor_generics;
+    let initialize_fn: syn::ItemFn = syn::parse_quote! {
+        /// Initialize the account that stores the extra accounts of the transfer hook `Execute`
+        /// instruction.
+        pub fn initialize_extra_account_meta_list(
+            ctx: anchor_lang::context::Context<super::InitializeExtraAccountMetaList>,
+        ) -> anchor_lang::Result<()> {
+            let extra_account_metas = <super::#execute_accounts #execute_generics as ::anchor_spl::token_interface::TransferHookAccounts>::extra_account_metas()?;
+            ::anchor_spl::token_interface::initialize_extra_account_meta_list(
+                ctx.program_id,
+                anchor_lang::ToAccountInfo::to_account_info(&ctx.accounts.extra_account_meta_list),
+                &anchor_lang::Key::key(&ctx.accounts.mint),
+                anchor_lang::ToAccountInfo::to_account_info(&ctx.accounts.system_program),
+                ctx.bumps.extra_account_meta_list,
+                &extra_account_metas,
+            )
+        }
+    };
+    let update_fn: syn::ItemFn = syn::parse_quote! {
+        /// Update the account that stores the extra accounts of the transfer hook `Execute`
+        /// instruction.
+        pub fn update_extra_account_meta_list(
+            ctx: anchor_lang::context::Context<super::UpdateExtraAccountMetaList>,
+        ) -> anchor_lang::Result<()> {
+            let extra_account_metas = <super::#execute_accounts #execute_generics as ::anchor_spl::token_interface::TransferHookAccounts>::extra_account_metas()?;
+            ::anchor_spl::token_interface::update_extra_account_meta_list(
+                anchor_lang::ToAccountInfo::to_account_info(&ctx.accounts.extra_account_meta_list),
+                &extra_account_metas,
+            )
+        }
+    };
+
+    let new_ixs = [
+        (
+            initialize_fn,
+            "InitializeExtraAccountMetaList",
+            INITIALIZE_DISCRIMINATOR,
+        ),
+        (
+            update_fn,
+            "UpdateExtraAccountMetaList",
+            UPDATE_DISCRIMINATOR,
+        ),
+    ]
+    .into_iter()
+    .map(|(method, anchor_ident, discriminator)| {
+        Ok(Ix {
+            ident: method.sig.ident.clone(),
+            docs: crate::parser::docs::parse(&method.attrs),
+            cfgs: execute_ix.cfgs.clone(),
+            args: vec![],
+            returns: parse_return(&method)?,
+            anchor_ident: format_ident!("{}", anchor_ident),
+            anchor_generics: syn::PathArguments::None,
+            interface_discriminator: None,
+            overrides: Some(Overrides {
+                discriminator: Some(quote! { &[#(#discriminator),*] }),
+                ..Default::default()
+            }),
+            raw_method: method,
+        })
+    })
+    .collect::<ParseResult<Vec<_>>>()?;
+
+    let content = &mut program_mod
+        .content
+        .as_mut()
+        .ok_or_else(|| ParseError::new(Span::call_site(), "program content not provided"))?
+        .1;
+    for ix in &new_ixs {
+        if content.iter().any(|item| match item {
+            syn::Item::Fn(item_fn) => item_fn.sig.ident == ix.ident,
+            _ => false,
+        }) {
+            return Err(ParseError::new(
+                ix.ident.span(),
+                format!(
+                    "`{}` is generated by `transfer_hook` and must not be defined manually",
+                    ix.ident
+                ),
+            ));
+        }
+        content.push(syn::Item::Fn(ix.raw_method.clone()));
+    }
+    ixs.extend(new_ixs);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+// The program codegen checks the features of the program crate, and the generated IDL
+// instructions use `AccountInfo::realloc`.
+#![allow(unexpected_cfgs, deprecated)]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Epoch;
+use anchor_lang::solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT;
+use anchor_lang::Discriminator;
+use solana_instruction::BorrowedInstruction;
+use solana_instructions_sysvar as instructions;
+use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub const CALLER: Pubkey = Pubkey::new_from_array([1; 32]);
+
+#[program]
+pub mod callers {
+    use super::*;
+
+    #[instruction(no_cpi)]
+    pub fn top_level(_ctx: Context<Empty>) -> Result<()> {
+        Ok(())
+    }
+
+    #[instruction(allowed_callers = [CALLER])]
+    pub fn from_caller(_ctx: Context<Empty>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Empty {}
+
+static STACK_HEIGHT: AtomicU64 = AtomicU64::new(0);
+
+struct StackHeightStubs;
+
+impl SyscallStubs for StackHeightStubs {
+    fn sol_get_stack_height(&self) -> u64 {
+        STACK_HEIGHT.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn test_caller_checks() {
+    set_syscall_stubs(Box::new(StackHeightStubs));
+    let set_stack_height = |cpi_depth: usize| {
+        STACK_HEIGHT.store(
+            (TRANSACTION_LEVEL_STACK_HEIGHT + cpi_depth) as u64,
+            Ordering::SeqCst,
+        )
+    };
+
+    let sysvar_data = |program_id: &Pubkey| {
+        let ix = BorrowedInstruction {
+            program_id,
+            accounts: vec![],
+            data: &[],
+        };
+        instructions::construct_instructions_data(&[ix])
+    };
+    let key = instructions::ID;
+    let mut lamports = [0; 2];
+    let [l0, l1] = &mut lamports;
+    let mut caller_data = sysvar_data(&CALLER);
+    let mut other_data = sysvar_data(&ID);
+    let caller_sysvar = AccountInfo::new(
+        &key,
+        false,
+        false,
+        l0,
+        &mut caller_data,
+        &key,
+        false,
+        Epoch::default(),
+    );
+    let other_sysvar = AccountInfo::new(
+        &key,
+        false,
+        false,
+        l1,
+        &mut other_data,
+        &key,
+        false,
+        Epoch::default(),
+    );
+
+    let caller = [caller_sysvar];
+    let other = [other_sysvar];
+    let top_level = instruction::TopLevel::DISCRIMINATOR;
+    let from_caller = instruction::FromCaller::DISCRIMINATOR;
+    let error = |error_code: ErrorCode| ProgramError::from(Error::from(error_code));
+
+    set_stack_height(0);
+    assert!(entry(&ID, &[], top_level).is_ok());
+    assert_eq!(
+        entry(&ID, &caller, from_caller).unwrap_err(),
+        error(ErrorCode::InstructionCallerNotAllowed)
+    );
+
+    // The Instructions sysvar can be given as a remaining account.
+    set_stack_height(1);
+    assert_eq!(
+        entry(&ID, &[], top_level).unwrap_err(),
+        error(ErrorCode::InstructionCalledViaCpi)
+    );
+    assert!(entry(&ID, &caller, from_caller).is_ok());
+    assert_eq!(
+        entry(&ID, &other, from_caller).unwrap_err(),
+        error(ErrorCode::InstructionCallerNotAllowed)
+    );
+    assert_eq!(
+        entry(&ID, &[], from_caller).unwrap_err(),
+        error(ErrorCode::AccountNotEnoughKeys)
+    );
+
+    set_stack_height(2);
+    assert_eq!(
+        entry(&ID, &caller, from_caller).unwrap_err(),
+        error(ErrorCode::InstructionCallerNotAllowed)
+    );
+}
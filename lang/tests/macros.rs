@@ -26,3 +26,560 @@ fn test_pubkey() {
     let want = Pubkey::from_str("A7yUYJNEVYRLE4QWsnc9rE9JRsm7DfqEmLscQVwkffAk");
     assert_eq!(want.unwrap(), pk::PUBKEY);
 }
+
+mod errors {
+    use anchor_lang::prelude::*;
+
+    #[error_code]
+    pub enum MyError {
+        #[msg("Unit error")]
+        Unit,
+        #[msg("Insufficient funds, needed {needed} but only {available} is available")]
+        InsufficientFunds { needed: u64, available: u64 },
+    }
+}
+
+#[test]
+fn test_error_code_fields() {
+    use anchor_lang::error::{AnchorError, DecodeError, Error};
+    use errors::MyError;
+
+    let error = MyError::InsufficientFunds {
+        needed: 10,
+        available: 3,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Insufficient funds, needed 10 but only 3 is available"
+    );
+    assert_eq!(u32::from(error.clone()), 6001);
+
+    let Error::AnchorError(anchor_error) = Error::from(error) else {
+        panic!("expected an AnchorError");
+    };
+    let AnchorError {
+        error_code_number,
+        error_data,
+        ..
+    } = anchor_error.as_ref();
+    assert_eq!(*error_code_number, 6001);
+    assert!(matches!(
+        anchor_error.decode(),
+        Some(MyError::InsufficientFunds {
+            needed: 10,
+            available: 3
+        })
+    ));
+
+    assert!(Error::from(MyError::Unit)
+        .to_string()
+        .contains("Unit error"));
+    assert!(matches!(
+        MyError::decode_error(6000, &[]),
+        Some(MyError::Unit)
+    ));
+    assert!(MyError::decode_error(6001, &error_data.as_ref().unwrap()[..8]).is_none());
+    assert!(MyError::decode_error(6002, &[]).is_none());
+}
+
+#[test]
+fn test_error_from_cpi() {
+    use anchor_lang::error::{Error, ErrorCode, ErrorOrigin, Source};
+    use anchor_lang::solana_program::program_error::ProgramError;
+    use errors::MyError;
+
+    let program_id = Pubkey::new_unique();
+    let error =
+        Error::from_cpi::<MyError>(program_id, ProgramError::Custom(6000)).with_source(Source {
+            filename: "src/lib.rs",
+            line: 1,
+        });
+    assert!(matches!(error.decode(), Some(MyError::Unit)));
+    let Error::AnchorError(anchor_error) = &error else {
+        panic!("expected an AnchorError");
+    };
+    assert_eq!(anchor_error.error_name, "Unit");
+    assert!(matches!(
+        &anchor_error.error_origin,
+        Some(ErrorOrigin::Cpi { program_id: id, caller: Some(caller) })
+            if *id == program_id && matches!(caller.as_ref(), ErrorOrigin::Source(_))
+    ));
+
+    let error = Error::from_cpi::<MyError>(program_id, ProgramError::Custom(2000));
+    assert!(matches!(error.decode(), Some(ErrorCode::ConstraintMut)));
+
+    // The error data isn't returned by CPIs, so variants with fields are kept as is.
+    let error = Error::from_cpi::<MyError>(program_id, ProgramError::Custom(6001));
+    assert_eq!(error, ProgramError::Custom(6001).into());
+
+    let error = Error::from_cpi::<MyError>(program_id, ProgramError::Custom(1));
+    assert_eq!(error, ProgramError::Custom(1).into());
+    assert!(error.decode::<MyError>().is_none());
+}
+
+// `#[account]` expects the program id at the crate root.
+anchor_lang::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+/// Creates an account owned by the program.
+fn account_info<'a>(
+    key: &'a Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    lamports: &'a mut u64,
+    data: &'a mut [u8],
+) -> anchor_lang::prelude::AccountInfo<'a> {
+    anchor_lang::prelude::AccountInfo::new(
+        key,
+        is_signer,
+        is_writable,
+        lamports,
+        data,
+        &ID,
+        false,
+        anchor_lang::solana_program::clock::Epoch::default(),
+    )
+}
+
+/// Deserializes the accounts struct `T` from `infos`, as the program would.
+fn try_accounts<'info, T>(
+    infos: &'info [anchor_lang::prelude::AccountInfo<'info>],
+    ix_data: &[u8],
+) -> anchor_lang::Result<T>
+where
+    T: anchor_lang::Accounts<'info, T::Bumps> + anchor_lang::Bumps,
+    T::Bumps: Default,
+{
+    let mut accounts = infos;
+    T::try_accounts(
+        &ID,
+        &mut accounts,
+        ix_data,
+        &mut T::Bumps::default(),
+        &mut Default::default(),
+    )
+}
+
+mod duplicates {
+    use anchor_lang::prelude::*;
+
+    #[account]
+    pub struct Data {
+        pub value: u64,
+    }
+
+    #[derive(Accounts)]
+    pub struct Inner<'info> {
+        #[account(mut)]
+        pub inner: Account<'info, Data>,
+    }
+
+    #[derive(Accounts)]
+    pub struct Outer<'info> {
+        #[account(mut)]
+        pub first: Account<'info, Data>,
+        #[account(mut, dup)]
+        pub aliased: Account<'info, Data>,
+        pub read_only: Account<'info, Data>,
+        pub nested: Inner<'info>,
+    }
+}
+
+#[test]
+fn test_duplicate_mut_accounts() {
+    use anchor_lang::error::{Error, ErrorCode};
+    use anchor_lang::prelude::*;
+    use duplicates::{Data, Outer};
+
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut lamports = [1; 4];
+    let mut data = [[0; 16]; 4];
+    for data in &mut data {
+        data[..8].copy_from_slice(Data::DISCRIMINATOR);
+    }
+    let [l1, l2, l3, l4] = &mut lamports;
+    let [d1, d2, d3, d4] = &mut data;
+    // `aliased` and `read_only` share the key of `first`, only `nested.inner` differs.
+    let infos = [
+        account_info(&keys[0], false, true, l1, d1),
+        account_info(&keys[0], false, true, l2, d2),
+        account_info(&keys[0], false, true, l3, d3),
+        account_info(&keys[1], false, true, l4, d4),
+    ];
+
+    fn check<'info>(infos: &'info [AccountInfo<'info>]) -> Result<()> {
+        let parsed = try_accounts::<Outer>(infos, &[]).unwrap();
+        anchor_lang::__private::check_duplicate_mut_accounts(|visit| {
+            parsed.__anchor_private_visit_mut_accounts(&mut Vec::new(), visit)
+        })
+    }
+    // Giving the key of `first` for `nested.inner` too is rejected.
+    let duplicated = [
+        infos[0].clone(),
+        infos[1].clone(),
+        infos[2].clone(),
+        infos[0].clone(),
+    ];
+
+    assert!(check(&infos).is_ok());
+    let err = check(&duplicated).err().unwrap();
+    assert_eq!(err, ErrorCode::ConstraintDuplicateMutableAccount.into());
+    let Error::AnchorError(anchor_error) = err else {
+        panic!("expected an AnchorError");
+    };
+    assert!(matches!(
+        &anchor_error.error_origin,
+        Some(anchor_lang::error::ErrorOrigin::AccountName(name)) if name == "first, nested.inner"
+    ));
+}
+
+mod pdas {
+    use anchor_lang::prelude::*;
+
+    pub const SEED: &[u8] = b"seed";
+
+    #[account]
+    pub struct Config {
+        pub value: u64,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(id: u64)]
+    pub struct Pdas<'info> {
+        pub user: Signer<'info>,
+        pub config: Account<'info, Config>,
+        /// CHECK: test
+        #[account(seeds = [b"vault", user.key().as_ref(), &id.to_le_bytes()], bump)]
+        pub vault: UncheckedAccount<'info>,
+        /// CHECK: test
+        #[account(seeds = [SEED, &config.value.to_le_bytes(), user.key.as_ref()], bump)]
+        pub by_value: UncheckedAccount<'info>,
+        /// CHECK: test
+        #[account(seeds = [SEED], seeds::program = other_program.key(), bump)]
+        pub external: UncheckedAccount<'info>,
+        /// CHECK: test
+        pub other_program: UncheckedAccount<'info>,
+    }
+}
+
+#[test]
+fn test_pda_helpers() {
+    use pdas::{Config, Pdas, SEED};
+
+    let program_id = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (vault, bump) = Pdas::find_vault_address(&program_id, &user, 7);
+    assert_eq!(
+        (vault, bump),
+        Pubkey::find_program_address(&[b"vault", user.as_ref(), &7u64.to_le_bytes()], &program_id)
+    );
+    assert_eq!(
+        Pdas::create_vault_address(&program_id, &user, 7, bump).unwrap(),
+        vault
+    );
+
+    let (by_value, _) = Pdas::find_by_value_address(&program_id, &Config { value: 3 }, &user);
+    assert_eq!(
+        by_value,
+        Pubkey::find_program_address(&[SEED, &3u64.to_le_bytes(), user.as_ref()], &program_id).0
+    );
+
+    let program = Pubkey::new_unique();
+    let (external, bump) = Pdas::find_external_address(&program);
+    assert_eq!(external, Pubkey::find_program_address(&[SEED], &program).0);
+    assert_eq!(
+        Pdas::create_external_address(&program, bump).unwrap(),
+        external
+    );
+}
+
+mod tombstones {
+    use anchor_lang::prelude::*;
+
+    #[account(tombstone)]
+    pub struct Vault {
+        pub value: u64,
+    }
+
+    #[account(discriminator = 1, tombstone = 2)]
+    pub struct Small {}
+
+    #[account(zero_copy, tombstone)]
+    pub struct ZeroCopyVault {
+        pub value: u64,
+    }
+
+    #[derive(Accounts)]
+    pub struct CloseVault<'info> {
+        #[account(mut, close = dest, tombstone)]
+        pub vault: Account<'info, Vault>,
+        /// CHECK: test
+        #[account(mut)]
+        pub dest: UncheckedAccount<'info>,
+    }
+}
+
+#[test]
+fn test_close_tombstone() {
+    use anchor_lang::error::ErrorCode;
+    use anchor_lang::prelude::*;
+    use anchor_lang::{AccountDeserialize, Tombstone, CLOSED_ACCOUNT_DISCRIMINATOR};
+    use tombstones::{CloseVault, Small, Vault, ZeroCopyVault};
+
+    assert_eq!(Vault::TOMBSTONE, CLOSED_ACCOUNT_DISCRIMINATOR);
+    assert_eq!(Small::TOMBSTONE, &[2]);
+
+    // `resize` writes the new length before the data, like the runtime serializes accounts
+    let mut buf = [0u64; 3];
+    // SAFETY: The buffer is 24 bytes long and properly aligned.
+    let buf = unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr().cast::<u8>(), 24) };
+    buf[..8].copy_from_slice(&16u64.to_le_bytes());
+    let (_, data) = buf.split_at_mut(8);
+    data[..8].copy_from_slice(Vault::DISCRIMINATOR);
+
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let (mut vault_lamports, mut dest_lamports) = (10, 5);
+    let mut dest_data = [];
+    let infos = [
+        account_info(&keys[0], false, true, &mut vault_lamports, data),
+        account_info(&keys[1], false, true, &mut dest_lamports, &mut dest_data),
+    ];
+
+    let parsed = try_accounts::<CloseVault>(&infos, &[]).unwrap();
+    parsed.exit(&ID).unwrap();
+
+    // The account is kept owned by the program with the tombstone as its data
+    assert_eq!(infos[0].lamports(), 0);
+    assert_eq!(infos[1].lamports(), 15);
+    assert_eq!(infos[0].owner, &ID);
+    assert_eq!(&**infos[0].data.borrow(), CLOSED_ACCOUNT_DISCRIMINATOR);
+    assert_eq!(
+        Account::<Vault>::try_from(&infos[0]).err().unwrap(),
+        ErrorCode::AccountTombstoned.into()
+    );
+
+    assert_eq!(
+        Small::try_deserialize(&mut &[2][..]).err().unwrap(),
+        ErrorCode::AccountTombstoned.into()
+    );
+    assert!(Small::try_deserialize(&mut &[1][..]).is_ok());
+
+    // Zero-copy accounts report the tombstone the same way
+    assert_eq!(
+        AccountLoader::<ZeroCopyVault>::try_from(&infos[0])
+            .err()
+            .unwrap(),
+        ErrorCode::AccountTombstoned.into()
+    );
+}
+
+mod large {
+    use anchor_lang::prelude::*;
+
+    #[account]
+    pub struct Large {
+        pub data: [u8; 1024],
+    }
+
+    #[derive(Accounts)]
+    pub struct ReadLarge<'info> {
+        #[account(constraint = large.data[0] == 1)]
+        pub large: Account<'info, Large>,
+        pub optional: Option<Account<'info, Large>>,
+    }
+}
+
+#[test]
+fn test_large_account_field() {
+    use anchor_lang::error::ErrorCode;
+    use anchor_lang::prelude::*;
+    use large::{Large, ReadLarge};
+
+    let mut data = vec![0; 8 + 1024];
+    data[..8].copy_from_slice(Large::DISCRIMINATOR);
+    data[8] = 1;
+
+    let key = Pubkey::new_unique();
+    let mut lamports = 10;
+    let info = account_info(&key, false, false, &mut lamports, &mut data);
+    let infos = [info.clone(), info];
+
+    fn read<'info>(infos: &'info [AccountInfo<'info>]) -> Result<(u8, Option<u8>)> {
+        let parsed = try_accounts::<ReadLarge>(infos, &[])?;
+        Ok((parsed.large.data[0], parsed.optional.map(|o| o.data[0])))
+    }
+    assert_eq!(read(&infos).unwrap(), (1, Some(1)));
+
+    infos[0].data.borrow_mut()[8] = 2;
+    assert_eq!(read(&infos).err().unwrap(), ErrorCode::ConstraintRaw.into());
+}
+
+mod forwarding {
+    use anchor_lang::prelude::*;
+
+    pub trait Counted: AccountSerialize + AccountDeserialize + Owner + Clone {
+        fn count(&self) -> u64;
+    }
+
+    #[account]
+    pub struct Counter {
+        pub count: u64,
+    }
+
+    impl Counted for Counter {
+        fn count(&self) -> u64 {
+            self.count
+        }
+    }
+
+    #[derive(Accounts)]
+    #[instruction(count: u64)]
+    pub struct CheckCount<'info, T: Counted> {
+        #[account(constraint = counter.count() == count)]
+        pub counter: Account<'info, T>,
+    }
+
+    #[derive(Accounts)]
+    #[instruction(offset: u64, count: u64)]
+    pub struct Forward<'info> {
+        #[instruction(offset + count)]
+        pub forwarded: CheckCount<'info, Counter>,
+        // Gets the instruction data as is, i.e. checks `offset`
+        pub prefix: CheckCount<'info, Counter>,
+    }
+}
+
+#[test]
+fn test_forward_instruction_args() {
+    use anchor_lang::error::ErrorCode;
+    use anchor_lang::prelude::*;
+    use forwarding::{Counter, Forward};
+
+    let mut data = Counter::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&7u64.to_le_bytes());
+
+    let key = Pubkey::new_unique();
+    let mut lamports = 10;
+    let info = account_info(&key, false, false, &mut lamports, &mut data);
+    let infos = [info.clone(), info];
+
+    let forward = |offset: u64, count: u64| {
+        let mut ix_data = offset.to_le_bytes().to_vec();
+        ix_data.extend_from_slice(&count.to_le_bytes());
+        try_accounts::<Forward>(&infos, &ix_data).map(|_| ())
+    };
+    assert!(forward(7, 0).is_ok());
+    assert_eq!(
+        forward(7, 1).err().unwrap(),
+        ErrorCode::ConstraintRaw.into()
+    );
+    assert_eq!(
+        forward(3, 4).err().unwrap(),
+        ErrorCode::ConstraintRaw.into()
+    );
+}
+
+mod multisig {
+    use anchor_lang::prelude::*;
+
+    #[account]
+    pub struct Multisig {
+        pub owners: Vec<Pubkey>,
+        pub threshold: u64,
+    }
+
+    #[derive(Accounts)]
+    pub struct Approve<'info> {
+        #[account(signers = multisig.owners, threshold = multisig.threshold)]
+        pub multisig: Account<'info, Multisig>,
+        pub proposer: Signer<'info>,
+    }
+}
+
+#[test]
+fn test_signer_threshold() {
+    use anchor_lang::error::{ComparedValues, Error, ErrorCode};
+    use anchor_lang::prelude::*;
+    use multisig::{Approve, Multisig};
+
+    let owners = [Pubkey::new_unique(), Pubkey::new_unique()];
+    // The first owner is listed twice but only counts once.
+    let multisig = Multisig {
+        owners: vec![owners[0], owners[1], owners[0]],
+        threshold: 2,
+    };
+    let mut data = Vec::new();
+    multisig.try_serialize(&mut data).unwrap();
+    let mut uninitialized = Vec::new();
+    Multisig {
+        owners: Vec::new(),
+        threshold: 0,
+    }
+    .try_serialize(&mut uninitialized)
+    .unwrap();
+
+    let multisig_key = Pubkey::new_unique();
+    let mut lamports = [1; 4];
+    let [l1, l2, l3, l4] = &mut lamports;
+    let mut empty = [[0; 0]; 2];
+    let [d2, d3] = &mut empty;
+    let multisig_info = account_info(&multisig_key, false, false, l1, &mut data);
+    let first_owner = account_info(&owners[0], true, false, l2, d2);
+    let second_owner = account_info(&owners[1], true, false, l3, d3);
+
+    fn approve<'info>(infos: &'info [AccountInfo<'info>]) -> Result<()> {
+        try_accounts::<Approve>(infos, &[]).map(|_| ())
+    }
+
+    // The proposer is an owner and the other owner signs as a remaining account.
+    let infos = [
+        multisig_info.clone(),
+        first_owner.clone(),
+        second_owner.clone(),
+    ];
+    assert!(approve(&infos).is_ok());
+
+    // Signing twice with the same owner isn't enough.
+    let infos = [
+        multisig_info.clone(),
+        first_owner.clone(),
+        first_owner.clone(),
+    ];
+    let err = approve(&infos).err().unwrap();
+    assert_eq!(err, ErrorCode::ConstraintSignerThreshold.into());
+    let Error::AnchorError(anchor_error) = err else {
+        panic!("expected an AnchorError");
+    };
+    assert!(matches!(
+        &anchor_error.compared_values,
+        Some(ComparedValues::Values((found, required))) if found == "1" && required == "2"
+    ));
+
+    // Non-signing owners aren't counted.
+    let mut not_signing = second_owner.clone();
+    not_signing.is_signer = false;
+    let infos = [multisig_info, second_owner.clone(), not_signing];
+    assert_eq!(
+        approve(&infos).err().unwrap(),
+        ErrorCode::ConstraintSignerThreshold.into()
+    );
+
+    // A threshold of 0 is rejected even with signers.
+    let uninitialized_info = account_info(&multisig_key, false, false, l4, &mut uninitialized);
+    let infos = [uninitialized_info, first_owner, second_owner];
+    assert_eq!(
+        approve(&infos).err().unwrap(),
+        ErrorCode::ConstraintSignerThreshold.into()
+    );
+}
+
+#[test]
+fn test_accounts_len() {
+    use anchor_lang::Accounts;
+    use forwarding::{Forward, ForwardBumps};
+    use multisig::{Approve, ApproveBumps};
+
+    // Composite fields consume the accounts of their own struct.
+    assert_eq!(<Forward as Accounts<ForwardBumps>>::ACCOUNTS_LEN, Some(2));
+    // `signers` constraints look up the remaining accounts.
+    assert_eq!(<Approve as Accounts<ApproveBumps>>::ACCOUNTS_LEN, None);
+}
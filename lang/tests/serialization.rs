@@ -1,4 +1,6 @@
-use anchor_lang::{AnchorDeserialize, AnchorSerialize, Discriminator, InstructionData};
+use anchor_lang::{
+    AccountSerialize, AnchorDeserialize, AnchorSerialize, Discriminator, InstructionData,
+};
 
 #[test]
 fn test_instruction_data() {
@@ -34,3 +36,132 @@ fn test_instruction_data() {
         "the different methods produced different serialized representations"
     );
 }
+
+#[test]
+fn test_serialized_len() {
+    #[derive(AnchorSerialize)]
+    struct MyAccount {
+        bar: String,
+        baz: Vec<u16>,
+    }
+    impl Discriminator for MyAccount {
+        const DISCRIMINATOR: &'static [u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+    }
+    impl AccountSerialize for MyAccount {
+        fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> anchor_lang::Result<()> {
+            writer.write_all(MyAccount::DISCRIMINATOR)?;
+            self.serialize(writer)?;
+            Ok(())
+        }
+    }
+
+    let account = MyAccount {
+        bar: "abc".into(),
+        baz: vec![1, 2],
+    };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    assert_eq!(account.serialized_len().unwrap(), data.len());
+    assert_eq!(data.len(), 8 + (4 + 3) + (4 + 2 * 2));
+}
+
+// `#[account]` expects the program id at the crate root.
+use serializers::ID;
+
+mod serializers {
+    #[cfg(feature = "serde")]
+    use anchor_lang::{__private::serde, serializer::Bincode};
+    use anchor_lang::{error::ErrorCode, prelude::*, serializer::AccountSerializer};
+
+    declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+    /// Stores the value as big-endian bytes.
+    pub struct BigEndian;
+
+    impl AccountSerializer<Counter> for BigEndian {
+        const NAME: &'static str = "big_endian";
+
+        fn serialize<W: std::io::Write>(value: &Counter, writer: &mut W) -> Result<()> {
+            writer
+                .write_all(&value.count.to_be_bytes())
+                .map_err(|_| ErrorCode::AccountDidNotSerialize.into())
+        }
+
+        fn deserialize(buf: &mut &[u8]) -> Result<Counter> {
+            let (count, rest) = buf
+                .split_first_chunk()
+                .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+            *buf = rest;
+            Ok(Counter {
+                count: u64::from_be_bytes(*count),
+            })
+        }
+    }
+
+    #[account(serializer = BigEndian)]
+    pub struct Counter {
+        pub count: u64,
+    }
+
+    // `AnchorSerialize` is derived for the IDL of the type
+    #[cfg(feature = "serde")]
+    #[derive(Clone, AnchorSerialize, AnchorDeserialize, serde::Serialize, serde::Deserialize)]
+    #[serde(crate = "anchor_lang::__private::serde")]
+    pub enum Status {
+        Active { since: i64 },
+        Closed,
+    }
+
+    #[cfg(feature = "serde")]
+    #[account(serializer = Bincode)]
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(crate = "anchor_lang::__private::serde")]
+    pub struct Profile {
+        pub name: String,
+        pub status: Status,
+    }
+}
+
+#[test]
+fn test_custom_serializer() {
+    use anchor_lang::AccountDeserialize;
+    use serializers::Counter;
+
+    let counter = Counter { count: 258 };
+    let mut data = vec![];
+    counter.try_serialize(&mut data).unwrap();
+    assert_eq!(&data[..8], Counter::DISCRIMINATOR);
+    assert_eq!(&data[8..], &[0, 0, 0, 0, 0, 0, 1, 2]);
+    assert_eq!(counter.serialized_len().unwrap(), data.len());
+
+    let deserialized = Counter::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(deserialized.count, 258);
+
+    data[0] = data[0].wrapping_add(1);
+    assert!(Counter::try_deserialize(&mut &data[..]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_bincode_serializer() {
+    use anchor_lang::AccountDeserialize;
+    use serializers::{Profile, Status};
+
+    let profile = Profile {
+        name: "abc".into(),
+        status: Status::Active { since: 7 },
+    };
+    let mut data = vec![];
+    profile.try_serialize(&mut data).unwrap();
+    assert_eq!(&data[..8], Profile::DISCRIMINATOR);
+    // Bincode encodes string lengths as `u64` and enum variants as `u32`
+    assert_eq!(data.len(), 8 + (8 + 3) + (4 + 8));
+    assert_eq!(profile.serialized_len().unwrap(), data.len());
+
+    let deserialized = Profile::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(deserialized.name, "abc");
+    assert!(matches!(deserialized.status, Status::Active { since: 7 }));
+
+    // Truncated data
+    assert!(Profile::try_deserialize(&mut &data[..data.len() - 1]).is_err());
+}
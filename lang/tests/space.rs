@@ -1,3 +1,6 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::marker::PhantomData;
+
 use anchor_lang::prelude::*;
 
 // Needed to declare accounts.
@@ -141,6 +144,50 @@ pub struct TestTupleStruct {
     pub ultimate_complex: (u8, (bool, Option<(u16, u32)>, ChildStruct), Pubkey),
 }
 
+#[derive(InitSpace)]
+pub enum TestDataEnum {
+    Empty,
+    Amount(u64),
+    Named {
+        key: Pubkey,
+        #[max_len(4)]
+        label: String,
+    },
+}
+
+#[derive(InitSpace)]
+pub struct TestCollections {
+    #[max_len(5)]
+    pub map: BTreeMap<Pubkey, u64>,
+    #[max_len(3, 10, 2)]
+    pub nested_map: HashMap<String, Vec<u16>>,
+    #[max_len(4)]
+    pub set: BTreeSet<u32>,
+    #[max_len(2, 8)]
+    pub string_set: HashSet<String>,
+    pub boxed: Box<u64>,
+}
+
+#[derive(InitSpace)]
+pub struct TestGeneric<T, U: Space> {
+    pub value: T,
+    #[max_len(3)]
+    pub values: Vec<U>,
+    pub marker: PhantomData<T>,
+}
+
+#[derive(InitSpace)]
+pub enum TestGenericEnum<T> {
+    None,
+    Some(T),
+}
+
+#[derive(InitSpace)]
+pub struct TestNestedGenerics {
+    pub pair: TestGeneric<u64, TestBasicEnum>,
+    pub option: TestGenericEnum<TestGeneric<u8, u16>>,
+}
+
 #[test]
 fn test_empty_struct() {
     assert_eq!(TestEmptyAccount::INIT_SPACE, 0);
@@ -281,3 +328,30 @@ fn test_tuple_with_arrays() {
 
     assert!(TestTupleStruct::INIT_SPACE >= array_tuple_size + tuple_array_nested_size);
 }
+
+#[test]
+fn test_data_enum() {
+    assert_eq!(TestDataEnum::INIT_SPACE, 1 + 32 + (4 + 4));
+}
+
+#[test]
+fn test_collections() {
+    assert_eq!(
+        TestCollections::INIT_SPACE,
+        (4 + (32 + 8) * 5)
+            + (4 + ((4 + 10) + (4 + 2 * 2)) * 3)
+            + (4 + 4 * 4)
+            + (4 + (4 + 8) * 2)
+            + 8
+    );
+}
+
+#[test]
+fn test_generics() {
+    assert_eq!(TestGeneric::<u32, u8>::INIT_SPACE, 4 + (4 + 3));
+    assert_eq!(TestGenericEnum::<u64>::INIT_SPACE, 1 + 8);
+    assert_eq!(
+        TestNestedGenerics::INIT_SPACE,
+        (8 + (4 + 3 * TestBasicEnum::INIT_SPACE)) + (1 + (1 + (4 + 3 * 2)))
+    );
+}
@@ -59,6 +59,40 @@ pub fn transfer_checked<'info>(
     .map_err(Into::into)
 }
 
+/// Same as [`transfer_checked`], but also supports mints with the transfer hook extension.
+///
+/// If the mint has a transfer hook, the extra accounts required by the hook program are resolved
+/// from its `ExtraAccountMetaList` validation account, including the ones derived from seeds, and
+/// appended to the instruction. The hook program, the validation account and all accounts it
+/// references must be passed in [`CpiContext::remaining_accounts`], e.g. by forwarding the
+/// remaining accounts of the instruction:
+///
+/// ```ignore
+/// let cpi_ctx = CpiContext::new(token_program, accounts)
+///     .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+/// transfer_checked_with_hook(cpi_ctx, amount, decimals)?;
+/// ```
+///
+/// Multisig signers of the authority should also be passed in the remaining accounts.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
 pub fn mint_to<'info>(
     ctx: CpiContext<'_, '_, '_, 'info, MintTo<'info>>,
     amount: u64,
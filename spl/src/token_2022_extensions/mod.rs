@@ -33,4 +33,6 @@ pub use transfer_fee::*;
 pub use transfer_hook::*;
 
 pub use spl_pod;
+pub use spl_tlv_account_resolution;
 pub use spl_token_metadata_interface;
+pub use spl_transfer_hook_interface;
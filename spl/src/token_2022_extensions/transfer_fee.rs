@@ -95,6 +95,30 @@ pub fn transfer_checked_with_fee<'info>(
     .map_err(Into::into)
 }
 
+/// Same as [`transfer_checked_with_fee`], but also supports mints with the transfer hook
+/// extension. See [`crate::token_2022::transfer_checked_with_hook`] for the accounts that need to
+/// be passed in [`CpiContext::remaining_accounts`].
+pub fn transfer_checked_with_fee_and_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferCheckedWithFee<'info>>,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked_with_fee(
+        ctx.accounts.token_program_id.key,
+        ctx.accounts.source,
+        ctx.accounts.mint,
+        ctx.accounts.destination,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        fee,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
 #[derive(Accounts)]
 pub struct TransferCheckedWithFee<'info> {
     pub token_program_id: AccountInfo<'info>,
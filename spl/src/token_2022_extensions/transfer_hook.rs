@@ -1,7 +1,24 @@
+use anchor_lang::error::ErrorCode;
 use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::program_error::ProgramError;
 use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::sysvar::Sysvar;
+use anchor_lang::system_program::{allocate, assign, Allocate, Assign};
 use anchor_lang::Result;
-use anchor_lang::{context::CpiContext, Accounts};
+use anchor_lang::{context::CpiContext, Accounts, Key};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_token_2022::extension::{
+    transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+};
+use spl_transfer_hook_interface::{error::TransferHookError, instruction::ExecuteInstruction};
+
+#[cfg(feature = "token_2022")]
+use crate::token_interface::{Mint, MintExtensions, TokenAccount, TokenInterface};
+#[cfg(feature = "token_2022")]
+use anchor_lang::prelude::{Interface, InterfaceAccount, ToAccountInfo, UncheckedAccount};
+#[cfg(all(feature = "token_2022", feature = "idl-build"))]
+use anchor_lang::{Discriminator, IdlBuild};
 
 pub fn transfer_hook_initialize<'info>(
     ctx: CpiContext<'_, '_, '_, 'info, TransferHookInitialize<'info>>,
@@ -57,3 +74,229 @@ pub struct TransferHookUpdate<'info> {
     pub mint: AccountInfo<'info>,
     pub authority: AccountInfo<'info>,
 }
+
+/// Returns the address of the `ExtraAccountMetaList` validation account of `mint` for the given
+/// transfer hook program.
+pub fn get_extra_account_metas_address(mint: &Pubkey, transfer_hook_program_id: &Pubkey) -> Pubkey {
+    spl_transfer_hook_interface::get_extra_account_metas_address(mint, transfer_hook_program_id)
+}
+
+/// Accounts of a `transfer_checked` that supports mints with the transfer hook extension, to be
+/// used as a composite field of an accounts struct:
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// pub struct Pay<'info> {
+///     pub transfer: TransferCheckedWithHook<'info>,
+///     pub payer: Signer<'info>,
+/// }
+///
+/// pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+///     ctx.accounts
+///         .transfer
+///         .transfer_checked(amount, ctx.remaining_accounts, &[])
+/// }
+/// ```
+///
+/// The extra accounts of the transfer hook are resolved from the `ExtraAccountMetaList`
+/// validation account of the mint, which must be given along with the transfer hook program and
+/// the accounts it references in `extra_accounts`. The transfer hook program is looked up from the
+/// mint, so other accounts can't be passed off as it.
+#[cfg(feature = "token_2022")]
+#[derive(Accounts)]
+pub struct TransferCheckedWithHook<'info> {
+    #[account(mut, constraint = from.mint == mint.key() @ ErrorCode::ConstraintTokenMint)]
+    pub from: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = to.mint == mint.key() @ ErrorCode::ConstraintTokenMint)]
+    pub to: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner or delegate of `from`, checked by the token program.
+    pub authority: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[cfg(feature = "token_2022")]
+impl<'info> TransferCheckedWithHook<'info> {
+    /// Returns the transfer hook program of the mint and the address of its `ExtraAccountMetaList`
+    /// validation account, or `None` if the mint doesn't have a transfer hook.
+    pub fn transfer_hook_accounts(&self) -> Result<Option<(Pubkey, Pubkey)>> {
+        Ok(self.mint.transfer_hook_program_id()?.map(|program_id| {
+            (
+                program_id,
+                get_extra_account_metas_address(&self.mint.key(), &program_id),
+            )
+        }))
+    }
+
+    /// Transfers `amount` tokens with [`transfer_checked_with_hook`].
+    ///
+    /// If the mint has a transfer hook, `extra_accounts` must contain the transfer hook program,
+    /// its validation account and the extra accounts it references, e.g. by forwarding the
+    /// remaining accounts of the instruction.
+    ///
+    /// [`transfer_checked_with_hook`]: crate::token_2022::transfer_checked_with_hook
+    pub fn transfer_checked(
+        &self,
+        amount: u64,
+        extra_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        if let Some((program_id, extra_account_meta_list)) = self.transfer_hook_accounts()? {
+            for (name, expected) in [
+                ("transfer_hook_program", program_id),
+                ("extra_account_meta_list", extra_account_meta_list),
+            ] {
+                if !extra_accounts.iter().any(|info| *info.key == expected) {
+                    return Err(
+                        anchor_lang::error::Error::from(ErrorCode::AccountNotEnoughKeys)
+                            .with_account_name(name),
+                    );
+                }
+            }
+        }
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            crate::token_2022::TransferChecked {
+                from: self.from.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.to.to_account_info(),
+                authority: self.authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(extra_accounts.to_vec());
+        crate::token_2022::transfer_checked_with_hook(ctx, amount, self.mint.decimals)
+    }
+}
+
+/// Accounts of a transfer hook `Execute` instruction.
+///
+/// This trait is implemented by `#[derive(Accounts)]` for structs with the `#[transfer_hook]`
+/// attribute, where the first 5 fields are the accounts required by the transfer hook interface:
+///
+/// 0. Source token account
+/// 1. Mint
+/// 2. Destination token account
+/// 3. Source token account authority
+/// 4. `ExtraAccountMetaList` validation account
+///
+/// All remaining fields are the extra accounts, resolved from their `seeds` or `address`
+/// constraints.
+pub trait TransferHookAccounts {
+    /// Returns the extra accounts required by the `Execute` instruction, in order.
+    fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>>;
+}
+
+/// Checks the interface accounts of a transfer hook `Execute` instruction.
+///
+/// The validation account must be the `ExtraAccountMetaList` PDA of the mint, and both token
+/// accounts must be in the middle of a transfer, i.e. the instruction must be invoked by the token
+/// program during `transfer_checked`.
+pub fn check_transfer_hook_execute_accounts(
+    program_id: &Pubkey,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    extra_account_meta_list: &AccountInfo,
+) -> Result<()> {
+    let expected_address = get_extra_account_metas_address(mint.key, program_id);
+    if extra_account_meta_list.key() != expected_address {
+        return Err(anchor_lang::error::Error::from(ErrorCode::ConstraintSeeds)
+            .with_account_name("extra_account_meta_list")
+            .with_pubkeys((extra_account_meta_list.key(), expected_address)));
+    }
+
+    check_is_transferring(source)?;
+    check_is_transferring(destination)
+}
+
+fn check_is_transferring(token_account: &AccountInfo) -> Result<()> {
+    let data = token_account.try_borrow_data()?;
+    let token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    let extension = token_account.get_extension::<TransferHookAccount>()?;
+    if bool::from(extension.transferring) {
+        Ok(())
+    } else {
+        Err(ProgramError::from(TransferHookError::ProgramCalledOutsideOfTransfer).into())
+    }
+}
+
+/// Creates the `ExtraAccountMetaList` validation account of `mint` and writes `extra_account_metas`
+/// to it.
+///
+/// The validation account is allocated and assigned to the program, so it must already hold
+/// enough lamports to be rent exempt, as required by the transfer hook interface.
+pub fn initialize_extra_account_meta_list<'info>(
+    program_id: &Pubkey,
+    extra_account_meta_list: AccountInfo<'info>,
+    mint: &Pubkey,
+    system_program: AccountInfo<'info>,
+    bump: u8,
+    extra_account_metas: &[ExtraAccountMeta],
+) -> Result<()> {
+    let space = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+    check_rent_exempt(&extra_account_meta_list, space)?;
+
+    let signer_seeds: &[&[u8]] = &[b"extra-account-metas", mint.as_ref(), &[bump]];
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: extra_account_meta_list.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program,
+            Assign {
+                account_to_assign: extra_account_meta_list.clone(),
+            },
+            &[signer_seeds],
+        ),
+        program_id,
+    )?;
+
+    let mut data = extra_account_meta_list.try_borrow_mut_data()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, extra_account_metas)?;
+    Ok(())
+}
+
+/// Overwrites the extra accounts stored in an existing `ExtraAccountMetaList` validation account,
+/// resizing it if necessary.
+///
+/// If the account grows, it must already hold enough lamports to stay rent exempt.
+pub fn update_extra_account_meta_list(
+    extra_account_meta_list: AccountInfo,
+    extra_account_metas: &[ExtraAccountMeta],
+) -> Result<()> {
+    let space = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+    let current_space = extra_account_meta_list.data_len();
+    if space > current_space {
+        check_rent_exempt(&extra_account_meta_list, space)?;
+        extra_account_meta_list.resize(space)?;
+    }
+
+    {
+        let mut data = extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::update::<ExecuteInstruction>(&mut data, extra_account_metas)?;
+    }
+
+    if space < current_space {
+        extra_account_meta_list.resize(space)?;
+    }
+    Ok(())
+}
+
+fn check_rent_exempt(account: &AccountInfo, space: usize) -> Result<()> {
+    if account.lamports() < Rent::get()?.minimum_balance(space) {
+        return Err(
+            anchor_lang::error::Error::from(ErrorCode::ConstraintRentExempt)
+                .with_account_name("extra_account_meta_list"),
+        );
+    }
+    Ok(())
+}
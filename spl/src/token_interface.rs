@@ -1,8 +1,15 @@
 use anchor_lang::__private::bytemuck::Pod;
+use anchor_lang::prelude::InterfaceAccount;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::program_error::ProgramError;
 use anchor_lang::solana_program::program_pack::Pack;
 use anchor_lang::solana_program::pubkey::Pubkey;
+use spl_token_2022::error::TokenError;
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
 use spl_token_2022::extension::ExtensionType;
-use spl_token_2022::extension::{BaseStateWithExtensions, Extension, StateWithExtensions};
+use spl_token_2022::extension::{
+    BaseState, BaseStateWithExtensions, Extension, StateWithExtensions,
+};
 use std::ops::Deref;
 
 pub use crate::token_2022::*;
@@ -67,6 +74,143 @@ impl Deref for Mint {
     }
 }
 
+/// Unpacks the extensions of `info` only for the duration of `f`, so that the account data isn't
+/// copied when deserializing the account.
+fn with_extensions<S: BaseState + Pack, R>(
+    info: &AccountInfo,
+    f: impl FnOnce(&StateWithExtensions<S>) -> Result<R, ProgramError>,
+) -> anchor_lang::Result<R> {
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<S>::unpack(&data)?;
+    f(&state).map_err(Into::into)
+}
+
+/// Returns the transfer fee config of the mint, or `None` if the mint doesn't have the extension.
+fn transfer_fee_config(
+    state: &StateWithExtensions<spl_token_2022::state::Mint>,
+) -> Result<Option<TransferFeeConfig>, ProgramError> {
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(Some(*config)),
+        Err(e) if e == TokenError::ExtensionNotFound.into() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Extensions of a [`TokenAccount`], read from the account data when they're accessed, e.g.
+/// `account.extension::<MemoTransfer>()`.
+pub trait TokenAccountExtensions {
+    /// Returns the types of all extensions initialized on the account.
+    fn extensions(&self) -> anchor_lang::Result<Vec<ExtensionType>>;
+
+    /// Returns the fixed-size extension `T`.
+    fn extension<T: Extension + Pod>(&self) -> anchor_lang::Result<T>;
+}
+
+impl TokenAccountExtensions for InterfaceAccount<'_, TokenAccount> {
+    fn extensions(&self) -> anchor_lang::Result<Vec<ExtensionType>> {
+        with_extensions::<spl_token_2022::state::Account, _>(self.as_ref(), |state| {
+            state.get_extension_types()
+        })
+    }
+
+    fn extension<T: Extension + Pod>(&self) -> anchor_lang::Result<T> {
+        with_extensions::<spl_token_2022::state::Account, _>(self.as_ref(), |state| {
+            state.get_extension::<T>().copied()
+        })
+    }
+}
+
+/// Extensions of a [`Mint`], read from the account data when they're accessed, e.g.
+/// `mint.extension::<TransferFeeConfig>()`.
+pub trait MintExtensions {
+    /// Returns the types of all extensions initialized on the mint.
+    fn extensions(&self) -> anchor_lang::Result<Vec<ExtensionType>>;
+
+    /// Returns the fixed-size extension `T`.
+    fn extension<T: Extension + Pod>(&self) -> anchor_lang::Result<T>;
+
+    /// Returns the token metadata stored in the mint itself.
+    #[cfg(feature = "token_2022_extensions")]
+    fn token_metadata(
+        &self,
+    ) -> anchor_lang::Result<spl_token_metadata_interface::state::TokenMetadata>;
+
+    /// Returns the transfer hook program of the mint, if any.
+    fn transfer_hook_program_id(&self) -> anchor_lang::Result<Option<Pubkey>>;
+
+    /// Returns the fee withheld when transferring `amount` tokens in `epoch`.
+    ///
+    /// Mints without the transfer fee extension never charge a fee.
+    fn transfer_fee(&self, epoch: u64, amount: u64) -> anchor_lang::Result<u64>;
+
+    /// Returns the amount received by the destination when transferring `amount` tokens in
+    /// `epoch`, i.e. `amount` minus the transfer fee.
+    fn amount_after_transfer_fee(&self, epoch: u64, amount: u64) -> anchor_lang::Result<u64> {
+        let fee = self.transfer_fee(epoch, amount)?;
+        amount
+            .checked_sub(fee)
+            .ok_or_else(|| ProgramError::ArithmeticOverflow.into())
+    }
+
+    /// Returns the amount that needs to be transferred in `epoch` for the destination to receive
+    /// exactly `net_amount` tokens.
+    fn amount_before_transfer_fee(&self, epoch: u64, net_amount: u64) -> anchor_lang::Result<u64>;
+}
+
+impl MintExtensions for InterfaceAccount<'_, Mint> {
+    fn extensions(&self) -> anchor_lang::Result<Vec<ExtensionType>> {
+        with_extensions::<spl_token_2022::state::Mint, _>(self.as_ref(), |state| {
+            state.get_extension_types()
+        })
+    }
+
+    fn extension<T: Extension + Pod>(&self) -> anchor_lang::Result<T> {
+        with_extensions::<spl_token_2022::state::Mint, _>(self.as_ref(), |state| {
+            state.get_extension::<T>().copied()
+        })
+    }
+
+    #[cfg(feature = "token_2022_extensions")]
+    fn token_metadata(
+        &self,
+    ) -> anchor_lang::Result<spl_token_metadata_interface::state::TokenMetadata> {
+        with_extensions::<spl_token_2022::state::Mint, _>(self.as_ref(), |state| {
+            state.get_variable_len_extension()
+        })
+    }
+
+    fn transfer_hook_program_id(&self) -> anchor_lang::Result<Option<Pubkey>> {
+        with_extensions::<spl_token_2022::state::Mint, _>(self.as_ref(), |state| {
+            Ok(spl_token_2022::extension::transfer_hook::get_program_id(
+                state,
+            ))
+        })
+    }
+
+    fn transfer_fee(&self, epoch: u64, amount: u64) -> anchor_lang::Result<u64> {
+        with_extensions::<spl_token_2022::state::Mint, _>(self.as_ref(), |state| {
+            match transfer_fee_config(state)? {
+                Some(config) => config
+                    .calculate_epoch_fee(epoch, amount)
+                    .ok_or(ProgramError::ArithmeticOverflow),
+                None => Ok(0),
+            }
+        })
+    }
+
+    fn amount_before_transfer_fee(&self, epoch: u64, net_amount: u64) -> anchor_lang::Result<u64> {
+        with_extensions::<spl_token_2022::state::Mint, _>(self.as_ref(), |state| {
+            match transfer_fee_config(state)? {
+                Some(config) => config
+                    .calculate_inverse_epoch_fee(epoch, net_amount)
+                    .and_then(|fee| net_amount.checked_add(fee))
+                    .ok_or(ProgramError::ArithmeticOverflow),
+                None => Ok(net_amount),
+            }
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct TokenInterface;
 
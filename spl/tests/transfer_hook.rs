@@ -0,0 +1,157 @@
+#![cfg(all(feature = "token_2022", feature = "token_2022_extensions"))]
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Epoch;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_hook::TransferHook, BaseStateWithExtensionsMut, ExtensionType,
+        StateWithExtensionsMut,
+    },
+    state::{Account as SplTokenAccount, AccountState, Mint as SplMint},
+};
+use anchor_spl::token_interface::{
+    get_extra_account_metas_address, spl_pod::optional_keys::OptionalNonZeroPubkey,
+    TransferCheckedWithHook, TransferCheckedWithHookBumps,
+};
+
+fn mint_data(transfer_hook_program_id: Option<Pubkey>) -> Vec<u8> {
+    let base = SplMint {
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    };
+    let program_id = match transfer_hook_program_id {
+        Some(program_id) => program_id,
+        None => {
+            let mut data = vec![0; SplMint::LEN];
+            SplMint::pack(base, &mut data).unwrap();
+            return data;
+        }
+    };
+
+    let len = ExtensionType::try_calculate_account_len::<SplMint>(&[ExtensionType::TransferHook])
+        .unwrap();
+    let mut data = vec![0; len];
+    let mut state = StateWithExtensionsMut::<SplMint>::unpack_uninitialized(&mut data).unwrap();
+    let extension = state.init_extension::<TransferHook>(true).unwrap();
+    extension.program_id = OptionalNonZeroPubkey::try_from(Some(program_id)).unwrap();
+    state.base = base;
+    state.pack_base();
+    state.init_account_type().unwrap();
+    data
+}
+
+fn token_account_data(mint: Pubkey) -> Vec<u8> {
+    let mut data = vec![0; SplTokenAccount::LEN];
+    let account = SplTokenAccount {
+        mint,
+        owner: Pubkey::new_unique(),
+        amount: 100,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        ..Default::default()
+    };
+    SplTokenAccount::pack(account, &mut data).unwrap();
+    data
+}
+
+fn try_accounts<'info>(
+    infos: &'info [AccountInfo<'info>],
+) -> Result<TransferCheckedWithHook<'info>> {
+    let mut accounts = infos;
+    TransferCheckedWithHook::try_accounts(
+        &spl_token_2022::ID,
+        &mut accounts,
+        &[],
+        &mut TransferCheckedWithHookBumps::default(),
+        &mut Default::default(),
+    )
+}
+
+#[test]
+fn test_transfer_checked_with_hook_accounts() {
+    let token_program = spl_token_2022::ID;
+    let hook_program = Pubkey::new_unique();
+    let keys = [(); 4].map(|_| Pubkey::new_unique());
+    let [mint_key, from_key, to_key, authority_key] = &keys;
+    let other_mint = Pubkey::new_unique();
+
+    let mut hooked_mint = mint_data(Some(hook_program));
+    let mut plain_mint = mint_data(None);
+    let mut from = token_account_data(*mint_key);
+    let mut to = token_account_data(*mint_key);
+    let mut other = token_account_data(other_mint);
+    let mut lamports = [1; 7];
+    let [l1, l2, l3, l4, l5, l6, l7] = &mut lamports;
+    let mut empty = [[0u8; 0]; 2];
+    let [d1, d2] = &mut empty;
+
+    let info = |key, writable, executable, owner, lamports, data| {
+        AccountInfo::new(
+            key,
+            false,
+            writable,
+            lamports,
+            data,
+            owner,
+            executable,
+            Epoch::default(),
+        )
+    };
+    let hooked_mint = info(mint_key, false, false, &token_program, l1, &mut hooked_mint);
+    let plain_mint = info(mint_key, false, false, &token_program, l2, &mut plain_mint);
+    let from = info(from_key, true, false, &token_program, l3, &mut from);
+    let to = info(to_key, true, false, &token_program, l4, &mut to);
+    let other = info(to_key, true, false, &token_program, l5, &mut other);
+    let authority = info(authority_key, false, false, &token_program, l6, d1);
+    let program = info(&token_program, false, true, &token_program, l7, d2);
+
+    // Mint with a transfer hook.
+    let infos = [
+        from.clone(),
+        hooked_mint.clone(),
+        to.clone(),
+        authority.clone(),
+        program.clone(),
+    ];
+    let accounts = try_accounts(&infos).unwrap();
+    assert_eq!(
+        accounts.transfer_hook_accounts().unwrap(),
+        Some((
+            hook_program,
+            get_extra_account_metas_address(mint_key, &hook_program)
+        ))
+    );
+    // The transfer hook program and its validation account must be given.
+    let err = accounts.transfer_checked(1, &[], &[]).err().unwrap();
+    assert_eq!(err, ErrorCode::AccountNotEnoughKeys.into());
+    let anchor_lang::error::Error::AnchorError(anchor_error) = err else {
+        panic!("expected an AnchorError");
+    };
+    assert!(matches!(
+        &anchor_error.error_origin,
+        Some(anchor_lang::error::ErrorOrigin::AccountName(name)) if name == "transfer_hook_program"
+    ));
+
+    // Mint without a transfer hook.
+    let infos = [
+        from.clone(),
+        plain_mint,
+        to,
+        authority.clone(),
+        program.clone(),
+    ];
+    let accounts = try_accounts(&infos).unwrap();
+    assert_eq!(accounts.transfer_hook_accounts().unwrap(), None);
+
+    // Token accounts of another mint are rejected.
+    let infos = [from, hooked_mint, other, authority, program];
+    assert_eq!(
+        try_accounts(&infos).err().unwrap(),
+        ErrorCode::ConstraintTokenMint.into()
+    );
+}
@@ -0,0 +1,371 @@
+#![cfg(all(feature = "token_2022", feature = "token_2022_extensions"))]
+// The program codegen checks the features of the program crate, and the generated IDL
+// instructions use `AccountInfo::realloc`.
+#![allow(unexpected_cfgs, deprecated)]
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Epoch;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        transfer_hook::TransferHookAccount, BaseStateWithExtensionsMut, ExtensionType,
+        StateWithExtensionsMut,
+    },
+    state::{Account as SplTokenAccount, AccountState, Mint as SplMint},
+};
+use anchor_spl::token_interface::{
+    get_extra_account_metas_address,
+    spl_tlv_account_resolution::{
+        account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+    },
+    spl_transfer_hook_interface::{error::TransferHookError, instruction::ExecuteInstruction},
+    Mint, Token2022, TokenAccount, TransferHookAccounts,
+};
+use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+declare_id!("9vaEfNU4HquQJuNQ6HYrpJW518a3n4wNUt5mAMY2UUHW");
+
+#[program]
+pub mod hook {
+    use super::*;
+
+    #[instruction(transfer_hook)]
+    pub fn execute(ctx: Context<Execute>, amount: u64) -> Result<()> {
+        ctx.accounts.counter.count += amount;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+#[transfer_hook]
+#[instruction(amount: u64)]
+pub struct Execute<'info> {
+    pub source: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Can be any account
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Validated against the mint
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"counter", mint.key().as_ref()], bump)]
+    pub counter: Account<'info, Counter>,
+    /// CHECK: Derived from the instruction data
+    #[account(seeds = [b"amount", amount.to_le_bytes().as_ref(), authority.key().as_ref()], bump)]
+    pub by_amount: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token2022>,
+    /// CHECK: Fixed address
+    #[account(address = anchor_spl::associated_token::ID)]
+    pub associated_token_program: UncheckedAccount<'info>,
+    /// CHECK: PDA of another program
+    #[account(seeds = [b"external"], seeds::program = token_program, bump)]
+    pub external: UncheckedAccount<'info>,
+}
+
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [43, 34, 13, 49, 167, 88, 235, 235];
+const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+
+struct RentStubs;
+
+impl SyscallStubs for RentStubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+}
+
+fn mint_data(authority: Pubkey) -> Vec<u8> {
+    let mut data = vec![0; SplMint::LEN];
+    let mint = SplMint {
+        mint_authority: COption::Some(authority),
+        decimals: 6,
+        is_initialized: true,
+        ..Default::default()
+    };
+    SplMint::pack(mint, &mut data).unwrap();
+    data
+}
+
+fn token_account_data(mint: Pubkey, transferring: bool) -> Vec<u8> {
+    let len = ExtensionType::try_calculate_account_len::<SplTokenAccount>(&[
+        ExtensionType::TransferHookAccount,
+    ])
+    .unwrap();
+    let mut data = vec![0; len];
+    let mut state =
+        StateWithExtensionsMut::<SplTokenAccount>::unpack_uninitialized(&mut data).unwrap();
+    let extension = state.init_extension::<TransferHookAccount>(true).unwrap();
+    extension.transferring = transferring.into();
+    state.base = SplTokenAccount {
+        mint,
+        owner: Pubkey::new_unique(),
+        amount: 100,
+        state: AccountState::Initialized,
+        ..Default::default()
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    data
+}
+
+fn info<'a>(
+    key: &'a Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    lamports: &'a mut u64,
+    data: &'a mut [u8],
+    owner: &'a Pubkey,
+    executable: bool,
+) -> AccountInfo<'a> {
+    AccountInfo::new(
+        key,
+        is_signer,
+        is_writable,
+        lamports,
+        data,
+        owner,
+        executable,
+        Epoch::default(),
+    )
+}
+
+#[test]
+fn test_extra_account_metas() {
+    let seed = |bytes: &[u8]| Seed::Literal {
+        bytes: bytes.to_vec(),
+    };
+    let expected = vec![
+        ExtraAccountMeta::new_with_seeds(
+            &[seed(b"counter"), Seed::AccountKey { index: 1 }],
+            false,
+            true,
+        )
+        .unwrap(),
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                seed(b"amount"),
+                Seed::InstructionData {
+                    index: 8,
+                    length: 8,
+                },
+                Seed::AccountKey { index: 3 },
+            ],
+            false,
+            false,
+        )
+        .unwrap(),
+        ExtraAccountMeta::new_with_pubkey(&Token2022::id(), false, false).unwrap(),
+        ExtraAccountMeta::new_with_pubkey(&AssociatedToken::id(), false, false).unwrap(),
+        ExtraAccountMeta::new_external_pda_with_seeds(7, &[seed(b"external")], false, false)
+            .unwrap(),
+    ];
+    assert_eq!(Execute::extra_account_metas().unwrap(), expected);
+}
+
+#[test]
+fn test_initialize_and_execute() {
+    set_syscall_stubs(Box::new(RentStubs));
+
+    let amount = 5u64;
+    let keys = [(); 5].map(|_| Pubkey::new_unique());
+    let [mint_key, source_key, destination_key, authority_key, wrong_key] = &keys;
+    let validation_key = get_extra_account_metas_address(mint_key, &ID);
+    let counter_key = Pubkey::find_program_address(&[b"counter", mint_key.as_ref()], &ID).0;
+    let by_amount_key = Pubkey::find_program_address(
+        &[b"amount", &amount.to_le_bytes(), authority_key.as_ref()],
+        &ID,
+    )
+    .0;
+    let external_key = Pubkey::find_program_address(&[b"external"], &Token2022::id()).0;
+    let (token_program, system_program) = (Token2022::id(), system_program::ID);
+    let ata_program = AssociatedToken::id();
+
+    let space = ExtraAccountMetaList::size_of(5).unwrap();
+    let mut lamports = [Rent::default().minimum_balance(space); 14];
+    let [l0, l1, l2, l3, l4, l5, l6, l7, l8, l9, l10, l11, l12, l13] = &mut lamports;
+    let mut validation = vec![0; space];
+    let mut mint = mint_data(*authority_key);
+    let mut source = token_account_data(*mint_key, true);
+    let mut destination = token_account_data(*mint_key, true);
+    let mut idle = token_account_data(*mint_key, false);
+    let mut counter = Counter::DISCRIMINATOR.to_vec();
+    counter.extend(0u64.to_le_bytes());
+    let mut empty = [[0u8; 0]; 8];
+    let [d0, d1, d2, d3, d4, d5, d6, d7] = &mut empty;
+
+    // Off-chain, the system program CPIs are no-ops, so the validation account is created with
+    // its final size.
+    let validation = info(
+        &validation_key,
+        false,
+        true,
+        l0,
+        &mut validation,
+        &system_program,
+        false,
+    );
+    let mint = info(mint_key, false, false, l1, &mut mint, &token_program, false);
+    let authority = info(authority_key, true, false, l2, d0, &system_program, false);
+    let system = info(&system_program, false, false, l3, d1, &system_program, true);
+    let infos = [validation.clone(), mint.clone(), authority.clone(), system];
+    entry(&ID, &infos, &INITIALIZE_DISCRIMINATOR).unwrap();
+
+    let source = info(
+        source_key,
+        false,
+        false,
+        l4,
+        &mut source,
+        &token_program,
+        false,
+    );
+    let destination = info(
+        destination_key,
+        false,
+        false,
+        l5,
+        &mut destination,
+        &token_program,
+        false,
+    );
+    let idle = info(
+        destination_key,
+        false,
+        false,
+        l6,
+        &mut idle,
+        &token_program,
+        false,
+    );
+    let authority = info(authority_key, false, false, l7, d2, &system_program, false);
+    let counter = info(&counter_key, false, true, l8, &mut counter, &ID, false);
+    let by_amount = info(&by_amount_key, false, false, l9, d3, &system_program, false);
+    let token = info(&token_program, false, false, l10, d4, &token_program, true);
+    let ata = info(&ata_program, false, false, l11, d5, &ata_program, false);
+    let external = info(&external_key, false, false, l12, d6, &token_program, false);
+    let wrong_validation = info(wrong_key, false, false, l13, d7, &ID, false);
+
+    let mut data = EXECUTE_DISCRIMINATOR.to_vec();
+    data.extend(amount.to_le_bytes());
+    let infos = [
+        source.clone(),
+        mint.clone(),
+        destination.clone(),
+        authority.clone(),
+        validation.clone(),
+        counter.clone(),
+        by_amount,
+        token,
+        ata,
+        external,
+    ];
+
+    // The token program resolves the extra accounts from the validation account.
+    ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+        &infos,
+        &data,
+        &ID,
+        &validation.try_borrow_data().unwrap(),
+    )
+    .unwrap();
+
+    entry(&ID, &infos, &data).unwrap();
+    assert_eq!(
+        Counter::try_deserialize(&mut &counter.try_borrow_data().unwrap()[..])
+            .unwrap()
+            .count,
+        amount
+    );
+
+    // The validation account must be the PDA of the mint.
+    let mut wrong_infos = infos.clone();
+    wrong_infos[4] = wrong_validation;
+    assert_eq!(
+        entry(&ID, &wrong_infos, &data).unwrap_err(),
+        ProgramError::from(Error::from(ErrorCode::ConstraintSeeds))
+    );
+
+    // The token accounts must be in the middle of a transfer.
+    let mut idle_infos = infos.clone();
+    idle_infos[2] = idle;
+    assert_eq!(
+        entry(&ID, &idle_infos, &data).unwrap_err(),
+        ProgramError::from(TransferHookError::ProgramCalledOutsideOfTransfer)
+    );
+}
+
+#[cfg(feature = "idl-build")]
+#[test]
+fn test_idl_transfer_hook() {
+    use anchor_lang::idl::{types::*, IdlBuildAccounts};
+
+    let account = |name: &str, writable, address: Option<Pubkey>, pda| IdlExtraAccount {
+        name: name.into(),
+        writable,
+        signer: false,
+        address: address.map(|address| address.to_string()),
+        pda,
+    };
+    let pda = |seeds, program| Some(IdlExtraAccountPda { seeds, program });
+    let literal = |value: &[u8]| IdlExtraAccountSeed::Const {
+        value: value.to_vec(),
+    };
+    assert_eq!(
+        Execute::create_transfer_hook(),
+        Some(IdlTransferHook {
+            accounts: vec![
+                account(
+                    "counter",
+                    true,
+                    None,
+                    pda(
+                        vec![
+                            literal(b"counter"),
+                            IdlExtraAccountSeed::Account { index: 1 }
+                        ],
+                        None,
+                    ),
+                ),
+                account(
+                    "by_amount",
+                    false,
+                    None,
+                    pda(
+                        vec![
+                            literal(b"amount"),
+                            IdlExtraAccountSeed::Data {
+                                index: 8,
+                                length: 8,
+                            },
+                            IdlExtraAccountSeed::Account { index: 3 },
+                        ],
+                        None,
+                    ),
+                ),
+                account("token_program", false, Some(Token2022::id()), None),
+                account(
+                    "associated_token_program",
+                    false,
+                    Some(AssociatedToken::id()),
+                    None,
+                ),
+                account(
+                    "external",
+                    false,
+                    None,
+                    pda(vec![literal(b"external")], Some(7))
+                ),
+            ],
+        })
+    );
+}
@@ -366,6 +366,30 @@ pub mod bench {
     pub fn unchecked_account8(_ctx: Context<UncheckedAccount8>) -> Result<()> {
         Ok(())
     }
+
+    pub fn account_large1(_ctx: Context<AccountLarge1>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn account_large2(_ctx: Context<AccountLarge2>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn account_large4(_ctx: Context<AccountLarge4>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn boxed_account_large1(_ctx: Context<BoxedAccountLarge1>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn boxed_account_large2(_ctx: Context<BoxedAccountLarge2>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn boxed_account_large4(_ctx: Context<BoxedAccountLarge4>) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[account]
@@ -381,6 +405,11 @@ pub struct Unsized {
     pub field: Vec<u8>,
 }
 
+#[account]
+pub struct Large {
+    pub field: [u8; 1024],
+}
+
 #[derive(Accounts)]
 pub struct AccountInfo1<'info> {
     pub account1: AccountInfo<'info>,
@@ -1212,3 +1241,41 @@ pub struct UncheckedAccount8<'info> {
     pub account7: UncheckedAccount<'info>,
     pub account8: UncheckedAccount<'info>,
 }
+
+#[derive(Accounts)]
+pub struct AccountLarge1<'info> {
+    pub account1: Account<'info, Large>,
+}
+
+#[derive(Accounts)]
+pub struct AccountLarge2<'info> {
+    pub account1: Account<'info, Large>,
+    pub account2: Account<'info, Large>,
+}
+
+#[derive(Accounts)]
+pub struct AccountLarge4<'info> {
+    pub account1: Account<'info, Large>,
+    pub account2: Account<'info, Large>,
+    pub account3: Account<'info, Large>,
+    pub account4: Account<'info, Large>,
+}
+
+#[derive(Accounts)]
+pub struct BoxedAccountLarge1<'info> {
+    pub account1: Box<Account<'info, Large>>,
+}
+
+#[derive(Accounts)]
+pub struct BoxedAccountLarge2<'info> {
+    pub account1: Box<Account<'info, Large>>,
+    pub account2: Box<Account<'info, Large>>,
+}
+
+#[derive(Accounts)]
+pub struct BoxedAccountLarge4<'info> {
+    pub account1: Box<Account<'info, Large>>,
+    pub account2: Box<Account<'info, Large>>,
+    pub account3: Box<Account<'info, Large>>,
+    pub account4: Box<Account<'info, Large>>,
+}
@@ -64,6 +64,11 @@ const INSTRUCTIONS: Instruction[] = [
     name: "account_unsized",
     accountType: "Account<'info, Unsized>",
   },
+  {
+    name: "account_large",
+    accountType: "Account<'info, Large>",
+    accountCounts: [1, 2, 4],
+  },
   {
     name: "boxed_account_empty_init",
     accountType: "Box<Account<'info, Empty>>",
@@ -99,6 +104,11 @@ const INSTRUCTIONS: Instruction[] = [
     name: "boxed_account_unsized",
     accountType: "Box<Account<'info, Unsized>>",
   },
+  {
+    name: "boxed_account_large",
+    accountType: "Box<Account<'info, Large>>",
+    accountCounts: [1, 2, 4],
+  },
   {
     name: "boxed_interface_account_mint",
     accountType: "Box<InterfaceAccount<'info, Mint>>",
@@ -25,6 +25,20 @@ mod composite {
 
         Ok(())
     }
+
+    pub fn composite_swap(
+        ctx: Context<CompositeSwap<DummyA, DummyB>>,
+        _dummy_a: u64,
+        _dummy_b: u64,
+    ) -> Result<()> {
+        let a = ctx.accounts.foo.dummy.data();
+        let b = ctx.accounts.bar.dummy.data();
+
+        ctx.accounts.foo.dummy.set_data(b);
+        ctx.accounts.bar.dummy.set_data(a);
+
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -53,6 +67,29 @@ pub struct Bar<'info> {
     pub dummy_b: Account<'info, DummyB>,
 }
 
+/// Composite accounts can be generic, and get their instruction arguments
+/// forwarded from the parent with `#[instruction(..)]`.
+#[derive(Accounts)]
+#[instruction(dummy_a: u64, dummy_b: u64)]
+pub struct CompositeSwap<'info, A: Dummy, B: Dummy> {
+    #[instruction(dummy_a)]
+    foo: Checked<'info, A>,
+    #[instruction(dummy_b)]
+    bar: Checked<'info, B>,
+}
+
+#[derive(Accounts)]
+#[instruction(expected: u64)]
+pub struct Checked<'info, T: Dummy> {
+    #[account(mut, constraint = dummy.data() == expected)]
+    pub dummy: Account<'info, T>,
+}
+
+pub trait Dummy: AccountSerialize + AccountDeserialize + Owner + Clone {
+    fn data(&self) -> u64;
+    fn set_data(&mut self, data: u64);
+}
+
 #[account]
 pub struct DummyA {
     pub data: u64,
@@ -62,3 +99,23 @@ pub struct DummyA {
 pub struct DummyB {
     pub data: u64,
 }
+
+impl Dummy for DummyA {
+    fn data(&self) -> u64 {
+        self.data
+    }
+
+    fn set_data(&mut self, data: u64) {
+        self.data = data;
+    }
+}
+
+impl Dummy for DummyB {
+    fn data(&self) -> u64 {
+        self.data
+    }
+
+    fn set_data(&mut self, data: u64) {
+        self.data = data;
+    }
+}
@@ -47,4 +47,55 @@ describe("composite", () => {
     assert.isTrue(dummyAAccount.data.eq(new anchor.BN(1234)));
     assert.isTrue(dummyBAccount.data.eq(new anchor.BN(4321)));
   });
+
+  it("Can use generic composites with forwarded args", async () => {
+    const program = anchor.workspace.Composite;
+
+    const dummyA = anchor.web3.Keypair.generate();
+    const dummyB = anchor.web3.Keypair.generate();
+
+    await program.rpc.initialize({
+      accounts: {
+        dummyA: dummyA.publicKey,
+        dummyB: dummyB.publicKey,
+      },
+      signers: [dummyA, dummyB],
+      instructions: [
+        await program.account.dummyA.createInstruction(dummyA),
+        await program.account.dummyB.createInstruction(dummyB),
+      ],
+    });
+    await program.rpc.compositeUpdate(new anchor.BN(1), new anchor.BN(2), {
+      accounts: {
+        foo: { dummyA: dummyA.publicKey },
+        bar: { dummyB: dummyB.publicKey },
+      },
+    });
+
+    await program.rpc.compositeSwap(new anchor.BN(1), new anchor.BN(2), {
+      accounts: {
+        foo: { dummy: dummyA.publicKey },
+        bar: { dummy: dummyB.publicKey },
+      },
+    });
+
+    const dummyAAccount = await program.account.dummyA.fetch(dummyA.publicKey);
+    const dummyBAccount = await program.account.dummyB.fetch(dummyB.publicKey);
+
+    assert.isTrue(dummyAAccount.data.eq(new anchor.BN(2)));
+    assert.isTrue(dummyBAccount.data.eq(new anchor.BN(1)));
+
+    try {
+      // The values are already swapped
+      await program.rpc.compositeSwap(new anchor.BN(1), new anchor.BN(2), {
+        accounts: {
+          foo: { dummy: dummyA.publicKey },
+          bar: { dummy: dummyB.publicKey },
+        },
+      });
+      assert.fail("expected the constraint to fail");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "ConstraintRaw");
+    }
+  });
 });
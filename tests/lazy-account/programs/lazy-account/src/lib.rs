@@ -36,6 +36,36 @@ pub mod lazy_account {
         *ctx.accounts.my_account.load_mut_authority()? = new_authority;
         Ok(())
     }
+
+    pub fn write_dynamic(ctx: Context<WriteDynamic>, len: u16) -> Result<()> {
+        // Changing the size of `dynamic` shifts the fields after it on exit
+        ctx.accounts
+            .my_account
+            .load_mut_dynamic()?
+            .truncate(len as usize);
+        *ctx.accounts.my_account.load_mut_counter()? += 1;
+        Ok(())
+    }
+
+    pub fn init_kinds(ctx: Context<InitKinds>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        *ctx.accounts.enum_account.load_mut()? = EnumAccount::Active { authority };
+        *ctx.accounts.generic_account.load_mut_value()? = 1;
+        *ctx.accounts.zero_copy_account.load_mut_authority()? = authority;
+        Ok(())
+    }
+
+    pub fn write_kinds(ctx: Context<WriteKinds>) -> Result<()> {
+        // Only the variant is read
+        require_eq!(ctx.accounts.enum_account.load_variant()?, 1);
+        *ctx.accounts.enum_account.load_mut()? = EnumAccount::Closed;
+
+        ctx.accounts.generic_account.load_mut_values()?.push(2);
+
+        // Zero-copy accounts are written in place
+        ctx.accounts.zero_copy_account.load_mut_data()?[0] = 3;
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -93,6 +123,56 @@ pub struct Write<'info> {
     pub stack_heavy_account: Account<'info, StackHeavyAccount>,
 }
 
+#[derive(Accounts)]
+pub struct WriteDynamic<'info> {
+    /// Only the `dynamic` and `counter` fields are written back to the account data, without
+    /// deserializing or serializing the other fields.
+    #[account(mut, seeds = [b"my_account"], bump)]
+    pub my_account: LazyAccount<'info, MyAccount>,
+}
+
+#[derive(Accounts)]
+pub struct InitKinds<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = EnumAccount::DISCRIMINATOR.len() + EnumAccount::INIT_SPACE,
+        seeds = [b"enum_account"],
+        bump
+    )]
+    pub enum_account: LazyAccount<'info, EnumAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = GenericAccount::<u64>::DISCRIMINATOR.len() + GenericAccount::<u64>::INIT_SPACE,
+        seeds = [b"generic_account"],
+        bump
+    )]
+    pub generic_account: LazyAccount<'info, GenericAccount<u64>>,
+    #[account(
+        init,
+        payer = authority,
+        space = ZeroCopyAccount::DISCRIMINATOR.len() + std::mem::size_of::<ZeroCopyAccount>(),
+        seeds = [b"zero_copy_account"],
+        bump
+    )]
+    pub zero_copy_account: LazyAccount<'info, ZeroCopyAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteKinds<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut, seeds = [b"enum_account"], bump)]
+    pub enum_account: LazyAccount<'info, EnumAccount>,
+    #[account(mut, seeds = [b"generic_account"], bump)]
+    pub generic_account: LazyAccount<'info, GenericAccount<u64>>,
+    #[account(mut, seeds = [b"zero_copy_account"], bump, has_one = authority)]
+    pub zero_copy_account: LazyAccount<'info, ZeroCopyAccount>,
+}
+
 const MAX_DATA_LEN: usize = 256;
 
 #[account]
@@ -104,6 +184,8 @@ pub struct MyAccount {
     /// Dynamic sized data also works, unlike `AccountLoader`
     #[max_len(MAX_DATA_LEN)]
     pub dynamic: Vec<Pubkey>,
+    /// Fixed size data after dynamic sized data
+    pub counter: u64,
 }
 
 /// Stack heavy filler account that imitates heavy stack usage caused my many accounts
@@ -112,3 +194,25 @@ pub struct MyAccount {
 pub struct StackHeavyAccount {
     pub data: [u8; 1600],
 }
+
+#[account]
+#[derive(InitSpace)]
+pub enum EnumAccount {
+    Uninitialized,
+    Active { authority: Pubkey },
+    Closed,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GenericAccount<T: AnchorSerialize + AnchorDeserialize + Clone + Space> {
+    pub value: T,
+    #[max_len(4)]
+    pub values: Vec<T>,
+}
+
+#[account(zero_copy)]
+pub struct ZeroCopyAccount {
+    pub authority: Pubkey,
+    pub data: [u64; 1024],
+}
@@ -33,4 +33,66 @@ describe("lazy-account", () => {
     const myAccount = await program.account.myAccount.fetch(pubkeys.myAccount);
     assert(myAccount.authority.equals(newAuthority));
   });
+
+  it("Can write dynamic", async () => {
+    const len = 3;
+    const { pubkeys, signature } = await program.methods
+      .writeDynamic(len)
+      .rpcAndKeys();
+    await program.provider.connection.confirmTransaction(
+      signature,
+      "confirmed"
+    );
+    const myAccount = await program.account.myAccount.fetch(pubkeys.myAccount);
+    assert.strictEqual(myAccount.dynamic.length, len);
+    assert.strictEqual(myAccount.counter.toNumber(), 1);
+    assert(myAccount.authority.equals(anchor.web3.PublicKey.default));
+  });
+
+  it("Can init enum, generic and zero-copy accounts", async () => {
+    const { pubkeys, signature } = await program.methods
+      .initKinds()
+      .rpcAndKeys();
+    await program.provider.connection.confirmTransaction(
+      signature,
+      "confirmed"
+    );
+    const enumAccount = await program.account.enumAccount.fetch(
+      pubkeys.enumAccount
+    );
+    assert(enumAccount.active?.authority.equals(program.provider.publicKey!));
+    const genericAccount = await program.account.genericAccount.fetch(
+      pubkeys.genericAccount
+    );
+    assert.strictEqual(genericAccount.value.toNumber(), 1);
+    const zeroCopyAccount = await program.account.zeroCopyAccount.fetch(
+      pubkeys.zeroCopyAccount
+    );
+    assert(zeroCopyAccount.authority.equals(program.provider.publicKey!));
+  });
+
+  it("Can write enum, generic and zero-copy accounts", async () => {
+    const { pubkeys, signature } = await program.methods
+      .writeKinds()
+      .rpcAndKeys();
+    await program.provider.connection.confirmTransaction(
+      signature,
+      "confirmed"
+    );
+    const enumAccount = await program.account.enumAccount.fetch(
+      pubkeys.enumAccount
+    );
+    assert(enumAccount.closed);
+    const genericAccount = await program.account.genericAccount.fetch(
+      pubkeys.genericAccount
+    );
+    assert.deepStrictEqual(
+      genericAccount.values.map((v) => v.toNumber()),
+      [2]
+    );
+    const zeroCopyAccount = await program.account.zeroCopyAccount.fetch(
+      pubkeys.zeroCopyAccount
+    );
+    assert.strictEqual(zeroCopyAccount.data[0].toNumber(), 3);
+  });
 });
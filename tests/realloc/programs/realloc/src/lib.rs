@@ -32,6 +32,14 @@ pub mod realloc {
             .resize_with(len as usize, Default::default);
         Ok(())
     }
+
+    pub fn realloc_auto(ctx: Context<ReallocAuto>, len: u16) -> Result<()> {
+        ctx.accounts
+            .sample
+            .data
+            .resize_with(len as usize, Default::default);
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -99,6 +107,23 @@ pub struct Realloc2<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ReallocAuto<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sample"],
+        bump = sample.bump,
+        realloc = auto,
+        realloc::payer = authority,
+    )]
+    pub sample: Account<'info, Sample>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[account]
 pub struct Sample {
     pub data: Vec<u8>,
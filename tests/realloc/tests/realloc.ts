@@ -68,6 +68,38 @@ describe("realloc", () => {
     assert.lengthOf(s.data, 1);
   });
 
+  it("realloc auto additive", async () => {
+    await program.methods
+      .reallocAuto(20)
+      .accounts({ authority: authority.publicKey, sample })
+      .rpc();
+
+    const s = await program.account.sample.fetch(sample);
+    assert.lengthOf(s.data, 20);
+    const info = await program.provider.connection.getAccountInfo(sample);
+    assert.strictEqual(info.data.length, 8 + (4 + 20) + 1);
+  });
+
+  it("realloc auto subtractive", async () => {
+    const before = await program.provider.connection.getAccountInfo(sample);
+    await program.methods
+      .reallocAuto(2)
+      .accounts({ authority: authority.publicKey, sample })
+      .rpc();
+
+    const s = await program.account.sample.fetch(sample);
+    assert.lengthOf(s.data, 2);
+    const info = await program.provider.connection.getAccountInfo(sample);
+    assert.strictEqual(info.data.length, 8 + (4 + 2) + 1);
+    // The excess rent is refunded to the payer.
+    const rent =
+      await program.provider.connection.getMinimumBalanceForRentExemption(
+        info.data.length
+      );
+    assert.strictEqual(info.lamports, rent);
+    assert.isBelow(info.lamports, before.lamports);
+  });
+
   it("fails with duplicate account reallocations", async () => {
     try {
       await program.methods
@@ -9,7 +9,8 @@ use anchor_spl::{
     },
     token_interface::{
         get_mint_extension_data, spl_token_metadata_interface::state::TokenMetadata,
-        token_metadata_initialize, Mint, Token2022, TokenAccount, TokenMetadataInitialize,
+        token_metadata_initialize, Mint, MintExtensions, Token2022, TokenAccount,
+        TokenMetadataInitialize,
     },
 };
 use spl_pod::optional_keys::OptionalNonZeroPubkey;
@@ -106,6 +107,7 @@ pub fn handler(ctx: Context<CreateMintAccount>, args: CreateMintAccountArgs) ->
     ctx.accounts.mint.reload()?;
     let mint_data = &mut ctx.accounts.mint.to_account_info();
     let metadata = get_mint_extensible_extension_data::<TokenMetadata>(mint_data)?;
+    assert_eq!(metadata, ctx.accounts.mint.token_metadata()?);
     assert_eq!(metadata.mint, ctx.accounts.mint.key());
     assert_eq!(metadata.name, args.name);
     assert_eq!(metadata.symbol, args.symbol);
@@ -131,8 +133,12 @@ pub fn handler(ctx: Context<CreateMintAccount>, args: CreateMintAccountArgs) ->
         close_authority.close_authority,
         OptionalNonZeroPubkey::try_from(authority_key)?
     );
-    let transfer_hook = get_mint_extension_data::<TransferHook>(mint_data)?;
+    let transfer_hook = ctx.accounts.mint.extension::<TransferHook>()?;
     let program_id: Option<Pubkey> = Some(ctx.program_id.key());
+    assert_eq!(
+        ctx.accounts.mint.transfer_hook_program_id()?,
+        Some(ctx.program_id.key())
+    );
     assert_eq!(
         transfer_hook.authority,
         OptionalNonZeroPubkey::try_from(authority_key)?
@@ -8,6 +8,10 @@ export const ANCHOR_ERROR__INSTRUCTION_FALLBACK_NOT_FOUND = 101;
 export const ANCHOR_ERROR__INSTRUCTION_DID_NOT_DESERIALIZE = 102;
 /** The program could not serialize the given instruction. */
 export const ANCHOR_ERROR__INSTRUCTION_DID_NOT_SERIALIZE = 103;
+/** The instruction can't be called via CPI. */
+export const ANCHOR_ERROR__INSTRUCTION_CALLED_VIA_CPI = 104;
+/** The instruction must be called via CPI from an allowed program. */
+export const ANCHOR_ERROR__INSTRUCTION_CALLER_NOT_ALLOWED = 105;
 
 // IDL instruction errors.
 
@@ -105,6 +109,16 @@ export const ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION = 2037;
 export const ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION_AUTHORITY = 2038;
 /** A transfer hook extension transfer hook program id constraint was violated. */
 export const ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION_PROGRAM_ID = 2039;
+/** An instructions constraint was violated, a required instruction is missing. */
+export const ANCHOR_ERROR__CONSTRAINT_INSTRUCTIONS_MISSING = 2040;
+/** An instructions constraint was violated, a forbidden instruction is present. */
+export const ANCHOR_ERROR__CONSTRAINT_INSTRUCTIONS_FORBIDDEN = 2041;
+/** An upgrade authority constraint was violated. */
+export const ANCHOR_ERROR__CONSTRAINT_UPGRADE_AUTHORITY = 2042;
+/** The same account was given for more than one mutable field. */
+export const ANCHOR_ERROR__CONSTRAINT_DUPLICATE_MUTABLE_ACCOUNT = 2043;
+/** Not enough of the expected signers signed the transaction. */
+export const ANCHOR_ERROR__CONSTRAINT_SIGNER_THRESHOLD = 2044;
 
 // Require errors.
 
@@ -161,6 +175,8 @@ export const ANCHOR_ERROR__ACCOUNT_SYSVAR_MISMATCH = 3015;
 export const ANCHOR_ERROR__ACCOUNT_REALLOC_EXCEEDS_LIMIT = 3016;
 /** The account was duplicated for more than one reallocation. */
 export const ANCHOR_ERROR__ACCOUNT_DUPLICATE_REALLOCS = 3017;
+/** The account was closed with a tombstone. */
+export const ANCHOR_ERROR__ACCOUNT_TOMBSTONED = 3018;
 
 // Miscellaneous errors.
 
@@ -170,6 +186,8 @@ export const ANCHOR_ERROR__DECLARED_PROGRAM_ID_MISMATCH = 4100;
 export const ANCHOR_ERROR__TRYING_TO_INIT_PAYER_AS_PROGRAM_ACCOUNT = 4101;
 /** The program could not perform the numeric conversion, out of range integral type conversion attempted. */
 export const ANCHOR_ERROR__INVALID_NUMERIC_CONVERSION = 4102;
+/** No preceding signature verification instruction was found for the signer and message. */
+export const ANCHOR_ERROR__SIGNATURE_NOT_VERIFIED = 4103;
 
 // Deprecated errors.
 
@@ -182,6 +200,8 @@ export type AnchorErrorCode =
   | typeof ANCHOR_ERROR__INSTRUCTION_FALLBACK_NOT_FOUND
   | typeof ANCHOR_ERROR__INSTRUCTION_DID_NOT_DESERIALIZE
   | typeof ANCHOR_ERROR__INSTRUCTION_DID_NOT_SERIALIZE
+  | typeof ANCHOR_ERROR__INSTRUCTION_CALLED_VIA_CPI
+  | typeof ANCHOR_ERROR__INSTRUCTION_CALLER_NOT_ALLOWED
   | typeof ANCHOR_ERROR__IDL_INSTRUCTION_STUB
   | typeof ANCHOR_ERROR__IDL_INSTRUCTION_INVALID_PROGRAM
   | typeof ANCHOR_ERROR__IDL_ACCOUNT_NOT_EMPTY
@@ -226,6 +246,11 @@ export type AnchorErrorCode =
   | typeof ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION
   | typeof ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION_AUTHORITY
   | typeof ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION_PROGRAM_ID
+  | typeof ANCHOR_ERROR__CONSTRAINT_INSTRUCTIONS_MISSING
+  | typeof ANCHOR_ERROR__CONSTRAINT_INSTRUCTIONS_FORBIDDEN
+  | typeof ANCHOR_ERROR__CONSTRAINT_UPGRADE_AUTHORITY
+  | typeof ANCHOR_ERROR__CONSTRAINT_DUPLICATE_MUTABLE_ACCOUNT
+  | typeof ANCHOR_ERROR__CONSTRAINT_SIGNER_THRESHOLD
   | typeof ANCHOR_ERROR__REQUIRE_VIOLATED
   | typeof ANCHOR_ERROR__REQUIRE_EQ_VIOLATED
   | typeof ANCHOR_ERROR__REQUIRE_KEYS_EQ_VIOLATED
@@ -251,7 +276,9 @@ export type AnchorErrorCode =
   | typeof ANCHOR_ERROR__ACCOUNT_SYSVAR_MISMATCH
   | typeof ANCHOR_ERROR__ACCOUNT_REALLOC_EXCEEDS_LIMIT
   | typeof ANCHOR_ERROR__ACCOUNT_DUPLICATE_REALLOCS
+  | typeof ANCHOR_ERROR__ACCOUNT_TOMBSTONED
   | typeof ANCHOR_ERROR__DECLARED_PROGRAM_ID_MISMATCH
   | typeof ANCHOR_ERROR__TRYING_TO_INIT_PAYER_AS_PROGRAM_ACCOUNT
   | typeof ANCHOR_ERROR__INVALID_NUMERIC_CONVERSION
+  | typeof ANCHOR_ERROR__SIGNATURE_NOT_VERIFIED
   | typeof ANCHOR_ERROR__DEPRECATED;
@@ -315,6 +315,9 @@ export const LangErrorCode = {
     errors.ANCHOR_ERROR__INSTRUCTION_DID_NOT_DESERIALIZE,
   InstructionDidNotSerialize:
     errors.ANCHOR_ERROR__INSTRUCTION_DID_NOT_SERIALIZE,
+  InstructionCalledViaCpi: errors.ANCHOR_ERROR__INSTRUCTION_CALLED_VIA_CPI,
+  InstructionCallerNotAllowed:
+    errors.ANCHOR_ERROR__INSTRUCTION_CALLER_NOT_ALLOWED,
 
   // IDL instructions.
   IdlInstructionStub: errors.ANCHOR_ERROR__IDL_INSTRUCTION_STUB,
@@ -387,6 +390,14 @@ export const LangErrorCode = {
     errors.ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION_AUTHORITY,
   ConstraintMintTransferHookExtensionProgramId:
     errors.ANCHOR_ERROR__CONSTRAINT_MINT_TRANSFER_HOOK_EXTENSION_PROGRAM_ID,
+  ConstraintInstructionsMissing:
+    errors.ANCHOR_ERROR__CONSTRAINT_INSTRUCTIONS_MISSING,
+  ConstraintInstructionsForbidden:
+    errors.ANCHOR_ERROR__CONSTRAINT_INSTRUCTIONS_FORBIDDEN,
+  ConstraintUpgradeAuthority: errors.ANCHOR_ERROR__CONSTRAINT_UPGRADE_AUTHORITY,
+  ConstraintDuplicateMutableAccount:
+    errors.ANCHOR_ERROR__CONSTRAINT_DUPLICATE_MUTABLE_ACCOUNT,
+  ConstraintSignerThreshold: errors.ANCHOR_ERROR__CONSTRAINT_SIGNER_THRESHOLD,
 
   // Require.
   RequireViolated: errors.ANCHOR_ERROR__REQUIRE_VIOLATED,
@@ -422,12 +433,14 @@ export const LangErrorCode = {
   AccountReallocExceedsLimit:
     errors.ANCHOR_ERROR__ACCOUNT_REALLOC_EXCEEDS_LIMIT,
   AccountDuplicateReallocs: errors.ANCHOR_ERROR__ACCOUNT_DUPLICATE_REALLOCS,
+  AccountTombstoned: errors.ANCHOR_ERROR__ACCOUNT_TOMBSTONED,
 
   // Miscellaneous
   DeclaredProgramIdMismatch: errors.ANCHOR_ERROR__DECLARED_PROGRAM_ID_MISMATCH,
   TryingToInitPayerAsProgramAccount:
     errors.ANCHOR_ERROR__TRYING_TO_INIT_PAYER_AS_PROGRAM_ACCOUNT,
   InvalidNumericConversion: errors.ANCHOR_ERROR__INVALID_NUMERIC_CONVERSION,
+  SignatureNotVerified: errors.ANCHOR_ERROR__SIGNATURE_NOT_VERIFIED,
 
   // Used for APIs that shouldn't be used anymore.
   Deprecated: errors.ANCHOR_ERROR__DEPRECATED,
@@ -448,6 +461,14 @@ export const LangErrorMessage = new Map<number, string>([
     LangErrorCode.InstructionDidNotSerialize,
     "The program could not serialize the given instruction",
   ],
+  [
+    LangErrorCode.InstructionCalledViaCpi,
+    "The instruction can't be called via CPI",
+  ],
+  [
+    LangErrorCode.InstructionCallerNotAllowed,
+    "The instruction must be called via CPI from an allowed program",
+  ],
 
   // Idl instructions.
   [
@@ -588,6 +609,26 @@ export const LangErrorMessage = new Map<number, string>([
     LangErrorCode.ConstraintMintTransferHookExtensionProgramId,
     "A transfer hook extension transfer hook program id constraint was violated",
   ],
+  [
+    LangErrorCode.ConstraintInstructionsMissing,
+    "An instructions constraint was violated, a required instruction is missing",
+  ],
+  [
+    LangErrorCode.ConstraintInstructionsForbidden,
+    "An instructions constraint was violated, a forbidden instruction is present",
+  ],
+  [
+    LangErrorCode.ConstraintUpgradeAuthority,
+    "An upgrade authority constraint was violated",
+  ],
+  [
+    LangErrorCode.ConstraintDuplicateMutableAccount,
+    "The same account was given for more than one mutable field",
+  ],
+  [
+    LangErrorCode.ConstraintSignerThreshold,
+    "Not enough of the expected signers signed the transaction",
+  ],
 
   // Require.
   [LangErrorCode.RequireViolated, "A require expression was violated"],
@@ -659,6 +700,7 @@ export const LangErrorMessage = new Map<number, string>([
     LangErrorCode.AccountDuplicateReallocs,
     "The account was duplicated for more than one reallocation",
   ],
+  [LangErrorCode.AccountTombstoned, "The account was closed with a tombstone"],
 
   // Miscellaneous
   [
@@ -673,6 +715,10 @@ export const LangErrorMessage = new Map<number, string>([
     LangErrorCode.InvalidNumericConversion,
     "The program could not perform the numeric conversion, out of range integral type conversion attempted",
   ],
+  [
+    LangErrorCode.SignatureNotVerified,
+    "No preceding signature verification instruction was found for the signer and message",
+  ],
 
   // Deprecated
   [
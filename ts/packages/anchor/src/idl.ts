@@ -45,8 +45,36 @@ export type IdlInstruction = {
   accounts: IdlInstructionAccountItem[];
   args: IdlField[];
   returns?: IdlType;
+  caller?: IdlInstructionCaller;
+  transferHook?: IdlTransferHook;
 };
 
+export type IdlInstructionCaller =
+  | { kind: "transaction" }
+  | { kind: "cpi"; programs: string[] };
+
+export type IdlTransferHook = {
+  accounts: IdlExtraAccount[];
+};
+
+export type IdlExtraAccount = {
+  name: string;
+  writable?: boolean;
+  signer?: boolean;
+  address?: string;
+  pda?: IdlExtraAccountPda;
+};
+
+export type IdlExtraAccountPda = {
+  seeds: IdlExtraAccountSeed[];
+  program?: number;
+};
+
+export type IdlExtraAccountSeed =
+  | { kind: "const"; value: number[] }
+  | { kind: "data"; index: number; length: number }
+  | { kind: "account"; index: number };
+
 export type IdlInstructionAccountItem =
   | IdlInstructionAccount
   | IdlInstructionAccounts;
@@ -60,8 +88,28 @@ export type IdlInstructionAccount = {
   address?: string;
   pda?: IdlPda;
   relations?: string[];
+  remaining?: IdlRemainingAccounts;
+  signers?: IdlSigners;
+};
+
+export type IdlRemainingAccounts = {
+  len?: IdlRemainingAccountsLen;
+};
+
+export type IdlRemainingAccountsLen =
+  | { kind: "const"; value: number }
+  | { kind: "arg"; path: string };
+
+export type IdlSigners = {
+  path: string;
+  threshold?: IdlSignersThreshold;
 };
 
+export type IdlSignersThreshold =
+  | { kind: "const"; value: number }
+  | { kind: "arg"; path: string }
+  | { kind: "account"; path: string };
+
 export type IdlInstructionAccounts = {
   name: string;
   accounts: IdlInstructionAccount[];
@@ -110,6 +158,7 @@ export type IdlErrorCode = {
   name: string;
   code: number;
   msg?: string;
+  fields?: IdlDefinedFields;
 };
 
 export type IdlField = {
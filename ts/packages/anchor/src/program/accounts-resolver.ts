@@ -28,13 +28,13 @@ import {
 } from "./namespace/methods";
 
 export type AccountsGeneric = {
-  [name: string]: PublicKey | AccountsGeneric;
+  [name: string]: PublicKey | PublicKey[] | AccountsGeneric;
 };
 
 export function isAccountsGeneric(
-  accounts: PublicKey | AccountsGeneric
+  accounts: PublicKey | PublicKey[] | AccountsGeneric
 ): accounts is AccountsGeneric {
-  return !(accounts instanceof PublicKey);
+  return !(accounts instanceof PublicKey) && !Array.isArray(accounts);
 }
 
 export type CustomAccountResolver<IDL extends Idl> = (params: {
@@ -186,6 +186,10 @@ export class AccountsResolver<IDL extends Idl> {
             true
           );
         }
+      } else if (Array.isArray(partialAccount)) {
+        // remaining accounts are given as a list
+        nestedAccountsGeneric[accountName] =
+          partialAccount.map(translateAddress);
       } else {
         // if not compound accounts, do null/optional check and proceed
         if (partialAccount !== null) {
@@ -273,7 +277,8 @@ export class AccountsResolver<IDL extends Idl> {
       const name = accountOrAccounts.name;
       if (isCompositeAccounts(accountOrAccounts)) {
         this.resolveConst(accountOrAccounts.accounts, [...path, name]);
-      } else {
+      } else if (!accountOrAccounts.remaining) {
+        // Remaining accounts can't be resolved
         const account = accountOrAccounts;
 
         if ((account.signer || account.address) && !this.get([...path, name])) {
@@ -308,7 +313,7 @@ export class AccountsResolver<IDL extends Idl> {
           accountOrAccounts.accounts,
           [...path, name]
         );
-      } else {
+      } else if (!accountOrAccounts.remaining) {
         const account = accountOrAccounts;
         if ((account.pda || account.relations) && !this.get([...path, name])) {
           found++;
@@ -9,6 +9,7 @@ import {
   IdlInstructionAccountItem,
   IdlInstructionAccounts,
   IdlInstruction,
+  IdlRemainingAccounts,
 } from "../idl.js";
 
 /**
@@ -74,6 +75,8 @@ export type Accounts<
 type Account<A extends IdlInstructionAccountItem> =
   A extends IdlInstructionAccounts
     ? Accounts<A["accounts"][number]>
+    : A extends { remaining: IdlRemainingAccounts }
+    ? Address[]
     : A extends { optional: true }
     ? Address | null
     : A extends { signer: true }
@@ -96,6 +96,26 @@ export default class InstructionNamespaceFactory {
           ).flat();
         }
 
+        // Remaining accounts are given as a list of addresses
+        if (acc.remaining) {
+          const addresses = ctx[acc.name];
+          if (!Array.isArray(addresses)) {
+            throw new Error(
+              `Wrong input type for account "${
+                acc.name
+              }" in the instruction accounts object${
+                ixName !== undefined ? ' for instruction "' + ixName + '"' : ""
+              }. Expected an array of PublicKey or string.`
+            );
+          }
+
+          return addresses.map((address: Address) => ({
+            pubkey: translateAddress(address),
+            isWritable: Boolean(acc.writable),
+            isSigner: Boolean(acc.signer),
+          }));
+        }
+
         let pubkey: PublicKey;
         try {
           pubkey = translateAddress(ctx[acc.name] as Address);
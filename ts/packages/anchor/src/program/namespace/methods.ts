@@ -12,6 +12,7 @@ import {
   IdlInstructionAccount,
   IdlInstructionAccountItem,
   IdlInstructionAccounts,
+  IdlRemainingAccounts,
   IdlTypeDef,
 } from "../../idl.js";
 import Provider from "../../provider.js";
@@ -86,6 +87,8 @@ type ResolvedAccount<
   A extends IdlInstructionAccountItem = IdlInstructionAccountItem
 > = A extends IdlInstructionAccounts
   ? ResolvedAccountsRecursive<A["accounts"][number]>
+  : A extends { remaining: IdlRemainingAccounts }
+  ? Address[]
   : A extends NonNullable<Pick<IdlInstructionAccount, "address">>
   ? never
   : A extends NonNullable<Pick<IdlInstructionAccount, "pda">>
@@ -117,6 +120,8 @@ type PartialAccount<
   A extends IdlInstructionAccountItem = IdlInstructionAccountItem
 > = A extends IdlInstructionAccounts
   ? PartialAccounts<A["accounts"][number]>
+  : A extends { remaining: IdlRemainingAccounts }
+  ? Address[]
   : A extends { optional: true }
   ? Address | null
   : Address;
@@ -127,6 +132,7 @@ export function isPartialAccounts(
   return (
     typeof partialAccount === "object" &&
     partialAccount !== null &&
+    !Array.isArray(partialAccount) && // Ensures not a list of remaining accounts
     !("_bn" in partialAccount) // Ensures not a pubkey
   );
 }
@@ -147,6 +153,8 @@ export function flattenPartialAccounts<A extends IdlInstructionAccountItem>(
     }
     toReturn[accountName] = isPartialAccounts(account)
       ? flattenPartialAccounts(account, true)
+      : Array.isArray(account)
+      ? account.map(translateAddress)
       : translateAddress(account);
   }
   return toReturn;
@@ -15,6 +15,7 @@ import {
   IdlDefinedFieldsNamed,
   IdlDefinedFieldsTuple,
   IdlArrayLen,
+  IdlRemainingAccounts,
 } from "../../idl";
 import { Accounts, Context } from "../context";
 import { MethodsBuilder } from "./methods";
@@ -112,6 +113,8 @@ type InstructionAccountsAddresses<
 type InstructionAccountsAddress<A extends IdlInstructionAccountItem> =
   A extends IdlInstructionAccounts
     ? InstructionAccountsAddresses<A["accounts"][number]>
+    : A extends { remaining: IdlRemainingAccounts }
+    ? PublicKey[]
     : PublicKey;
 
 export type MethodsFn<